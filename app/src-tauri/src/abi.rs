@@ -0,0 +1,860 @@
+//! Solidity ABI encoding and decoding over the JSON ABI stored in `contracts.abi`.
+//!
+//! Arguments come from the JSON a transaction is saved with: integers may be JSON
+//! numbers or decimal/hex strings, `bytes` are hex, arrays are JSON arrays and
+//! tuples are either arrays or objects keyed by component name. Nested values may
+//! also be given as JSON text, which is what form fields hold. Decoded values use
+//! the same shapes, with integers as decimal strings so they survive JavaScript.
+
+use ethers::abi::token::{LenientTokenizer, Tokenizer};
+use ethers::abi::ethabi::AbiError;
+use ethers::abi::{Constructor, Event, EventParam, Function, Param, ParamType, RawLog, Token};
+use ethers::types::{I256, U256};
+use ethers::utils::to_checksum;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::error::{CocoError, Result};
+use crate::types::{ContractArg, DecodedEvent, FunctionDiff, Log, RevertInfo};
+
+/// Selector of the built-in `Error(string)` revert
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of the built-in `Panic(uint256)` revert
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// A contract ABI that keeps the tuple component names `ethers` drops, so
+/// structs can be passed and returned as JSON objects
+#[derive(Debug, Clone, Default)]
+pub struct ContractAbi {
+    constructor: Option<Entry<Constructor>>,
+    functions: Vec<Entry<Function>>,
+    events: Vec<Entry<Event>>,
+    errors: Vec<Entry<AbiError>>,
+}
+
+/// An ABI item along with the JSON shape of its parameters
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    item: T,
+    inputs: Vec<ParamSpec>,
+    outputs: Vec<ParamSpec>,
+}
+
+/// Parameter names as written in the ABI JSON, including tuple components
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ParamSpec {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    components: Vec<ParamSpec>,
+}
+
+#[derive(Deserialize)]
+struct ItemSpec {
+    /// Entries without a type are functions
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    inputs: Vec<ParamSpec>,
+    #[serde(default)]
+    outputs: Vec<ParamSpec>,
+}
+
+/// A decoded revert: `Error(string)`, `Panic(uint256)` or a custom error from the ABI
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedError {
+    pub name: String,
+    pub args: Vec<ContractArg>,
+}
+
+impl fmt::Display for DecodedError {
+    /// `Error(string)` shows just the reason, panics their cause, and custom
+    /// errors read like the call that raised them: `Name(arg, ...)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| match &arg.value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+
+        match self.name.as_str() {
+            "Error" => write!(f, "{}", values.join(", ")),
+            "Panic" => {
+                let code = values.first().map(String::as_str).unwrap_or_default();
+                write!(f, "Panic({}): {}", code, panic_description(code))
+            }
+            name => write!(f, "{}({})", name, values.join(", ")),
+        }
+    }
+}
+
+/// Meaning of a Solidity `Panic(uint256)` code
+fn panic_description(code: &str) -> &'static str {
+    match code {
+        "0x1" => "assertion failed",
+        "0x11" => "arithmetic underflow or overflow",
+        "0x12" => "division or modulo by zero",
+        "0x21" => "invalid enum value",
+        "0x22" => "corrupt storage byte array",
+        "0x31" => "pop on empty array",
+        "0x32" => "array index out of bounds",
+        "0x41" => "out of memory",
+        "0x51" => "call to uninitialized function",
+        _ => "unknown panic",
+    }
+}
+
+/// Describes revert data, trying the built-in errors and then each ABI's
+/// custom errors in turn
+pub fn revert_info(data: &[u8], abis: &[ContractAbi]) -> RevertInfo {
+    let decoded = abis
+        .iter()
+        .find_map(|abi| abi.decode_error(data))
+        .or_else(|| ContractAbi::default().decode_error(data));
+
+    let hex_data = (!data.is_empty()).then(|| format!("0x{}", hex::encode(data)));
+    match decoded {
+        Some(error) => RevertInfo {
+            data: hex_data,
+            message: error.to_string(),
+            error_name: Some(error.name),
+            args: error.args,
+        },
+        None if data.len() >= 4 => RevertInfo {
+            data: hex_data,
+            message: format!("execution reverted with unknown error 0x{}", hex::encode(&data[..4])),
+            ..Default::default()
+        },
+        None => RevertInfo {
+            data: hex_data,
+            message: "execution reverted".to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+impl ContractAbi {
+    /// Parses a JSON ABI, either a bare array or a build artifact with an `abi` field
+    pub fn parse(json: &[u8]) -> Result<Self> {
+        let value: Value = serde_json::from_slice(json)
+            .map_err(|e| CocoError::Serialization(format!("Invalid ABI JSON: {}", e)))?;
+        let entries = match value {
+            Value::Array(entries) => entries,
+            Value::Object(mut artifact) => match artifact.remove("abi") {
+                Some(Value::Array(entries)) => entries,
+                _ => return Err(CocoError::Serialization("Artifact has no ABI array".into())),
+            },
+            _ => return Err(CocoError::Serialization("ABI must be a JSON array".into())),
+        };
+
+        let mut abi = Self::default();
+        for entry in entries {
+            let spec: ItemSpec = serde_json::from_value(entry.clone())?;
+            match spec.kind.as_deref().unwrap_or("function") {
+                "function" => abi.functions.push(Entry {
+                    item: parse_item(entry)?,
+                    inputs: spec.inputs,
+                    outputs: spec.outputs,
+                }),
+                "event" => {
+                    let mut event: Event = parse_item(entry)?;
+                    // Logs are decoded by parameter name, so unnamed ones need distinct names
+                    let mut inputs = spec.inputs;
+                    for (i, (param, spec)) in event.inputs.iter_mut().zip(&mut inputs).enumerate() {
+                        if param.name.is_empty() {
+                            param.name = format!("arg{}", i);
+                            spec.name = param.name.clone();
+                        }
+                    }
+                    abi.events.push(Entry { item: event, inputs, outputs: vec![] });
+                }
+                "error" => abi.errors.push(Entry {
+                    item: parse_item(entry)?,
+                    inputs: spec.inputs,
+                    outputs: vec![],
+                }),
+                "constructor" => {
+                    abi.constructor = Some(Entry {
+                        item: parse_item(entry)?,
+                        inputs: spec.inputs,
+                        outputs: vec![],
+                    })
+                }
+                // Fallback and receive have nothing to call by name
+                _ => {}
+            }
+        }
+        Ok(abi)
+    }
+
+    /// Wraps a single function, e.g. one parsed from a human-readable signature.
+    /// Its tuples have no component names, so they are passed as arrays.
+    pub fn from_function(function: Function) -> Self {
+        let specs = |params: &[ethers::abi::Param]| {
+            params
+                .iter()
+                .map(|p| ParamSpec {
+                    name: p.name.clone(),
+                    components: vec![],
+                })
+                .collect()
+        };
+        Self {
+            functions: vec![Entry {
+                inputs: specs(&function.inputs),
+                outputs: specs(&function.outputs),
+                item: function,
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Finds a function by name, preferring the overload whose arity matches the args
+    fn function(&self, name: &str, arg_count: usize) -> Result<&Entry<Function>> {
+        let mut overloads = self.functions.iter().filter(|f| f.item.name == name).peekable();
+        let first = overloads
+            .peek()
+            .copied()
+            .ok_or_else(|| CocoError::NotFound(format!("Function not found in ABI: {}", name)))?;
+        Ok(overloads
+            .find(|f| f.item.inputs.len() == arg_count)
+            .unwrap_or(first))
+    }
+
+    /// Encodes calldata for a function, matching args by input name, then `argN`, then position
+    pub fn encode_call(&self, function: &str, args: &[ContractArg]) -> Result<Vec<u8>> {
+        let entry = self.function(function, args.len())?;
+        let tokens = tokenize_args(&entry.item.inputs, &entry.inputs, args, function)?;
+
+        entry
+            .item
+            .encode_input(&tokens)
+            .map_err(|e| CocoError::Serialization(format!("Failed to encode call: {}", e)))
+    }
+
+    /// Encodes constructor arguments, to be appended to the creation bytecode.
+    /// Contracts without a constructor take no arguments.
+    pub fn encode_constructor(&self, args: &[ContractArg]) -> Result<Vec<u8>> {
+        let Some(entry) = &self.constructor else {
+            if !args.is_empty() {
+                return Err(CocoError::Validation(
+                    "Contract has no constructor but arguments were given".into(),
+                ));
+            }
+            return Ok(vec![]);
+        };
+
+        let tokens = tokenize_args(&entry.item.inputs, &entry.inputs, args, "constructor")?;
+        Ok(ethers::abi::encode(&tokens))
+    }
+
+    /// Whether the ABI has a function with this name
+    pub fn has_function(&self, name: &str) -> bool {
+        self.functions.iter().any(|f| f.item.name == name)
+    }
+
+    /// Every overload of each function, as signature, mutability and input names,
+    /// since saved transactions pass args by name
+    fn function_shapes(&self) -> BTreeMap<&str, BTreeSet<String>> {
+        let mut shapes: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for entry in &self.functions {
+            let names: Vec<&str> = entry.inputs.iter().map(|p| p.name.as_str()).collect();
+            shapes.entry(entry.item.name.as_str()).or_default().insert(format!(
+                "{} {:?} {}",
+                entry.item.signature(),
+                entry.item.state_mutability,
+                names.join(",")
+            ));
+        }
+        shapes
+    }
+
+    /// Functions added, removed or changed in a newer version of this ABI, by name
+    pub fn diff_functions(&self, newer: &ContractAbi) -> FunctionDiff {
+        let before = self.function_shapes();
+        let after = newer.function_shapes();
+        let mut diff = FunctionDiff::default();
+
+        for (name, shapes) in &after {
+            match before.get(name) {
+                None => diff.added.push(name.to_string()),
+                Some(old) if old != shapes => diff.changed.push(name.to_string()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = before
+            .keys()
+            .filter(|name| !after.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+        diff
+    }
+
+    /// Decodes a function's return data: an object when every output is named,
+    /// otherwise an array in output order
+    pub fn decode_output(&self, function: &str, arg_count: usize, data: &[u8]) -> Result<Value> {
+        let entry = self.function(function, arg_count)?;
+        let tokens = entry
+            .item
+            .decode_output(data)
+            .map_err(|e| CocoError::Serialization(format!("Failed to decode {} output: {}", function, e)))?;
+        Ok(params_to_json(&tokens, &entry.outputs))
+    }
+
+    /// Decodes revert data as `Error(string)`, `Panic(uint256)` or one of the
+    /// ABI's custom errors. Returns `None` for empty or unrecognised data.
+    pub fn decode_error(&self, data: &[u8]) -> Option<DecodedError> {
+        let (selector, body) = data.split_first_chunk::<4>()?;
+
+        if *selector == ERROR_SELECTOR {
+            let tokens = ethers::abi::decode(&[ParamType::String], body).ok()?;
+            return Some(DecodedError {
+                name: "Error".to_string(),
+                args: vec![ContractArg {
+                    name: "reason".to_string(),
+                    value: token_to_json(&tokens[0], &ParamSpec::default()),
+                }],
+            });
+        }
+        if *selector == PANIC_SELECTOR {
+            let tokens = ethers::abi::decode(&[ParamType::Uint(256)], body).ok()?;
+            let code = tokens[0].clone().into_uint()?;
+            return Some(DecodedError {
+                name: "Panic".to_string(),
+                args: vec![ContractArg {
+                    name: "code".to_string(),
+                    value: Value::String(format!("{:#x}", code)),
+                }],
+            });
+        }
+
+        let entry = self
+            .errors
+            .iter()
+            .find(|e| e.item.signature().as_bytes()[..4] == *selector)?;
+        let tokens = entry.item.decode(body).ok()?;
+        Some(DecodedError {
+            name: entry.item.name.clone(),
+            args: tokens
+                .iter()
+                .enumerate()
+                .map(|(i, token)| {
+                    let spec = param_spec(&entry.inputs, i);
+                    ContractArg {
+                        name: spec.name.clone(),
+                        value: token_to_json(token, spec),
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Decodes the logs matching one of the ABI's events into named args.
+    /// Logs are matched by signature alone, so events with the same signature
+    /// from other contracts are decoded too; anonymous events are skipped.
+    pub fn decode_logs(&self, logs: &[Log]) -> Result<Vec<DecodedEvent>> {
+        let mut decoded = Vec::new();
+
+        for log in logs {
+            let topics = log
+                .topics
+                .iter()
+                .map(|t| {
+                    t.parse()
+                        .map_err(|_| CocoError::Validation(format!("Invalid log topic: {}", t)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let Some(topic0) = topics.first() else {
+                continue;
+            };
+            let Some(entry) = self
+                .events
+                .iter()
+                .find(|e| !e.item.anonymous && e.item.signature() == *topic0)
+            else {
+                continue;
+            };
+
+            let parsed = entry
+                .item
+                .parse_log(RawLog {
+                    topics: topics.clone(),
+                    data: log.data.clone(),
+                })
+                .map_err(|e| CocoError::Serialization(format!("Failed to decode log: {}", e)))?;
+
+            let args = parsed
+                .params
+                .iter()
+                .zip(&entry.item.inputs)
+                .enumerate()
+                .map(|(i, (param, input))| (param.name.clone(), event_param_to_json(&param.value, input, param_spec(&entry.inputs, i))))
+                .collect::<serde_json::Map<_, _>>();
+
+            decoded.push(DecodedEvent {
+                name: entry.item.name.clone(),
+                args: Value::Object(args),
+            });
+        }
+
+        Ok(decoded)
+    }
+}
+
+fn parse_item<T: serde::de::DeserializeOwned>(entry: Value) -> Result<T> {
+    serde_json::from_value(entry).map_err(|e| CocoError::Serialization(format!("Invalid ABI entry: {}", e)))
+}
+
+/// The spec for parameter `i`, or an unnamed one when the ABI omitted it
+fn param_spec(specs: &[ParamSpec], i: usize) -> &ParamSpec {
+    static UNNAMED: ParamSpec = ParamSpec {
+        name: String::new(),
+        components: Vec::new(),
+    };
+    specs.get(i).unwrap_or(&UNNAMED)
+}
+
+/// Tokenizes call arguments, matching them to inputs by name, then `argN`.
+/// Positional args, all unnamed or keyed `argN`, also fall back to their order;
+/// named args never do, so a missing one is reported rather than misplaced.
+fn tokenize_args(
+    inputs: &[Param],
+    specs: &[ParamSpec],
+    args: &[ContractArg],
+    context: &str,
+) -> Result<Vec<Token>> {
+    let positional = args.iter().all(|a| a.name.is_empty() || is_positional_name(&a.name));
+
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let arg = args
+                .iter()
+                .find(|a| !input.name.is_empty() && a.name == input.name)
+                .or_else(|| args.iter().find(|a| a.name == format!("arg{}", i)))
+                .or_else(|| args.get(i).filter(|_| positional))
+                .ok_or_else(|| {
+                    let name = if input.name.is_empty() { format!("arg{}", i) } else { input.name.clone() };
+                    CocoError::Validation(format!("Missing argument {} for {}", name, context))
+                })?;
+            tokenize(&input.kind, param_spec(specs, i), &arg.value).map_err(|e| {
+                CocoError::Validation(format!("Invalid value for {}: {}", input.name, e))
+            })
+        })
+        .collect()
+}
+
+/// Whether an arg name is the `argN` key a positional payload is stored under
+fn is_positional_name(name: &str) -> bool {
+    name.strip_prefix("arg")
+        .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Converts a JSON argument into a token of the given type
+fn tokenize(kind: &ParamType, spec: &ParamSpec, value: &Value) -> std::result::Result<Token, String> {
+    match kind {
+        ParamType::Array(inner) => json_array(value)?
+            .iter()
+            .map(|item| tokenize(inner, spec, item))
+            .collect::<std::result::Result<_, _>>()
+            .map(Token::Array),
+        ParamType::FixedArray(inner, len) => {
+            let items = json_array(value)?;
+            if items.len() != *len {
+                return Err(format!("expected {} items, got {}", len, items.len()));
+            }
+            items
+                .iter()
+                .map(|item| tokenize(inner, spec, item))
+                .collect::<std::result::Result<_, _>>()
+                .map(Token::FixedArray)
+        }
+        ParamType::Tuple(kinds) => {
+            let items = match nested_json(value)? {
+                Value::Array(items) => items,
+                Value::Object(mut fields) => {
+                    if spec.components.len() != kinds.len() {
+                        return Err("tuple components have no names; pass an array".into());
+                    }
+                    spec.components
+                        .iter()
+                        .map(|c| fields.remove(&c.name).ok_or_else(|| format!("missing field {}", c.name)))
+                        .collect::<std::result::Result<_, _>>()?
+                }
+                _ => return Err("expected an array or object".into()),
+            };
+            if items.len() != kinds.len() {
+                return Err(format!("expected {} tuple fields, got {}", kinds.len(), items.len()));
+            }
+            kinds
+                .iter()
+                .zip(&items)
+                .enumerate()
+                .map(|(i, (kind, item))| tokenize(kind, param_spec(&spec.components, i), item))
+                .collect::<std::result::Result<_, _>>()
+                .map(Token::Tuple)
+        }
+        ParamType::Bytes => decode_hex(value).map(Token::Bytes),
+        ParamType::FixedBytes(len) => {
+            let bytes = decode_hex(value)?;
+            if bytes.len() != *len {
+                return Err(format!("expected {} bytes, got {}", len, bytes.len()));
+            }
+            Ok(Token::FixedBytes(bytes))
+        }
+        ParamType::Uint(bits) => {
+            let text = json_text(value);
+            let n = match text.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).map_err(|e| e.to_string())?,
+                None => match LenientTokenizer::tokenize(kind, &text).map_err(|e| e.to_string())? {
+                    Token::Uint(n) => n,
+                    other => return Err(format!("expected an unsigned integer, got {}", other)),
+                },
+            };
+            if n.bits() > *bits {
+                return Err(format!("{} does not fit in uint{}", n, bits));
+            }
+            Ok(Token::Uint(n))
+        }
+        ParamType::Int(bits) => {
+            let token = LenientTokenizer::tokenize(kind, &json_text(value)).map_err(|e| e.to_string())?;
+            if let Token::Int(raw) = &token {
+                let n = I256::from_raw(*raw);
+                let bound = I256::from_raw(U256::one() << (bits - 1));
+                if *bits < 256 && (n < -bound || n >= bound) {
+                    return Err(format!("{} does not fit in int{}", n, bits));
+                }
+            }
+            Ok(token)
+        }
+        ParamType::String => Ok(Token::String(json_text(value))),
+        _ => LenientTokenizer::tokenize(kind, &json_text(value)).map_err(|e| e.to_string()),
+    }
+}
+
+/// Arrays and tuples may arrive as JSON text from a form field
+fn nested_json(value: &Value) -> std::result::Result<Value, String> {
+    match value {
+        Value::String(text) => serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e)),
+        other => Ok(other.clone()),
+    }
+}
+
+fn json_array(value: &Value) -> std::result::Result<Vec<Value>, String> {
+    match nested_json(value)? {
+        Value::Array(items) => Ok(items),
+        _ => Err("expected an array".into()),
+    }
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn decode_hex(value: &Value) -> std::result::Result<Vec<u8>, String> {
+    let text = json_text(value);
+    hex::decode(text.trim_start_matches("0x")).map_err(|e| format!("invalid hex: {}", e))
+}
+
+/// Converts decoded tokens for a parameter list: an object when every parameter
+/// has a distinct name, otherwise an array
+fn params_to_json(tokens: &[Token], specs: &[ParamSpec]) -> Value {
+    let named = specs.len() == tokens.len()
+        && specs.iter().enumerate().all(|(i, s)| {
+            !s.name.is_empty() && specs[..i].iter().all(|other| other.name != s.name)
+        });
+
+    if named {
+        Value::Object(
+            tokens
+                .iter()
+                .zip(specs)
+                .map(|(token, spec)| (spec.name.clone(), token_to_json(token, spec)))
+                .collect(),
+        )
+    } else {
+        Value::Array(
+            tokens
+                .iter()
+                .enumerate()
+                .map(|(i, token)| token_to_json(token, param_spec(specs, i)))
+                .collect(),
+        )
+    }
+}
+
+fn token_to_json(token: &Token, spec: &ParamSpec) -> Value {
+    match token {
+        Token::Address(a) => Value::String(to_checksum(a, None)),
+        Token::FixedBytes(b) | Token::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+        Token::Int(i) => Value::String(I256::from_raw(*i).to_string()),
+        Token::Uint(u) => Value::String(u.to_string()),
+        Token::Bool(b) => Value::Bool(*b),
+        Token::String(s) => Value::String(s.clone()),
+        Token::FixedArray(items) | Token::Array(items) => {
+            Value::Array(items.iter().map(|item| token_to_json(item, spec)).collect())
+        }
+        Token::Tuple(items) => params_to_json(items, &spec.components),
+    }
+}
+
+/// Indexed strings, bytes, arrays and tuples are stored as their keccak hash
+fn event_param_to_json(token: &Token, param: &EventParam, spec: &ParamSpec) -> Value {
+    let hashed = param.indexed
+        && matches!(
+            param.kind,
+            ParamType::String | ParamType::Bytes | ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_)
+        );
+    if hashed {
+        token_to_json(token, &ParamSpec::default())
+    } else {
+        token_to_json(token, spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const VAULT_ABI: &str = r#"[
+        {"type":"function","name":"deposit","stateMutability":"payable",
+         "inputs":[{"name":"order","type":"tuple","components":[
+                      {"name":"owner","type":"address"},
+                      {"name":"amounts","type":"uint256[]"},
+                      {"name":"memo","type":"bytes"}]},
+                   {"name":"salt","type":"bytes32"}],
+         "outputs":[{"name":"shares","type":"uint256"},{"name":"position","type":"tuple","components":[
+                      {"name":"id","type":"uint64"},{"name":"open","type":"bool"}]}]},
+        {"type":"event","name":"Deposited","anonymous":false,
+         "inputs":[{"name":"owner","type":"address","indexed":true},
+                   {"name":"","type":"uint256","indexed":false},
+                   {"name":"","type":"int128","indexed":false}]},
+        {"type":"error","name":"InsufficientAllowance",
+         "inputs":[{"name":"spender","type":"address"},{"name":"allowance","type":"uint256"},{"name":"needed","type":"uint256"}]}
+    ]"#;
+
+    const OWNER: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    fn arg(name: &str, value: Value) -> ContractArg {
+        ContractArg { name: name.to_string(), value }
+    }
+
+    #[test]
+    fn test_encode_tuple_from_object_or_array() {
+        let abi = ContractAbi::parse(VAULT_ABI.as_bytes()).unwrap();
+        let salt = format!("0x{}", "ab".repeat(32));
+
+        let from_object = abi
+            .encode_call(
+                "deposit",
+                &[
+                    arg("order", json!({ "owner": OWNER, "amounts": ["1", 2, "340282366920938463463374607431768211456"], "memo": "0xbeef" })),
+                    arg("salt", json!(salt)),
+                ],
+            )
+            .unwrap();
+        let from_text = abi
+            .encode_call(
+                "deposit",
+                &[
+                    arg("arg0", json!(format!(r#"["{}", ["0x1", "2", "340282366920938463463374607431768211456"], "0xbeef"]"#, OWNER))),
+                    arg("arg1", json!(salt)),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(from_object, from_text);
+        assert_eq!(from_object[..4], abi.functions[0].item.short_signature());
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_values() {
+        let abi = ContractAbi::parse(
+            br#"[{"type":"function","name":"set","inputs":[{"name":"small","type":"uint8"},{"name":"signed","type":"int8"}],"outputs":[]}]"#,
+        )
+        .unwrap();
+
+        assert!(abi.encode_call("set", &[arg("small", json!(255)), arg("signed", json!(-128))]).is_ok());
+        assert!(abi.encode_call("set", &[arg("small", json!(256)), arg("signed", json!(0))]).is_err());
+        assert!(abi.encode_call("set", &[arg("small", json!(0)), arg("signed", json!(128))]).is_err());
+    }
+
+    #[test]
+    fn test_named_args_never_fall_back_to_position() {
+        let abi = ContractAbi::parse(
+            br#"[{"type":"function","name":"set","inputs":[{"name":"small","type":"uint8"},{"name":"signed","type":"int8"}],"outputs":[]}]"#,
+        )
+        .unwrap();
+
+        let positional = abi.encode_call("set", &[arg("arg0", json!(1)), arg("arg1", json!(-1))]).unwrap();
+        let unnamed = abi.encode_call("set", &[arg("", json!(1)), arg("", json!(-1))]).unwrap();
+        let named = abi.encode_call("set", &[arg("signed", json!(-1)), arg("small", json!(1))]).unwrap();
+        assert_eq!(positional, named);
+        assert_eq!(unnamed, named);
+
+        let error = abi
+            .encode_call("set", &[arg("small", json!(1)), arg("signd", json!(-1))])
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Validation error: Missing argument signed for set");
+    }
+
+    #[test]
+    fn test_encode_constructor_args() {
+        let abi = ContractAbi::parse(
+            br#"{"abi": [{"type":"constructor","inputs":[{"name":"owner","type":"address"},{"name":"supply","type":"uint256"}]}]}"#,
+        )
+        .unwrap();
+
+        let encoded = abi
+            .encode_constructor(&[arg("supply", json!("1000")), arg("owner", json!(OWNER))])
+            .unwrap();
+        assert_eq!(
+            encoded,
+            ethers::abi::encode(&[
+                Token::Address(OWNER.parse().unwrap()),
+                Token::Uint(U256::from(1000)),
+            ])
+        );
+        assert!(abi.encode_constructor(&[arg("owner", json!(OWNER))]).is_err());
+
+        // Without a constructor there is nothing to encode
+        let bare = ContractAbi::parse(b"[]").unwrap();
+        assert!(bare.encode_constructor(&[]).unwrap().is_empty());
+        assert!(bare.encode_constructor(&[arg("owner", json!(OWNER))]).is_err());
+    }
+
+    #[test]
+    fn test_diff_functions() {
+        let before = ContractAbi::parse(
+            br#"[
+                {"type":"function","name":"deposit","stateMutability":"payable","inputs":[],"outputs":[]},
+                {"type":"function","name":"withdraw","stateMutability":"nonpayable",
+                 "inputs":[{"name":"amount","type":"uint256"}],"outputs":[]},
+                {"type":"function","name":"owner","stateMutability":"view","inputs":[],
+                 "outputs":[{"name":"","type":"address"}]}
+            ]"#,
+        )
+        .unwrap();
+        let after = ContractAbi::parse(
+            br#"[
+                {"type":"function","name":"deposit","stateMutability":"payable","inputs":[],"outputs":[]},
+                {"type":"function","name":"withdraw","stateMutability":"nonpayable",
+                 "inputs":[{"name":"shares","type":"uint256"}],"outputs":[]},
+                {"type":"function","name":"pause","stateMutability":"nonpayable","inputs":[],"outputs":[]}
+            ]"#,
+        )
+        .unwrap();
+
+        let diff = before.diff_functions(&after);
+        assert_eq!(diff.added, vec!["pause"]);
+        assert_eq!(diff.removed, vec!["owner"]);
+        // Renaming an input breaks args saved by name
+        assert_eq!(diff.changed, vec!["withdraw"]);
+        assert!(after.diff_functions(&after).is_empty());
+        assert!(!after.has_function("owner"));
+    }
+
+    #[test]
+    fn test_decode_named_outputs() {
+        let abi = ContractAbi::parse(VAULT_ABI.as_bytes()).unwrap();
+        let data = ethers::abi::encode(&[
+            Token::Uint(U256::from(500u64)),
+            Token::Tuple(vec![Token::Uint(U256::from(7u64)), Token::Bool(true)]),
+        ]);
+
+        let decoded = abi.decode_output("deposit", 2, &data).unwrap();
+        assert_eq!(decoded, json!({ "shares": "500", "position": { "id": "7", "open": true } }));
+    }
+
+    #[test]
+    fn test_decode_revert_reasons() {
+        let abi = ContractAbi::parse(VAULT_ABI.as_bytes()).unwrap();
+
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(ethers::abi::encode(&[Token::String("not owner".into())]));
+        let error = abi.decode_error(&data).unwrap();
+        assert_eq!((error.name.as_str(), &error.args[0].value), ("Error", &json!("not owner")));
+
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend(ethers::abi::encode(&[Token::Uint(U256::from(0x11))]));
+        assert_eq!(abi.decode_error(&data).unwrap().args[0].value, json!("0x11"));
+
+        let custom = &abi.errors[0].item;
+        let data = custom
+            .encode(&[
+                Token::Address(OWNER.parse().unwrap()),
+                Token::Uint(U256::from(10)),
+                Token::Uint(U256::from(20)),
+            ])
+            .unwrap();
+        let error = abi.decode_error(&data).unwrap();
+        assert_eq!(error.name, "InsufficientAllowance");
+        assert_eq!(error.args[0].name, "spender");
+        assert_eq!(error.args[2].value, json!("20"));
+
+        assert_eq!(abi.decode_error(&[0xde, 0xad, 0xbe, 0xef]), None);
+        assert_eq!(abi.decode_error(&[]), None);
+    }
+
+    #[test]
+    fn test_revert_info_messages() {
+        let abi = ContractAbi::parse(VAULT_ABI.as_bytes()).unwrap();
+        let data = abi.errors[0]
+            .item
+            .encode(&[
+                Token::Address(OWNER.parse().unwrap()),
+                Token::Uint(U256::from(10)),
+                Token::Uint(U256::from(20)),
+            ])
+            .unwrap();
+
+        let info = revert_info(&data, &[ContractAbi::default(), abi]);
+        assert_eq!(info.message, format!("InsufficientAllowance({}, 10, 20)", OWNER));
+        assert_eq!(info.error_name.as_deref(), Some("InsufficientAllowance"));
+        assert_eq!(info.data, Some(format!("0x{}", hex::encode(&data))));
+
+        // Without the declaring ABI only the selector is known
+        assert_eq!(
+            revert_info(&data, &[]).message,
+            format!("execution reverted with unknown error 0x{}", hex::encode(&data[..4]))
+        );
+
+        let mut panic = PANIC_SELECTOR.to_vec();
+        panic.extend(ethers::abi::encode(&[Token::Uint(U256::from(0x12))]));
+        assert_eq!(revert_info(&panic, &[]).message, "Panic(0x12): division or modulo by zero");
+
+        assert_eq!(revert_info(&[], &[]).message, "execution reverted");
+    }
+
+    #[test]
+    fn test_decode_logs_names_unnamed_params() {
+        let abi = ContractAbi::parse(VAULT_ABI.as_bytes()).unwrap();
+        let event = &abi.events[0].item;
+        let log = Log {
+            address: OWNER.to_string(),
+            topics: vec![
+                format!("{:?}", event.signature()),
+                format!("0x{:0>64}", OWNER.trim_start_matches("0x").to_lowercase()),
+            ],
+            data: ethers::abi::encode(&[
+                Token::Uint(U256::from(1000u64)),
+                Token::Int(I256::from(-5).into_raw()),
+            ]),
+        };
+
+        let events = abi.decode_logs(&[log]).unwrap();
+        assert_eq!(events[0].name, "Deposited");
+        assert_eq!(events[0].args, json!({ "owner": OWNER, "arg1": "1000", "arg2": "-5" }));
+    }
+}
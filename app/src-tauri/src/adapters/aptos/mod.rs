@@ -0,0 +1,999 @@
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::{json, Value};
+use sha3::{Digest, Sha3_256};
+use std::path::Path;
+
+use crate::adapters::derivation;
+use crate::adapters::signer::SoftwareSigner;
+use crate::adapters::traits::*;
+use crate::error::CocoError;
+use crate::types::*;
+
+pub mod package;
+pub mod payload;
+pub mod rest;
+
+pub use rest::{HttpTransport, RestTransport};
+use payload::{normalize_address, MoveFunctionDef};
+
+/// Default derivation path used by Petra and the Aptos CLI
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/637'/0'/0'/0'";
+
+const APT_COIN: &str = "0x1::aptos_coin::AptosCoin";
+const OCTAS_PER_APT: u64 = 100_000_000;
+const DEFAULT_MAX_GAS_AMOUNT: u64 = 200_000;
+const TRANSACTION_TTL_SECS: i64 = 600;
+
+/// Aptos adapter that talks to a fullnode over its REST API
+pub struct AptosAdapter<T: RestTransport = HttpTransport> {
+    config: ChainConfig,
+    base_url: String,
+    rest: T,
+}
+
+impl AptosAdapter<HttpTransport> {
+    /// Creates an adapter for the fullnode at the chain's `rpc_url`
+    pub fn new(config: ChainConfig) -> Result<Self, CocoError> {
+        reqwest::Url::parse(&config.rpc_url)
+            .map_err(|e| CocoError::Adapter(format!("Invalid RPC URL {}: {}", config.rpc_url, e)))?;
+        Ok(Self::with_transport(config, HttpTransport::new()))
+    }
+}
+
+impl<T: RestTransport> AptosAdapter<T> {
+    /// Creates an adapter over an existing transport (e.g. a recorded stand-in in tests)
+    pub fn with_transport(config: ChainConfig, rest: T) -> Self {
+        // Seeded network URLs omit the API version prefix
+        let trimmed = config.rpc_url.trim_end_matches('/');
+        let base_url = if trimmed.ends_with("/v1") {
+            trimmed.to_string()
+        } else {
+            format!("{}/v1", trimmed)
+        };
+        Self { config, base_url, rest }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Faucets run beside the fullnode: faucet.<network>.aptoslabs.com, or :8081 locally
+    fn faucet_url(&self) -> Option<String> {
+        let url = reqwest::Url::parse(&self.config.rpc_url).ok()?;
+        let host = url.host_str()?;
+
+        if let Some(network) = host.strip_prefix("fullnode.") {
+            Some(format!("https://faucet.{}", network))
+        } else if matches!(host, "localhost" | "127.0.0.1") {
+            Some(format!("{}://{}:8081", url.scheme(), host))
+        } else {
+            None
+        }
+    }
+
+    async fn fetch_transaction(&self, hash: &str) -> Result<Value, CocoError> {
+        self.rest
+            .get(&self.url(&format!("/transactions/by_hash/{}", hash)))
+            .await
+    }
+
+    /// Resolves a view or entry function from the module ABI published on chain
+    async fn resolve_function(&self, address: &str, function: &str) -> Result<MoveFunctionDef, CocoError> {
+        let parts: Vec<&str> = function.split("::").collect();
+        let (module_address, module, name) = match parts.as_slice() {
+            [addr, module, name] => (addr.to_string(), *module, *name),
+            [module, name] => (address.to_string(), *module, *name),
+            _ => {
+                return Err(CocoError::Validation(format!(
+                    "Aptos functions must be given as module::function, got {}",
+                    function
+                )))
+            }
+        };
+
+        let module_abi = self
+            .rest
+            .get(&self.url(&format!(
+                "/accounts/{}/module/{}",
+                normalize_address(&module_address)?,
+                module
+            )))
+            .await?;
+        let abi = module_abi
+            .get("abi")
+            .ok_or_else(|| CocoError::NotFound(format!("Module {} has no ABI", module)))?;
+
+        payload::from_module_abi(abi, name)
+    }
+
+    /// Builds the unsigned transaction body shared by submission and simulation
+    async fn unsigned_transaction(
+        &self,
+        sender: &str,
+        tx: &TransactionRequest,
+        gas_unit_price: u64,
+    ) -> Result<Value, CocoError> {
+        let sequence_number = match tx.nonce {
+            Some(nonce) => nonce,
+            None => self.sequence_number(sender).await?,
+        };
+
+        Ok(json!({
+            "sender": sender,
+            "sequence_number": sequence_number.to_string(),
+            "max_gas_amount": tx.gas_limit.unwrap_or(DEFAULT_MAX_GAS_AMOUNT).to_string(),
+            "gas_unit_price": gas_unit_price.to_string(),
+            "expiration_timestamp_secs": (chrono::Utc::now().timestamp() + TRANSACTION_TTL_SECS).to_string(),
+            "payload": request_payload(tx)?,
+        }))
+    }
+
+    /// The account's next sequence number, counting only committed transactions
+    async fn sequence_number(&self, sender: &str) -> Result<u64, CocoError> {
+        let account = self
+            .rest
+            .get(&self.url(&format!("/accounts/{}", sender)))
+            .await
+            .map_err(|e| match e {
+                CocoError::NotFound(_) => CocoError::Validation(format!(
+                    "Account {} does not exist on chain yet; fund it first",
+                    sender
+                )),
+                other => other,
+            })?;
+        Ok(number_field(&account, "sequence_number"))
+    }
+
+    /// The request's own gas unit price, or the node's current estimate
+    async fn gas_unit_price(&self, tx: &TransactionRequest) -> Result<u64, CocoError> {
+        match &tx.fee {
+            Some(FeePricing::Legacy { gas_price }) => gas_price
+                .parse()
+                .map_err(|_| CocoError::Validation(format!("Invalid gas unit price: {}", gas_price))),
+            Some(_) => Err(CocoError::Validation(
+                "Aptos transactions take a single gas unit price".into(),
+            )),
+            None => {
+                let estimate = self.rest.get(&self.url("/estimate_gas_price")).await?;
+                estimate
+                    .get("gas_estimate")
+                    .and_then(|g| g.as_u64())
+                    .ok_or_else(|| CocoError::Adapter("estimate_gas_price returned no estimate".into()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T: RestTransport> ChainAdapter for AptosAdapter<T> {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Aptos
+    }
+
+    fn chain_id(&self) -> &str {
+        &self.config.id
+    }
+
+    async fn validate_connection(&self) -> Result<bool, CocoError> {
+        Ok(self.rest.get(&self.url("")).await.is_ok())
+    }
+
+    async fn get_block_height(&self) -> Result<u64, CocoError> {
+        let ledger = self.rest.get(&self.url("")).await?;
+        ledger
+            .get("ledger_version")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| CocoError::Adapter("Ledger info has no ledger_version".into()))
+    }
+}
+
+#[async_trait]
+impl<T: RestTransport> WalletAdapter for AptosAdapter<T> {
+    async fn generate_wallet(&self) -> Result<WalletData, CocoError> {
+        let key = SigningKey::generate(&mut rand::rngs::OsRng);
+        Ok(wallet_data(&key))
+    }
+
+    async fn import_wallet(&self, private_key: &str) -> Result<WalletData, CocoError> {
+        let key = parse_private_key(private_key)?;
+        Ok(wallet_data(&key))
+    }
+
+    async fn import_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        derivation_path: Option<&str>,
+    ) -> Result<WalletData, CocoError> {
+        let seed = derivation::mnemonic_to_seed(mnemonic)?;
+        let secret = derivation::derive_ed25519(
+            &seed,
+            derivation_path.unwrap_or(DEFAULT_DERIVATION_PATH),
+        )?;
+        Ok(wallet_data(&SigningKey::from_bytes(&secret)))
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<Balance, CocoError> {
+        let address = normalize_address(address)?;
+        let resource = self
+            .rest
+            .get(&self.url(&format!(
+                "/accounts/{}/resource/0x1::coin::CoinStore<{}>",
+                address, APT_COIN
+            )))
+            .await;
+
+        let octas = match resource {
+            Ok(store) => store
+                .pointer("/data/coin/value")
+                .and_then(|v| v.as_str())
+                .unwrap_or("0")
+                .to_string(),
+            // Accounts migrated to fungible assets no longer hold a CoinStore
+            Err(CocoError::NotFound(_)) => {
+                let result = self
+                    .rest
+                    .post(
+                        &self.url("/view"),
+                        json!({
+                            "function": "0x1::coin::balance",
+                            "type_arguments": [APT_COIN],
+                            "arguments": [address],
+                        }),
+                    )
+                    .await;
+                match result {
+                    Ok(values) => values
+                        .get(0)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("0")
+                        .to_string(),
+                    Err(CocoError::NotFound(_)) => "0".to_string(),
+                    Err(e) => return Err(e),
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(Balance {
+            native: octas,
+            native_decimals: 8,
+            native_symbol: self.config.native_currency.clone(),
+        })
+    }
+
+    async fn get_token_balances(&self, address: &str) -> Result<Vec<TokenBalance>, CocoError> {
+        let address = normalize_address(address)?;
+        let resources = match self
+            .rest
+            .get(&self.url(&format!("/accounts/{}/resources", address)))
+            .await
+        {
+            Ok(resources) => resources,
+            Err(CocoError::NotFound(_)) => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut balances = Vec::new();
+        for resource in resources.as_array().cloned().unwrap_or_default() {
+            let resource_type = resource.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+            let Some(coin_type) = resource_type
+                .strip_prefix("0x1::coin::CoinStore<")
+                .and_then(|t| t.strip_suffix('>'))
+            else {
+                continue;
+            };
+            if coin_type == APT_COIN {
+                continue;
+            }
+
+            // Symbol and decimals live in the CoinInfo resource at the coin's address
+            let coin_address = coin_type.split("::").next().unwrap_or_default();
+            let info = self
+                .rest
+                .get(&self.url(&format!(
+                    "/accounts/{}/resource/0x1::coin::CoinInfo<{}>",
+                    coin_address, coin_type
+                )))
+                .await
+                .ok();
+
+            balances.push(TokenBalance {
+                address: coin_type.to_string(),
+                symbol: info
+                    .as_ref()
+                    .and_then(|i| i.pointer("/data/symbol"))
+                    .and_then(|s| s.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| coin_type.rsplit("::").next().unwrap_or(coin_type).to_string()),
+                decimals: info
+                    .as_ref()
+                    .and_then(|i| i.pointer("/data/decimals"))
+                    .and_then(|d| d.as_u64())
+                    .unwrap_or(0) as u8,
+                balance: resource
+                    .pointer("/data/coin/value")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("0")
+                    .to_string(),
+            });
+        }
+
+        Ok(balances)
+    }
+
+    async fn request_faucet(&self, address: &str) -> Result<String, CocoError> {
+        let address = normalize_address(address)?;
+        let faucet = self.faucet_url().ok_or_else(|| {
+            CocoError::Adapter(format!(
+                "No faucet available for {}; use the network's web faucet instead",
+                self.config.name
+            ))
+        })?;
+
+        let hashes = self
+            .rest
+            .post(
+                &format!("{}/mint?amount={}&address={}", faucet, OCTAS_PER_APT, address),
+                Value::Null,
+            )
+            .await?;
+
+        Ok(hashes
+            .get(0)
+            .and_then(|h| h.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn sign_message(
+        &self,
+        wallet: &WalletData,
+        message: &[u8],
+    ) -> Result<Vec<u8>, CocoError> {
+        let key = signing_key(wallet)?;
+        Ok(key.sign(message).to_bytes().to_vec())
+    }
+}
+
+#[async_trait]
+impl<T: RestTransport> TransactionAdapter for AptosAdapter<T> {
+    async fn send_transaction(
+        &self,
+        wallet: &WalletData,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let signer = SoftwareSigner::from_wallet(Ecosystem::Aptos, wallet)?;
+        self.send_transaction_with_signer(&signer, tx).await
+    }
+
+    async fn get_pending_nonce(&self, address: &str) -> Result<Option<u64>, CocoError> {
+        self.sequence_number(address).await.map(Some)
+    }
+
+    async fn send_transaction_with_signer(
+        &self,
+        signer: &dyn ExternalSigner,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let sender = signer.address();
+
+        let gas_unit_price = self.gas_unit_price(&tx).await?;
+        let mut unsigned = self.unsigned_transaction(&sender, &tx, gas_unit_price).await?;
+
+        // The node returns the exact bytes to sign, which avoids a local BCS encoder
+        let signing_message = self
+            .rest
+            .post(&self.url("/transactions/encode_submission"), unsigned.clone())
+            .await?;
+        let signing_message = signing_message
+            .as_str()
+            .and_then(|m| hex::decode(m.trim_start_matches("0x")).ok())
+            .ok_or_else(|| CocoError::Adapter("encode_submission returned no signing message".into()))?;
+
+        let signature = signer.sign_transaction(&signing_message).await?;
+        unsigned["signature"] = json!({
+            "type": "ed25519_signature",
+            "public_key": signer.public_key(),
+            "signature": format!("0x{}", hex::encode(signature)),
+        });
+
+        let nonce = number_field(&unsigned, "sequence_number");
+        let pending = self.rest.post(&self.url("/transactions"), unsigned).await?;
+        let hash = pending
+            .get("hash")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| CocoError::Adapter("Submission returned no transaction hash".into()))?;
+
+        Ok(TransactionResult {
+            hash: hash.to_string(),
+            status: TransactionStatus::Pending,
+            nonce: Some(nonce),
+        })
+    }
+
+    async fn get_transaction(&self, hash: &str) -> Result<TransactionData, CocoError> {
+        let tx = self.fetch_transaction(hash).await.map_err(|e| match e {
+            CocoError::NotFound(_) => CocoError::NotFound("Transaction not found".into()),
+            other => other,
+        })?;
+        let committed = tx.get("type").and_then(|t| t.as_str()) != Some("pending_transaction");
+
+        let to = tx
+            .pointer("/payload/function")
+            .and_then(|f| f.as_str())
+            .and_then(|f| f.split("::").next())
+            .map(String::from);
+
+        Ok(TransactionData {
+            hash: hash.to_string(),
+            from: tx.get("sender").and_then(|s| s.as_str()).unwrap_or_default().to_string(),
+            to,
+            value: None,
+            data: tx.get("payload").map(|p| p.to_string().into_bytes()),
+            block_number: committed
+                .then(|| tx.get("version").and_then(|v| v.as_str()).and_then(|v| v.parse().ok()))
+                .flatten(),
+            timestamp: tx
+                .get("timestamp")
+                .and_then(|t| t.as_str())
+                .and_then(|t| t.parse::<i64>().ok())
+                .and_then(chrono::DateTime::from_timestamp_micros)
+                .unwrap_or_else(chrono::Utc::now),
+        })
+    }
+
+    async fn get_transaction_receipt(&self, hash: &str) -> Result<TransactionReceipt, CocoError> {
+        let tx = self.fetch_transaction(hash).await.map_err(|e| match e {
+            CocoError::NotFound(_) => CocoError::NotFound("Receipt not available yet".into()),
+            other => other,
+        })?;
+        if tx.get("type").and_then(|t| t.as_str()) == Some("pending_transaction") {
+            return Err(CocoError::NotFound("Receipt not available yet".into()));
+        }
+
+        Ok(TransactionReceipt {
+            hash: hash.to_string(),
+            status: tx.get("success").and_then(|s| s.as_bool()).unwrap_or(false),
+            revert: vm_failure(&tx),
+            block_number: number_field(&tx, "version"),
+            block_hash: None,
+            gas_used: number_field(&tx, "gas_used"),
+            fee: Some((number_field(&tx, "gas_used") * number_field(&tx, "gas_unit_price")).to_string()),
+            events: vec![],
+            logs: event_logs(tx.get("events")),
+            contract_address: None,
+        })
+    }
+
+    async fn estimate_fees(&self, tx: &TransactionRequest) -> Result<FeeEstimate, CocoError> {
+        let estimate = self.rest.get(&self.url("/estimate_gas_price")).await?;
+        let normal = estimate
+            .get("gas_estimate")
+            .and_then(|g| g.as_u64())
+            .ok_or_else(|| CocoError::Adapter("estimate_gas_price returned no estimate".into()))?;
+        // Older nodes only report the middle estimate
+        let slow = estimate
+            .get("deprioritized_gas_estimate")
+            .and_then(|g| g.as_u64())
+            .unwrap_or(normal);
+        let fast = estimate
+            .get("prioritized_gas_estimate")
+            .and_then(|g| g.as_u64())
+            .unwrap_or(normal);
+
+        // Simulation gives the real gas use when a sender is known; otherwise assume the cap
+        let gas_limit = match tx.gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => match self.simulate_transaction(tx).await {
+                Ok(simulation) if simulation.gas_used > 0 => simulation.gas_used,
+                _ => DEFAULT_MAX_GAS_AMOUNT,
+            },
+        };
+        let tier = |gas_unit_price: u64| FeeTier {
+            pricing: FeePricing::Legacy {
+                gas_price: gas_unit_price.to_string(),
+            },
+            total_fee: (gas_limit * gas_unit_price).to_string(),
+        };
+
+        Ok(FeeEstimate {
+            gas_limit,
+            slow: tier(slow),
+            normal: tier(normal),
+            fast: tier(fast),
+        })
+    }
+
+    /// The sender is the first signer account, whose `pubkey` holds the ed25519 public key
+    async fn simulate_transaction(
+        &self,
+        tx: &TransactionRequest,
+    ) -> Result<SimulationResult, CocoError> {
+        let public_key = tx
+            .accounts
+            .iter()
+            .find(|a| a.is_signer)
+            .map(|a| a.pubkey.trim_start_matches("0x").to_string())
+            .ok_or_else(|| {
+                CocoError::Validation("Simulation needs the sender's public key as a signer account".into())
+            })?;
+        let public_key: [u8; 32] = hex::decode(&public_key)
+            .ok()
+            .and_then(|k| k.try_into().ok())
+            .ok_or_else(|| CocoError::Validation(format!("Invalid ed25519 public key: {}", public_key)))?;
+
+        let gas_unit_price = self.gas_unit_price(tx).await?;
+        let mut unsigned = self
+            .unsigned_transaction(&account_address(&public_key), tx, gas_unit_price)
+            .await?;
+
+        // Simulation rejects valid signatures, so send an all-zero one
+        unsigned["signature"] = json!({
+            "type": "ed25519_signature",
+            "public_key": format!("0x{}", hex::encode(public_key)),
+            "signature": format!("0x{}", "00".repeat(64)),
+        });
+
+        let results = self
+            .rest
+            .post(&self.url("/transactions/simulate"), unsigned)
+            .await?;
+        let result = results
+            .get(0)
+            .ok_or_else(|| CocoError::Adapter("Simulation returned no result".into()))?;
+
+        let success = result.get("success").and_then(|s| s.as_bool()).unwrap_or(false);
+        Ok(SimulationResult {
+            revert: vm_failure(&result),
+            success,
+            // Surface the VM status (e.g. an abort code) when the simulation fails
+            return_data: if success {
+                vec![]
+            } else {
+                result
+                    .get("vm_status")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or_default()
+                    .as_bytes()
+                    .to_vec()
+            },
+            gas_used: number_field(result, "gas_used"),
+            logs: event_logs(result.get("events")),
+        })
+    }
+}
+
+#[async_trait]
+impl<T: RestTransport> ContractAdapter for AptosAdapter<T> {
+    fn deployment_request(
+        &self,
+        _bytecode: &[u8],
+        _constructor_args: &[u8],
+    ) -> Result<TransactionRequest, CocoError> {
+        // Publishing needs the package metadata as well as module bytecode
+        Err(CocoError::Adapter(
+            "Module publishing is not supported; use `aptos move publish`".into(),
+        ))
+    }
+
+    /// Calls a view function given as `module::function` (under `address`) or fully qualified
+    async fn call_contract(
+        &self,
+        address: &str,
+        function: &str,
+        args: Vec<ContractArg>,
+    ) -> Result<ContractCallResult, CocoError> {
+        let def = self.resolve_function(address, function).await?;
+        if !def.is_view {
+            return Err(CocoError::Validation(format!(
+                "{} is not a #[view] function",
+                def.id
+            )));
+        }
+
+        let values = self
+            .rest
+            .post(&self.url("/view"), payload::view_request(&def, &args)?)
+            .await?;
+
+        Ok(ContractCallResult {
+            return_data: values.to_string().into_bytes(),
+            decoded: values,
+        })
+    }
+
+    /// Encodes an entry-function payload as JSON from the contract's `moveDefinition`
+    fn encode_call(
+        &self,
+        abi: &[u8],
+        function: &str,
+        args: Vec<ContractArg>,
+    ) -> Result<Vec<u8>, CocoError> {
+        let definition: Value = serde_json::from_slice(abi)
+            .map_err(|e| CocoError::Serialization(format!("Invalid Move definition: {}", e)))?;
+        let def = payload::from_definition(&definition, function)?;
+        let payload = payload::entry_payload(&def, &args)?;
+        Ok(payload.to_string().into_bytes())
+    }
+
+    fn decode_events(
+        &self,
+        abi: &[u8],
+        logs: &[Log],
+    ) -> Result<Vec<DecodedEvent>, CocoError> {
+        // Limit to the contract's own module when a definition is available
+        let module_prefix = serde_json::from_slice::<Value>(abi).ok().and_then(|d| {
+            let address = normalize_address(d.get("moduleAddress")?.as_str()?).ok()?;
+            let module = d.get("moduleName")?.as_str()?;
+            Some(format!("{}::{}::", address, module))
+        });
+
+        Ok(logs
+            .iter()
+            .filter_map(|log| {
+                let event_type = log.topics.first()?;
+                if let Some(prefix) = &module_prefix {
+                    let (address, rest) = event_type.split_once("::")?;
+                    let normalized = format!("{}::{}", normalize_address(address).ok()?, rest);
+                    if !normalized.starts_with(prefix.as_str()) {
+                        return None;
+                    }
+                }
+
+                let struct_name = event_type.split('<').next()?.rsplit("::").next()?;
+                Some(DecodedEvent {
+                    name: struct_name.to_string(),
+                    args: serde_json::from_slice(&log.data).unwrap_or(Value::Null),
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<T: RestTransport> DiscoveryAdapter for AptosAdapter<T> {
+    async fn discover_contracts(
+        &self,
+        project_path: &Path,
+    ) -> Result<Vec<DiscoveredContract>, CocoError> {
+        Ok(package::discover(project_path))
+    }
+
+    /// Returns the ABIs of all modules published under an account
+    async fn fetch_contract_interface(
+        &self,
+        address: &str,
+    ) -> Result<Option<Vec<u8>>, CocoError> {
+        let modules = match self
+            .rest
+            .get(&self.url(&format!("/accounts/{}/modules", normalize_address(address)?)))
+            .await
+        {
+            Ok(modules) => modules,
+            Err(CocoError::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let abis: Vec<Value> = modules
+            .as_array()
+            .map(|m| m.iter().filter_map(|module| module.get("abi").cloned()).collect())
+            .unwrap_or_default();
+
+        if abis.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Value::Array(abis).to_string().into_bytes()))
+    }
+}
+
+/// Single-key ed25519 account address: sha3-256(public key || scheme 0x00)
+pub fn account_address(public_key: &[u8; 32]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key);
+    hasher.update([0u8]);
+    format!("0x{}", hex::encode(hasher.finalize()))
+}
+
+/// Accepts hex keys with or without 0x, and the AIP-80 `ed25519-priv-` prefix
+pub(crate) fn parse_private_key(private_key: &str) -> Result<SigningKey, CocoError> {
+    let trimmed = private_key.trim();
+    let hex_key = trimmed
+        .strip_prefix("ed25519-priv-")
+        .unwrap_or(trimmed)
+        .trim_start_matches("0x");
+
+    let bytes = hex::decode(hex_key)
+        .map_err(|e| CocoError::Validation(format!("Invalid Aptos private key: {}", e)))?;
+    let secret: [u8; 32] = bytes
+        .get(..32)
+        .filter(|_| bytes.len() == 32 || bytes.len() == 64)
+        .and_then(|s| s.try_into().ok())
+        .ok_or_else(|| {
+            CocoError::Validation(format!("Invalid Aptos private key length: {} bytes", bytes.len()))
+        })?;
+
+    Ok(SigningKey::from_bytes(&secret))
+}
+
+fn signing_key(wallet: &WalletData) -> Result<SigningKey, CocoError> {
+    let private_key = wallet.private_key.as_deref().ok_or_else(|| {
+        CocoError::Validation(format!("Wallet {} has no private key to sign with", wallet.address))
+    })?;
+    parse_private_key(private_key)
+}
+
+pub(crate) fn wallet_data(key: &SigningKey) -> WalletData {
+    let public_key = key.verifying_key().to_bytes();
+    WalletData {
+        address: account_address(&public_key),
+        public_key: format!("0x{}", hex::encode(public_key)),
+        private_key_encrypted: None,
+        private_key: Some(format!("0x{}", hex::encode(key.to_bytes()))),
+    }
+}
+
+/// Entry-function payload JSON from `data`, otherwise an APT transfer of `value` octas to `to`
+fn request_payload(tx: &TransactionRequest) -> Result<Value, CocoError> {
+    if let Some(data) = &tx.data {
+        return serde_json::from_slice(data)
+            .map_err(|e| CocoError::Serialization(format!("Invalid entry function payload: {}", e)));
+    }
+
+    let to = tx
+        .to
+        .as_deref()
+        .ok_or_else(|| CocoError::Validation("Transfers need a recipient address".into()))?;
+    let amount = tx.value.as_deref().unwrap_or("0");
+    if !amount.chars().all(|c| c.is_ascii_digit()) {
+        return Err(CocoError::Validation(format!("Invalid octa amount: {}", amount)));
+    }
+
+    Ok(json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [normalize_address(to)?, amount],
+    }))
+}
+
+/// The VM status of a failed transaction, e.g. `Move abort in 0x1::coin: EINSUFFICIENT_BALANCE(0x10006)`
+fn vm_failure(tx: &Value) -> Option<RevertInfo> {
+    if tx.get("success").and_then(|s| s.as_bool()).unwrap_or(false) {
+        return None;
+    }
+    Some(RevertInfo {
+        message: tx
+            .get("vm_status")
+            .and_then(|s| s.as_str())
+            .unwrap_or("Transaction failed")
+            .to_string(),
+        ..Default::default()
+    })
+}
+
+/// Aptos encodes u64 fields as decimal strings
+fn number_field(value: &Value, key: &str) -> u64 {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Maps Move events to logs: the event type is the only topic and data holds its JSON
+fn event_logs(events: Option<&Value>) -> Vec<Log> {
+    events
+        .and_then(|e| e.as_array())
+        .map(|events| {
+            events
+                .iter()
+                .map(|event| {
+                    let event_type = event.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+                    Log {
+                        address: event_type.split("::").next().unwrap_or_default().to_string(),
+                        topics: vec![event_type.to_string()],
+                        data: event.get("data").map(|d| d.to_string().into_bytes()).unwrap_or_default(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::Mutex;
+
+    /// Replays canned REST responses keyed by method and path, recording request bodies
+    #[derive(Default)]
+    struct RecordedRest {
+        responses: Mutex<HashMap<String, VecDeque<Result<Value, CocoError>>>>,
+        bodies: Mutex<Vec<(String, Value)>>,
+    }
+
+    impl RecordedRest {
+        fn respond(&self, key: &str, result: Result<Value, CocoError>) {
+            self.responses
+                .lock()
+                .unwrap()
+                .entry(key.to_string())
+                .or_default()
+                .push_back(result);
+        }
+
+        fn next(&self, key: String) -> Result<Value, CocoError> {
+            self.responses
+                .lock()
+                .unwrap()
+                .get_mut(&key)
+                .and_then(|q| q.pop_front())
+                .unwrap_or_else(|| Err(CocoError::Adapter(format!("No recorded response for {}", key))))
+        }
+    }
+
+    #[async_trait]
+    impl RestTransport for RecordedRest {
+        async fn get(&self, url: &str) -> Result<Value, CocoError> {
+            self.next(format!("GET {}", url.trim_start_matches(BASE)))
+        }
+
+        async fn post(&self, url: &str, body: Value) -> Result<Value, CocoError> {
+            let path = url.trim_start_matches(BASE).to_string();
+            self.bodies.lock().unwrap().push((path.clone(), body));
+            self.next(format!("POST {}", path))
+        }
+    }
+
+    const BASE: &str = "http://127.0.0.1:8080/v1";
+    const MODULE: &str = "0xcafe";
+
+    fn adapter() -> AptosAdapter<RecordedRest> {
+        let config = ChainConfig {
+            id: "aptos-localnet".to_string(),
+            name: "Localnet".to_string(),
+            ecosystem: Ecosystem::Aptos,
+            rpc_url: "http://127.0.0.1:8080".to_string(),
+            native_currency: "APT".to_string(),
+        };
+        AptosAdapter::with_transport(config, RecordedRest::default())
+    }
+
+    fn arg(name: &str, value: &str) -> ContractArg {
+        ContractArg { name: name.to_string(), value: json!(value) }
+    }
+
+    #[tokio::test]
+    async fn test_ledger_version_and_balance() {
+        let adapter = adapter();
+        let owner = "0x1234";
+        let long_owner = normalize_address(owner).unwrap();
+        adapter.rest.respond("GET ", Ok(json!({ "chain_id": 4, "ledger_version": "5678" })));
+        adapter.rest.respond(
+            &format!("GET /accounts/{}/resource/0x1::coin::CoinStore<{}>", long_owner, APT_COIN),
+            Err(CocoError::NotFound("Resource not found".into())),
+        );
+        adapter.rest.respond("POST /view", Ok(json!(["250000000"])));
+
+        assert_eq!(adapter.get_block_height().await.unwrap(), 5678);
+
+        let balance = adapter.get_balance(owner).await.unwrap();
+        assert_eq!(balance.native, "250000000");
+        assert_eq!(balance.native_decimals, 8);
+    }
+
+    #[test]
+    fn test_encode_call_from_move_definition() {
+        let adapter = adapter();
+        let definition = json!({
+            "moduleName": "vault",
+            "moduleAddress": MODULE,
+            "functions": [{
+                "name": "deposit",
+                "visibility": "entry",
+                "typeParams": ["CoinType"],
+                "params": [
+                    { "name": "account", "type": "&signer" },
+                    { "name": "amount", "type": "u64" },
+                    { "name": "lock_days", "type": "u8" },
+                    { "name": "recipients", "type": "vector<address>" }
+                ]
+            }],
+            "structs": []
+        });
+
+        let data = adapter
+            .encode_call(
+                definition.to_string().as_bytes(),
+                "deposit",
+                vec![
+                    arg("type_arg:0", APT_COIN),
+                    arg("amount", "1000"),
+                    arg("lock_days", "30"),
+                    arg("recipients", "0x1, 0x2"),
+                ],
+            )
+            .unwrap();
+        let payload: Value = serde_json::from_slice(&data).unwrap();
+
+        assert_eq!(payload["type"], "entry_function_payload");
+        assert_eq!(payload["function"], format!("{}::vault::deposit", normalize_address(MODULE).unwrap()));
+        assert_eq!(payload["type_arguments"], json!([APT_COIN]));
+        assert_eq!(payload["arguments"][0], "1000");
+        assert_eq!(payload["arguments"][1], 30);
+        assert_eq!(payload["arguments"][2][1], normalize_address("0x2").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_view_call_uses_onchain_abi() {
+        let adapter = adapter();
+        let module = normalize_address(MODULE).unwrap();
+        adapter.rest.respond(
+            &format!("GET /accounts/{}/module/vault", module),
+            Ok(json!({ "abi": {
+                "address": MODULE,
+                "name": "vault",
+                "exposed_functions": [{
+                    "name": "locked", "visibility": "public", "is_entry": false, "is_view": true,
+                    "generic_type_params": [], "params": ["address"], "return": ["u64"]
+                }]
+            }})),
+        );
+        adapter.rest.respond("POST /view", Ok(json!(["42"])));
+
+        let result = adapter
+            .call_contract(MODULE, "vault::locked", vec![arg("arg0", "0x1")])
+            .await
+            .unwrap();
+        assert_eq!(result.decoded, json!(["42"]));
+
+        let bodies = adapter.rest.bodies.lock().unwrap();
+        assert_eq!(bodies[0].1["arguments"][0], normalize_address("0x1").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_reports_vm_status_and_events() {
+        let adapter = adapter();
+        let wallet = adapter.generate_wallet().await.unwrap();
+        adapter.rest.respond("GET /estimate_gas_price", Ok(json!({ "gas_estimate": 100 })));
+        adapter.rest.respond(
+            &format!("GET /accounts/{}", wallet.address),
+            Ok(json!({ "sequence_number": "3", "authentication_key": wallet.address })),
+        );
+        adapter.rest.respond("POST /transactions/simulate", Ok(json!([{
+            "success": false,
+            "vm_status": "Move abort in 0xcafe::vault: EINSUFFICIENT(0x1)",
+            "gas_used": "12",
+            "events": [{ "type": "0xcafe::vault::Deposited", "data": { "amount": "5" } }]
+        }])));
+
+        let tx = TransactionRequest {
+            to: Some("0x2".to_string()),
+            value: Some("5".to_string()),
+            accounts: vec![InstructionAccount {
+                pubkey: wallet.public_key.clone(),
+                is_signer: true,
+                is_writable: true,
+            }],
+            ..Default::default()
+        };
+        let simulation = adapter.simulate_transaction(&tx).await.unwrap();
+        assert!(!simulation.success);
+        assert_eq!(simulation.gas_used, 12);
+        assert!(String::from_utf8_lossy(&simulation.return_data).contains("EINSUFFICIENT"));
+
+        let bodies = adapter.rest.bodies.lock().unwrap();
+        assert_eq!(bodies[0].1["sequence_number"], "3");
+        assert_eq!(bodies[0].1["payload"]["function"], "0x1::aptos_account::transfer");
+
+        let definition = json!({ "moduleName": "vault", "moduleAddress": "0xcafe", "functions": [] });
+        let events = adapter
+            .decode_events(definition.to_string().as_bytes(), &simulation.logs)
+            .unwrap();
+        assert_eq!(events[0].name, "Deposited");
+        assert_eq!(events[0].args["amount"], "5");
+    }
+}
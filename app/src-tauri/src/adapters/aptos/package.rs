@@ -0,0 +1,959 @@
+//! Move package discovery. Named addresses come from `Move.toml`. Entry
+//! functions come from the ABIs `aptos move compile --save-metadata` writes to
+//! `build/<package>/abis`; the sources are scanned for what those leave out:
+//! view and other public functions, structs, and any parameter names the ABIs
+//! don't carry. Once built, only the modules in `bytecode_modules` are listed.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::payload::{is_signer, normalize_address};
+use crate::types::{DiscoveredContract, InterfaceType};
+
+/// The parts of `Move.toml` discovery needs
+struct Manifest {
+    name: String,
+    /// Named addresses that resolve to a literal, normalized
+    addresses: HashMap<String, String>,
+}
+
+fn read_manifest(project: &Path) -> Option<Manifest> {
+    let text = std::fs::read_to_string(project.join("Move.toml")).ok()?;
+    let manifest: toml::Table = toml::from_str(&text).ok()?;
+    let name = manifest.get("package")?.get("name")?.as_str()?.to_string();
+
+    // "_" leaves the address to be supplied at publish time
+    let addresses = manifest
+        .get("addresses")
+        .and_then(|a| a.as_table())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(named, value)| {
+                    let address = normalize_address(value.as_str()?).ok()?;
+                    Some((named.clone(), address))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Manifest { name, addresses })
+}
+
+/// Finds the modules of the Move package at the project root
+pub fn discover(project: &Path) -> Vec<DiscoveredContract> {
+    let Some(manifest) = read_manifest(project) else {
+        return vec![];
+    };
+    let build = project.join("build").join(&manifest.name);
+
+    let compiled: Option<HashSet<String>> = {
+        std::fs::read_dir(build.join("bytecode_modules")).ok().map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "mv"))
+                .filter_map(|p| Some(p.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+    };
+    let mut abis = compiled_abis(&build);
+
+    let mut discovered = Vec::new();
+    for entry in WalkDir::new(project.join("sources"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "move"))
+    {
+        let Ok(source) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let path = entry
+            .path()
+            .strip_prefix(project)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .to_string();
+
+        for module in parse_modules(&source) {
+            let included = match &compiled {
+                Some(names) => names.contains(&module.name),
+                None => !module.test_only,
+            };
+            if !included {
+                continue;
+            }
+
+            let (address, functions) = match abis.remove(&module.name) {
+                Some(abi) => (Some(abi.address), merge_functions(abi.functions, module.functions)),
+                // Named addresses can look like hex (`cafe`), literals always start with 0x
+                None if module.address.starts_with("0x") => (normalize_address(&module.address).ok(), module.functions),
+                None => (manifest.addresses.get(&module.address).cloned(), module.functions),
+            };
+            discovered.push(move_contract(
+                module.name,
+                path.clone(),
+                module.address,
+                address,
+                functions,
+                module.structs,
+            ));
+        }
+    }
+
+    // Built modules whose sources live outside `sources/`
+    for (name, abi) in abis {
+        let path = Path::new("build").join(&manifest.name).join("abis").join(&name);
+        discovered.push(move_contract(
+            name,
+            path.to_string_lossy().to_string(),
+            abi.address.clone(),
+            Some(abi.address),
+            abi.functions,
+            vec![],
+        ));
+    }
+    discovered
+}
+
+/// A discovered module. `written` is its address as the source names it, kept
+/// when that named address doesn't resolve to a literal `address`.
+fn move_contract(
+    name: String,
+    path: String,
+    written: String,
+    address: Option<String>,
+    functions: Vec<Value>,
+    structs: Vec<Value>,
+) -> DiscoveredContract {
+    let definition = json!({
+        "moduleName": name,
+        "moduleAddress": address.clone().unwrap_or(written),
+        "functions": functions,
+        "structs": structs,
+    });
+
+    DiscoveredContract {
+        name,
+        path,
+        interface_type: InterfaceType::Move,
+        is_interface: false,
+        is_dependency: false,
+        abi: None,
+        bytecode: None,
+        compiler_version: None,
+        idl: None,
+        move_definition: Some(definition.to_string()),
+        deployed_address: address,
+    }
+}
+
+/// Entry functions of one built module
+struct CompiledAbi {
+    /// Normalized address the module was compiled for
+    address: String,
+    functions: Vec<Value>,
+}
+
+/// Reads the entry-function ABIs saved under `build/<package>/abis/<module>`,
+/// keyed by module name. Files that don't decode are skipped.
+fn compiled_abis(build: &Path) -> BTreeMap<String, CompiledAbi> {
+    let mut modules: BTreeMap<String, CompiledAbi> = BTreeMap::new();
+
+    for entry in WalkDir::new(build.join("abis"))
+        .min_depth(2)
+        .max_depth(2)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "abi"))
+    {
+        let Some((address, module, function)) =
+            std::fs::read(entry.path()).ok().and_then(|bytes| entry_function_abi(&bytes))
+        else {
+            continue;
+        };
+        modules
+            .entry(module)
+            .or_insert_with(|| CompiledAbi { address, functions: vec![] })
+            .functions
+            .push(function);
+    }
+    modules
+}
+
+/// Decodes a BCS `EntryABI::EntryFunction` into its module address, module
+/// name and the function in the `moveDefinition` shape
+fn entry_function_abi(bytes: &[u8]) -> Option<(String, String, Value)> {
+    let mut bcs = Bcs { bytes, pos: 0 };
+    // Variant 0 is a transaction script
+    if bcs.uleb128()? != 1 {
+        return None;
+    }
+    let name = bcs.string()?;
+    let address = format!("0x{}", hex::encode(bcs.address()?));
+    let module = bcs.string()?;
+    let _doc = bcs.string()?;
+    let type_params = bcs.seq(|b| b.string())?;
+    let params = bcs.seq(|b| {
+        let name = b.string()?;
+        Some(json!({ "name": name, "type": b.type_tag(0)? }))
+    })?;
+    if bcs.pos != bytes.len() {
+        return None;
+    }
+
+    let mut function = json!({ "name": name, "visibility": "entry", "params": params });
+    if !type_params.is_empty() {
+        function["typeParams"] = json!(type_params);
+    }
+    Some((address, module, function))
+}
+
+/// Deepest type nesting read from an ABI, so a malformed file can't recurse forever
+const MAX_TYPE_DEPTH: usize = 16;
+
+/// Reader over BCS-encoded bytes
+struct Bcs<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Bcs<'_> {
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn uleb128(&mut self) -> Option<usize> {
+        let mut value = 0usize;
+        for shift in (0..32).step_by(7) {
+            let byte = *self.take(1)?.first()?;
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.uleb128()?;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    fn address(&mut self) -> Option<[u8; 32]> {
+        self.take(32)?.try_into().ok()
+    }
+
+    fn seq<T>(&mut self, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.uleb128()?;
+        (0..len).map(|_| item(self)).collect()
+    }
+
+    /// A `TypeTag`, written the way the node's REST API writes types
+    fn type_tag(&mut self, depth: usize) -> Option<String> {
+        if depth > MAX_TYPE_DEPTH {
+            return None;
+        }
+        let primitive = match self.uleb128()? {
+            0 => "bool",
+            1 => "u8",
+            2 => "u64",
+            3 => "u128",
+            4 => "address",
+            5 => "signer",
+            6 => return Some(format!("vector<{}>", self.type_tag(depth + 1)?)),
+            7 => {
+                let address = self.address()?;
+                let module = self.string()?;
+                let name = self.string()?;
+                let type_args = self.seq(|b| b.type_tag(depth + 1))?;
+                let mut tag = format!("{}::{}::{}", standard_address(&address), module, name);
+                if !type_args.is_empty() {
+                    tag.push_str(&format!("<{}>", type_args.join(", ")));
+                }
+                return Some(tag);
+            }
+            8 => "u16",
+            9 => "u32",
+            10 => "u256",
+            // Function values and anything newer
+            _ => return None,
+        };
+        Some(primitive.to_string())
+    }
+}
+
+/// Short form for the reserved addresses `0x0`..`0xf`, the full 64 digits otherwise
+fn standard_address(address: &[u8; 32]) -> String {
+    let digits = hex::encode(address);
+    match digits.trim_start_matches('0') {
+        "" => "0x0".to_string(),
+        short if short.len() == 1 => format!("0x{}", short),
+        _ => format!("0x{}", digits),
+    }
+}
+
+/// Entry functions from the compiled ABIs, which are authoritative, followed by
+/// the scanned functions they don't cover. Scanned parameter names fill in any
+/// the ABIs left blank; signer params are skipped when lining the two up.
+fn merge_functions(compiled: Vec<Value>, scanned: Vec<Value>) -> Vec<Value> {
+    let mut functions = compiled;
+
+    for function in &mut functions {
+        let Some(source) = scanned.iter().find(|s| s["name"] == function["name"]) else {
+            continue;
+        };
+        let mut names = source["params"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|p| !p["type"].as_str().is_some_and(is_signer))
+            .map(|p| p["name"].clone());
+
+        for param in function["params"].as_array_mut().into_iter().flatten() {
+            if param["type"].as_str().is_some_and(is_signer) {
+                continue;
+            }
+            let name = names.next();
+            if param["name"] == "" {
+                if let Some(name) = name {
+                    param["name"] = name;
+                }
+            }
+        }
+    }
+
+    // A scanned entry function the build didn't produce is stale or misread
+    let covered: HashSet<String> = functions
+        .iter()
+        .filter_map(|f| f["name"].as_str().map(str::to_string))
+        .collect();
+    functions.extend(scanned.into_iter().filter(|f| {
+        f["visibility"] != "entry" && !f["name"].as_str().is_some_and(|name| covered.contains(name))
+    }));
+    functions
+}
+
+/// A module declared in a Move source file
+#[derive(Debug)]
+pub struct MoveModule {
+    /// Address as written in the source: a literal or a named address
+    pub address: String,
+    pub name: String,
+    /// Public and entry functions in the `moveDefinition` shape
+    pub functions: Vec<Value>,
+    pub structs: Vec<Value>,
+    pub test_only: bool,
+}
+
+/// Reads module, function and struct declarations out of Move source. This is
+/// a declaration scanner, not a parser: function bodies are skipped unread.
+pub fn parse_modules(source: &str) -> Vec<MoveModule> {
+    let mut tokens = Tokens::new(source);
+    let mut modules = Vec::new();
+    let mut block_address: Option<String> = None;
+    let mut attributes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "#" if tokens.peek() == Some("[") => attributes.extend(tokens.attributes()),
+            // Legacy `address 0x1 { module m { .. } }` blocks
+            "address" => {
+                block_address = tokens.next().map(str::to_string);
+                tokens.eat("{");
+            }
+            "module" => {
+                // Move 2's `module a::b;` runs to the end of the file instead of a brace
+                let mut header = Vec::new();
+                let mut braced = false;
+                while let Some(token) = tokens.next() {
+                    match token {
+                        "{" => {
+                            braced = true;
+                            break;
+                        }
+                        ";" => break,
+                        _ => header.push(token),
+                    }
+                }
+                let (address, name) = match header.as_slice() {
+                    [address, "::", name] => (address.to_string(), name.to_string()),
+                    [name] => (block_address.clone().unwrap_or_default(), name.to_string()),
+                    _ => {
+                        if braced {
+                            tokens.skip_block();
+                        }
+                        attributes.clear();
+                        continue;
+                    }
+                };
+                let mut module = MoveModule {
+                    address,
+                    name,
+                    functions: Vec::new(),
+                    structs: Vec::new(),
+                    test_only: attributes.iter().any(|a| a == "test_only"),
+                };
+                tokens.module_body(&mut module);
+                modules.push(module);
+                attributes.clear();
+            }
+            "}" => block_address = None,
+            "{" => tokens.skip_block(),
+            _ => attributes.clear(),
+        }
+    }
+    modules
+}
+
+/// Token spans over Move source with comments and whitespace removed
+struct Tokens<'a> {
+    source: &'a str,
+    spans: Vec<(usize, usize)>,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(source: &'a str) -> Self {
+        let bytes = source.as_bytes();
+        let mut spans = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let c = bytes[i];
+            let start = i;
+            if c.is_ascii_whitespace() {
+                i += 1;
+                continue;
+            } else if bytes[i..].starts_with(b"//") {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            } else if bytes[i..].starts_with(b"/*") {
+                i = source[i + 2..].find("*/").map(|end| i + end + 4).unwrap_or(bytes.len());
+                continue;
+            } else if c == b'"' {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+            } else if c.is_ascii_alphanumeric() || c == b'_' {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+            } else if bytes[i..].starts_with(b"::") {
+                i += 2;
+            } else {
+                // Multi-byte characters only appear in strings and comments
+                i += source[i..].chars().next().map_or(1, char::len_utf8);
+            }
+            spans.push((start, i));
+        }
+
+        Self { source, spans, pos: 0 }
+    }
+
+    fn token(&self, index: usize) -> &'a str {
+        let (start, end) = self.spans[index];
+        &self.source[start..end]
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        (self.pos < self.spans.len()).then(|| self.token(self.pos))
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek()?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let matched = self.peek() == Some(expected);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    /// Source text of tokens `from..to`, whitespace collapsed
+    fn text(&self, from: usize, to: usize) -> String {
+        if from >= to {
+            return String::new();
+        }
+        self.source[self.spans[from].0..self.spans[to - 1].1]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Skips to the brace closing one that was just consumed
+    fn skip_block(&mut self) {
+        let mut depth = 1;
+        while let Some(token) = self.next() {
+            match token {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return;
+            }
+        }
+    }
+
+    /// Reads `[name, name(args), ..]` after a `#`, returning the names
+    fn attributes(&mut self) -> Vec<String> {
+        self.eat("[");
+        self.segments("[", "]")
+            .into_iter()
+            .filter(|(from, to)| from < to)
+            .map(|(from, _)| self.token(from).to_string())
+            .collect()
+    }
+
+    /// Splits the tokens up to the delimiter closing an already consumed `open`
+    /// at top-level commas, returning each piece as a token range
+    fn segments(&mut self, open: &str, close: &str) -> Vec<(usize, usize)> {
+        let mut segments = Vec::new();
+        let mut depth = 0;
+        let mut start = self.pos;
+
+        while let Some(token) = self.next() {
+            match token {
+                t if t == close && depth == 0 => {
+                    segments.push((start, self.pos - 1));
+                    break;
+                }
+                "," if depth == 0 => {
+                    segments.push((start, self.pos - 1));
+                    start = self.pos;
+                }
+                "(" | "<" | "[" | "{" => depth += 1,
+                ")" | ">" | "]" | "}" => depth -= 1,
+                t if t == open => depth += 1,
+                _ => {}
+            }
+        }
+        // A trailing comma leaves an empty last piece
+        segments.retain(|(from, to)| from < to);
+        segments
+    }
+
+    /// Splits `name: Type` segments into name/type pairs
+    fn fields(&self, segments: &[(usize, usize)]) -> Vec<Value> {
+        segments
+            .iter()
+            .filter(|(from, to)| to - from >= 3 && self.token(from + 1) == ":")
+            .map(|&(from, to)| json!({ "name": self.token(from), "type": self.text(from + 2, to) }))
+            .collect()
+    }
+
+    fn module_body(&mut self, module: &mut MoveModule) {
+        let mut attributes: Vec<String> = Vec::new();
+        let mut visibility: Option<&str> = None;
+        let mut entry = false;
+        let mut inline = false;
+
+        while let Some(token) = self.next() {
+            match token {
+                "}" => return,
+                "#" if self.peek() == Some("[") => {
+                    attributes.extend(self.attributes());
+                    continue;
+                }
+                "public" => {
+                    visibility = Some("public");
+                    if self.eat("(") {
+                        // public(friend) and public(package) aren't callable from outside
+                        match self.next() {
+                            Some("script") => entry = true,
+                            _ => visibility = Some("friend"),
+                        }
+                        self.eat(")");
+                    }
+                    continue;
+                }
+                // Move 2 `friend fun` and `package fun`; `friend a::b;` declares a friend
+                "friend" | "package" if matches!(self.peek(), Some("fun" | "entry" | "inline" | "native")) => {
+                    visibility = Some("friend");
+                    continue;
+                }
+                "entry" => {
+                    entry = true;
+                    continue;
+                }
+                "native" => continue,
+                // Inlined and macro functions are expanded at compile time and can't be called
+                "inline" | "macro" => {
+                    inline = true;
+                    continue;
+                }
+                "fun" => {
+                    let callable = !inline && (entry || visibility == Some("public"));
+                    let is_view = attributes.iter().any(|a| a == "view");
+                    let test_only = attributes.iter().any(|a| a == "test_only" || a == "test");
+                    if let Some(mut function) = self.function() {
+                        if callable && !test_only {
+                            function["visibility"] = json!(if entry { "entry" } else { "public" });
+                            if is_view {
+                                function["isView"] = json!(true);
+                            }
+                            module.functions.push(function);
+                        }
+                    }
+                }
+                "struct" => {
+                    let test_only = attributes.iter().any(|a| a == "test_only");
+                    if let Some(structure) = self.structure() {
+                        if !test_only {
+                            module.structs.push(structure);
+                        }
+                    }
+                }
+                "{" => self.skip_block(),
+                _ => {}
+            }
+            attributes.clear();
+            visibility = None;
+            entry = false;
+            inline = false;
+        }
+    }
+
+    /// Reads a function signature after `fun` and skips its body
+    fn function(&mut self) -> Option<Value> {
+        let name = self.next()?.to_string();
+
+        let mut type_params = Vec::new();
+        if self.eat("<") {
+            for (from, to) in self.segments("<", ">") {
+                let from = if self.token(from) == "phantom" { from + 1 } else { from };
+                if from < to {
+                    type_params.push(self.token(from).to_string());
+                }
+            }
+        }
+
+        if !self.eat("(") {
+            return None;
+        }
+        let params = self.segments("(", ")");
+        let params = self.fields(&params);
+
+        let mut return_type = Vec::new();
+        if self.eat(":") {
+            if self.eat("(") {
+                for (from, to) in self.segments("(", ")") {
+                    return_type.push(self.text(from, to));
+                }
+            } else {
+                let from = self.pos;
+                while !matches!(self.peek(), None | Some("{" | ";" | "acquires")) {
+                    self.pos += 1;
+                }
+                return_type.push(self.text(from, self.pos));
+            }
+        }
+
+        // acquires list, then the body or `;` for native functions
+        while let Some(token) = self.next() {
+            match token {
+                "{" => {
+                    self.skip_block();
+                    break;
+                }
+                ";" => break,
+                _ => {}
+            }
+        }
+
+        let mut function = json!({ "name": name, "params": params });
+        if !type_params.is_empty() {
+            function["typeParams"] = json!(type_params);
+        }
+        if !return_type.is_empty() {
+            function["returnType"] = json!(return_type);
+        }
+        Some(function)
+    }
+
+    /// Reads a struct declaration after `struct`
+    fn structure(&mut self) -> Option<Value> {
+        let name = self.next()?.to_string();
+        if self.eat("<") {
+            self.segments("<", ">");
+        }
+
+        let mut fields = Vec::new();
+        while let Some(token) = self.next() {
+            match token {
+                "{" => {
+                    let segments = self.segments("{", "}");
+                    fields = self.fields(&segments);
+                    break;
+                }
+                // Positional fields, named by index
+                "(" => {
+                    fields = self
+                        .segments("(", ")")
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (from, to))| json!({ "name": i.to_string(), "type": self.text(from, to) }))
+                        .collect();
+                }
+                ";" => break,
+                _ => {}
+            }
+        }
+        Some(json!({ "name": name, "fields": fields }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+        /// Coin vault
+        module vault::bank {
+            use std::signer;
+            friend vault::admin;
+
+            const E_EMPTY: u64 = 1; // "not } a brace"
+
+            struct Vault<phantom CoinType> has key {
+                balance: u64,
+                owners: vector<address>,
+            }
+
+            /* entry fun hidden() {} */
+            public entry fun deposit<CoinType>(account: &signer, amount: u64) acquires Vault {
+                if (amount == 0) { abort E_EMPTY };
+            }
+
+            #[view]
+            public fun balance_of(owner: address): u64 { 0 }
+
+            public fun split(amount: u64): (u64, Option<u64>) { (amount, option::none()) }
+
+            public(friend) fun reset() {}
+
+            fun internal(x: u64): u64 { x }
+
+            #[test_only]
+            public fun setup() {}
+        }
+
+        #[test_only]
+        module vault::bank_tests {
+            #[test]
+            fun test_deposit() {}
+        }
+    "#;
+
+    #[test]
+    fn test_parses_move_module_interface() {
+        let modules = parse_modules(SOURCE);
+        assert_eq!(modules.len(), 2);
+        assert!(modules[1].test_only);
+
+        let bank = &modules[0];
+        assert_eq!((bank.address.as_str(), bank.name.as_str()), ("vault", "bank"));
+        assert!(!bank.test_only);
+
+        let names: Vec<_> = bank.functions.iter().map(|f| f["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["deposit", "balance_of", "split"]);
+
+        assert_eq!(
+            bank.functions[0],
+            json!({
+                "name": "deposit",
+                "visibility": "entry",
+                "typeParams": ["CoinType"],
+                "params": [
+                    {"name": "account", "type": "&signer"},
+                    {"name": "amount", "type": "u64"}
+                ]
+            })
+        );
+        assert_eq!(bank.functions[1]["isView"], json!(true));
+        assert_eq!(bank.functions[1]["returnType"], json!(["u64"]));
+        assert_eq!(bank.functions[2]["returnType"], json!(["u64", "Option<u64>"]));
+
+        assert_eq!(
+            bank.structs,
+            vec![json!({
+                "name": "Vault",
+                "fields": [
+                    {"name": "balance", "type": "u64"},
+                    {"name": "owners", "type": "vector<address>"}
+                ]
+            })]
+        );
+    }
+
+    #[test]
+    fn test_discovers_compiled_modules_with_named_addresses() {
+        let dir = std::env::temp_dir().join(format!("coco-move-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("sources")).unwrap();
+        std::fs::create_dir_all(dir.join("build/Vault/bytecode_modules/dependencies")).unwrap();
+        std::fs::write(
+            dir.join("Move.toml"),
+            "[package]\nname = \"Vault\"\nversion = \"1.0.0\"\n\n[addresses]\nvault = \"0xCAFE\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("sources/bank.move"), SOURCE).unwrap();
+        std::fs::write(dir.join("build/Vault/bytecode_modules/bank.mv"), [0xa1, 0x1c, 0xeb, 0x0b]).unwrap();
+
+        let discovered = discover(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(discovered.len(), 1);
+        let bank = &discovered[0];
+        assert_eq!(bank.name, "bank");
+        assert!(matches!(bank.interface_type, InterfaceType::Move));
+        assert_eq!(bank.path, Path::new("sources").join("bank.move").to_string_lossy());
+
+        let address = format!("0x{:0>64}", "cafe");
+        assert_eq!(bank.deployed_address.as_deref(), Some(address.as_str()));
+        let definition: Value = serde_json::from_str(bank.move_definition.as_ref().unwrap()).unwrap();
+        assert_eq!(definition["moduleAddress"], json!(address));
+        assert_eq!(definition["functions"].as_array().unwrap().len(), 3);
+    }
+
+    const MOVE_2_SOURCE: &str = r#"
+        module vault::bank;
+
+        use std::string::String;
+        friend vault::admin;
+
+        enum Status has copy, drop { Open, Closed { at: u64 } }
+
+        public entry fun deposit<CoinType>(account: &signer, amount: u64, memos: vector<String>) {}
+
+        #[view]
+        public fun balance_of(owner: address): u64 { 0 }
+
+        public(package) fun reset() {}
+        package fun sweep() {}
+        friend entry fun admin_deposit(admin: &signer) {}
+        public inline fun double(x: u64): u64 { x * 2 }
+        public macro fun apply($f: |u64| u64, $x: u64): u64 { $f($x) }
+        entry fun stale() {}
+    "#;
+
+    #[test]
+    fn test_parses_move_2_module() {
+        let modules = parse_modules(MOVE_2_SOURCE);
+        assert_eq!(modules.len(), 1);
+        assert_eq!((modules[0].address.as_str(), modules[0].name.as_str()), ("vault", "bank"));
+
+        let names: Vec<_> = modules[0].functions.iter().map(|f| f["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["deposit", "balance_of", "admin_deposit", "stale"]);
+        assert_eq!(modules[0].functions[2]["visibility"], json!("entry"));
+    }
+
+    /// BCS of an `EntryABI::EntryFunction` for a module at `0xcafe`
+    fn entry_abi(module: &str, function: &str, type_params: &[&str], params: &[(&str, &[u8])]) -> Vec<u8> {
+        fn string(out: &mut Vec<u8>, s: &str) {
+            out.push(s.len() as u8);
+            out.extend(s.as_bytes());
+        }
+        let mut address = [0u8; 32];
+        address[30..].copy_from_slice(&[0xca, 0xfe]);
+
+        let mut out = vec![1];
+        string(&mut out, function);
+        out.extend(address);
+        string(&mut out, module);
+        string(&mut out, "");
+        out.push(type_params.len() as u8);
+        for name in type_params {
+            string(&mut out, name);
+        }
+        out.push(params.len() as u8);
+        for (name, type_tag) in params {
+            string(&mut out, name);
+            out.extend(*type_tag);
+        }
+        out
+    }
+
+    #[test]
+    fn test_compiled_abis_are_authoritative() {
+        let dir = std::env::temp_dir().join(format!("coco-move-{}", uuid::Uuid::new_v4()));
+        let build = dir.join("build/Vault");
+        std::fs::create_dir_all(dir.join("sources")).unwrap();
+        std::fs::create_dir_all(build.join("bytecode_modules")).unwrap();
+        std::fs::create_dir_all(build.join("abis/bank")).unwrap();
+        std::fs::create_dir_all(build.join("abis/registry")).unwrap();
+        std::fs::write(
+            dir.join("Move.toml"),
+            "[package]\nname = \"Vault\"\nversion = \"1.0.0\"\n\n[addresses]\nvault = \"_\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("sources/bank.move"), MOVE_2_SOURCE).unwrap();
+        for module in ["bank", "registry"] {
+            std::fs::write(build.join(format!("bytecode_modules/{}.mv", module)), [0xa1, 0x1c, 0xeb, 0x0b]).unwrap();
+        }
+
+        let mut strings = vec![6, 7];
+        strings.extend([0u8; 31]);
+        strings.push(1);
+        strings.extend(b"\x06string\x06String\x00");
+        std::fs::write(
+            build.join("abis/bank/deposit.abi"),
+            entry_abi("bank", "deposit", &["CoinType"], &[("amount", &[2]), ("", &strings)]),
+        )
+        .unwrap();
+        std::fs::write(build.join("abis/bank/admin_deposit.abi"), entry_abi("bank", "admin_deposit", &[], &[])).unwrap();
+        std::fs::write(build.join("abis/registry/register.abi"), entry_abi("registry", "register", &[], &[("name", &[6, 1])])).unwrap();
+        std::fs::write(build.join("abis/bank/broken.abi"), [1, 200]).unwrap();
+
+        let discovered = discover(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        let address = format!("0x{:0>64}", "cafe");
+        assert_eq!(discovered.len(), 2);
+        let bank = &discovered[0];
+        assert_eq!(bank.deployed_address.as_deref(), Some(address.as_str()));
+        let definition: Value = serde_json::from_str(bank.move_definition.as_ref().unwrap()).unwrap();
+        assert_eq!(definition["moduleAddress"], json!(address));
+
+        let names: Vec<_> = definition["functions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["admin_deposit", "deposit", "balance_of"]);
+        assert_eq!(
+            definition["functions"][1],
+            json!({
+                "name": "deposit",
+                "visibility": "entry",
+                "typeParams": ["CoinType"],
+                "params": [
+                    {"name": "amount", "type": "u64"},
+                    {"name": "memos", "type": "vector<0x1::string::String>"}
+                ]
+            })
+        );
+
+        // No source for this one, so the ABI is all there is
+        let registry = &discovered[1];
+        assert_eq!(registry.name, "registry");
+        assert_eq!(registry.path, Path::new("build/Vault/abis/registry").to_string_lossy());
+        let definition: Value = serde_json::from_str(registry.move_definition.as_ref().unwrap()).unwrap();
+        assert_eq!(definition["functions"][0]["params"], json!([{"name": "name", "type": "vector<u8>"}]));
+    }
+}
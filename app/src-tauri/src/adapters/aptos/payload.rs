@@ -0,0 +1,242 @@
+//! Conversion of form arguments into Aptos JSON payloads, driven either by the
+//! `moveDefinition` saved on a contract or by the module ABI served by the node.
+
+use serde_json::{json, Value};
+
+use crate::error::CocoError;
+use crate::types::ContractArg;
+
+/// Payload key prefix for generic type arguments, e.g. `type_arg:0`
+pub const TYPE_ARG_PREFIX: &str = "type_arg:";
+
+/// The parts of a Move function needed to build a payload
+#[derive(Debug, Clone)]
+pub struct MoveFunctionDef {
+    /// Fully qualified `address::module::name`
+    pub id: String,
+    /// Parameter name (when known) and Move type, excluding signer params
+    pub params: Vec<(Option<String>, String)>,
+    pub type_param_count: usize,
+    pub is_entry: bool,
+    pub is_view: bool,
+}
+
+pub fn is_signer(ty: &str) -> bool {
+    matches!(ty.trim(), "signer" | "&signer")
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|v| v.as_slice())
+        .unwrap_or(&[])
+}
+
+/// Looks up a function in a `moveDefinition` (moduleAddress, moduleName, functions)
+pub fn from_definition(definition: &Value, function: &str) -> Result<MoveFunctionDef, CocoError> {
+    let address = definition
+        .get("moduleAddress")
+        .and_then(|a| a.as_str())
+        .ok_or_else(|| CocoError::Validation("Move definition has no module address".into()))?;
+    let module = definition
+        .get("moduleName")
+        .and_then(|m| m.as_str())
+        .ok_or_else(|| CocoError::Validation("Move definition has no module name".into()))?;
+
+    let func = array(definition, "functions")
+        .iter()
+        .find(|f| f.get("name").and_then(|n| n.as_str()) == Some(function))
+        .ok_or_else(|| CocoError::NotFound(format!("Function not found in Move definition: {}", function)))?;
+
+    let params = array(func, "params")
+        .iter()
+        .filter_map(|p| {
+            let ty = p.get("type").and_then(|t| t.as_str())?;
+            (!is_signer(ty)).then(|| (p.get("name").and_then(|n| n.as_str()).map(String::from), ty.to_string()))
+        })
+        .collect();
+
+    Ok(MoveFunctionDef {
+        id: format!("{}::{}::{}", normalize_address(address)?, module, function),
+        params,
+        type_param_count: array(func, "typeParams").len(),
+        is_entry: func.get("visibility").and_then(|v| v.as_str()) == Some("entry"),
+        is_view: func.get("isView").and_then(|v| v.as_bool()).unwrap_or(false),
+    })
+}
+
+/// Looks up a function in a module ABI as returned by `/accounts/{address}/module/{name}`
+pub fn from_module_abi(abi: &Value, function: &str) -> Result<MoveFunctionDef, CocoError> {
+    let address = abi.get("address").and_then(|a| a.as_str()).unwrap_or_default();
+    let module = abi.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+
+    let func = array(abi, "exposed_functions")
+        .iter()
+        .find(|f| f.get("name").and_then(|n| n.as_str()) == Some(function))
+        .ok_or_else(|| CocoError::NotFound(format!("Function not found in module {}: {}", module, function)))?;
+
+    Ok(MoveFunctionDef {
+        id: format!("{}::{}::{}", normalize_address(address)?, module, function),
+        params: array(func, "params")
+            .iter()
+            .filter_map(|p| p.as_str())
+            .filter(|ty| !is_signer(ty))
+            .map(|ty| (None, ty.to_string()))
+            .collect(),
+        type_param_count: array(func, "generic_type_params").len(),
+        is_entry: func.get("is_entry").and_then(|v| v.as_bool()).unwrap_or(false),
+        is_view: func.get("is_view").and_then(|v| v.as_bool()).unwrap_or(false),
+    })
+}
+
+/// Builds the `function`, `type_arguments` and `arguments` triple shared by
+/// entry-function payloads and view requests
+fn function_call(def: &MoveFunctionDef, args: &[ContractArg]) -> Result<Value, CocoError> {
+    let type_arguments = (0..def.type_param_count)
+        .map(|i| {
+            args.iter()
+                .find(|a| a.name == format!("{}{}", TYPE_ARG_PREFIX, i))
+                .map(|a| text(&a.value))
+                .filter(|t| !t.is_empty())
+                .ok_or_else(|| CocoError::Validation(format!("Missing type argument {} for {}", i, def.id)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let plain: Vec<&ContractArg> = args.iter().filter(|a| !a.name.contains(':')).collect();
+    let arguments = def
+        .params
+        .iter()
+        .enumerate()
+        .map(|(i, (name, ty))| {
+            let label = name.clone().unwrap_or_else(|| format!("arg{}", i));
+            let arg = name
+                .as_ref()
+                .and_then(|n| plain.iter().find(|a| &a.name == n))
+                .or_else(|| plain.iter().find(|a| a.name == format!("arg{}", i)))
+                .or_else(|| plain.get(i))
+                .ok_or_else(|| CocoError::Validation(format!("Missing argument {} for {}", label, def.id)))?;
+            convert_arg(ty, &arg.value)
+                .map_err(|e| CocoError::Validation(format!("Invalid value for {}: {}", label, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(json!({
+        "function": def.id,
+        "type_arguments": type_arguments,
+        "arguments": arguments,
+    }))
+}
+
+/// Builds an `entry_function_payload` for submission or simulation
+pub fn entry_payload(def: &MoveFunctionDef, args: &[ContractArg]) -> Result<Value, CocoError> {
+    let mut payload = function_call(def, args)?;
+    payload["type"] = json!("entry_function_payload");
+    Ok(payload)
+}
+
+/// Builds a request body for `/view`
+pub fn view_request(def: &MoveFunctionDef, args: &[ContractArg]) -> Result<Value, CocoError> {
+    function_call(def, args)
+}
+
+/// Normalizes an account address to its long 0x-prefixed 64 hex digit form
+pub fn normalize_address(address: &str) -> Result<String, CocoError> {
+    let digits = address.trim().trim_start_matches("0x");
+    if digits.is_empty() || digits.len() > 64 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(CocoError::Validation(format!("Invalid Aptos address: {}", address)));
+    }
+    Ok(format!("0x{:0>64}", digits.to_lowercase()))
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Form values arrive as strings, so lists accept JSON text or comma-separated items
+fn list(value: &Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items.clone(),
+        Value::String(s) => match serde_json::from_str::<Value>(s) {
+            Ok(Value::Array(items)) => items,
+            _ if s.trim().is_empty() => vec![],
+            _ => s.split(',').map(|v| Value::String(v.trim().to_string())).collect(),
+        },
+        other => vec![other.clone()],
+    }
+}
+
+fn generic_inner<'a>(ty: &'a str, outer: &str) -> Option<&'a str> {
+    let rest = ty.strip_prefix(outer)?.strip_prefix('<')?;
+    rest.strip_suffix('>').map(str::trim)
+}
+
+/// Converts a form value into the JSON shape the Aptos API expects for a Move type
+pub fn convert_arg(ty: &str, value: &Value) -> Result<Value, String> {
+    let ty = ty.trim();
+    let raw = text(value);
+
+    match ty {
+        "u8" | "u16" | "u32" => raw
+            .parse::<u32>()
+            .ok()
+            .filter(|n| ty != "u8" || *n <= u8::MAX as u32)
+            .filter(|n| ty != "u16" || *n <= u16::MAX as u32)
+            .map(Value::from)
+            .ok_or_else(|| format!("expected {}, got {}", ty, raw)),
+        // 64-bit and wider integers are passed as strings
+        "u64" | "u128" | "u256" => {
+            if !raw.is_empty() && raw.chars().all(|c| c.is_ascii_digit()) {
+                Ok(Value::String(raw))
+            } else {
+                Err(format!("expected {}, got {}", ty, raw))
+            }
+        }
+        "bool" => match raw.as_str() {
+            "true" | "1" => Ok(Value::Bool(true)),
+            "false" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("expected a boolean, got {}", raw)),
+        },
+        "address" => normalize_address(&raw).map(Value::String).map_err(|e| e.to_string()),
+        "string" | "String" | "0x1::string::String" => match value {
+            Value::String(s) => Ok(Value::String(s.clone())),
+            other => Ok(Value::String(other.to_string())),
+        },
+        "vector" | "vector<u8>" => {
+            if let Some(hex_value) = raw.strip_prefix("0x") {
+                hex::decode(hex_value).map_err(|e| format!("invalid hex: {}", e))?;
+                Ok(Value::String(raw))
+            } else if let Ok(bytes) = serde_json::from_str::<Vec<u8>>(&raw) {
+                Ok(Value::String(format!("0x{}", hex::encode(bytes))))
+            } else {
+                Ok(Value::String(format!("0x{}", hex::encode(raw.as_bytes()))))
+            }
+        }
+        _ => {
+            if let Some(inner) = generic_inner(ty, "vector") {
+                return list(value)
+                    .iter()
+                    .map(|item| convert_arg(inner, item))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array);
+            }
+            if let Some(inner) = generic_inner(ty, "0x1::option::Option").or_else(|| generic_inner(ty, "Option")) {
+                if raw.is_empty() || raw == "null" {
+                    return Ok(json!({ "vec": [] }));
+                }
+                return Ok(json!({ "vec": [convert_arg(inner, value)?] }));
+            }
+            if generic_inner(ty, "0x1::object::Object").or_else(|| generic_inner(ty, "Object")).is_some() {
+                return normalize_address(&raw).map(Value::String).map_err(|e| e.to_string());
+            }
+            // Structs and anything else are passed through as JSON
+            match value {
+                Value::String(s) => Ok(serde_json::from_str(s).unwrap_or_else(|_| value.clone())),
+                other => Ok(other.clone()),
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::CocoError;
+
+/// Transport for Aptos REST calls, swappable for a recorded stand-in in tests
+#[async_trait]
+pub trait RestTransport: Send + Sync {
+    /// GETs a URL; a 404 surfaces as `CocoError::NotFound`
+    async fn get(&self, url: &str) -> Result<Value, CocoError>;
+
+    /// POSTs a JSON body to a URL; a 404 surfaces as `CocoError::NotFound`
+    async fn post(&self, url: &str, body: Value) -> Result<Value, CocoError>;
+}
+
+/// REST over HTTP
+pub struct HttpTransport {
+    client: reqwest::Client,
+}
+
+impl HttpTransport {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn read(response: reqwest::Response) -> Result<Value, CocoError> {
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| CocoError::Adapter(format!("Invalid REST response: {}", e)))?;
+
+        if status.is_success() {
+            return Ok(body);
+        }
+
+        // Aptos errors look like {"message": ..., "error_code": ..., "vm_error_code": ...}
+        let message = body
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| body.to_string());
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            Err(CocoError::NotFound(message))
+        } else {
+            Err(CocoError::Adapter(format!("Aptos API error ({}): {}", status, message)))
+        }
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RestTransport for HttpTransport {
+    async fn get(&self, url: &str) -> Result<Value, CocoError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| CocoError::Adapter(format!("REST request failed: {}", e)))?;
+        Self::read(response).await
+    }
+
+    async fn post(&self, url: &str, body: Value) -> Result<Value, CocoError> {
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| CocoError::Adapter(format!("REST request failed: {}", e)))?;
+        Self::read(response).await
+    }
+}
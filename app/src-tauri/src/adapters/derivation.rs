@@ -0,0 +1,149 @@
+use ethers::signers::coins_bip39::{English, Mnemonic};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use crate::error::CocoError;
+use crate::types::Ecosystem;
+
+type HmacSha512 = Hmac<Sha512>;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Generates a new English BIP-39 mnemonic with the given number of words
+pub fn generate_mnemonic(word_count: usize) -> Result<String, CocoError> {
+    Mnemonic::<English>::new_with_count(&mut rand::thread_rng(), word_count)
+        .map(|m| m.to_phrase())
+        .map_err(|e| CocoError::Validation(format!("Invalid word count: {}", e)))
+}
+
+/// Standard derivation path for the account at `index`, matching what
+/// MetaMask, Phantom and Petra derive from the same phrase
+pub fn hd_path(ecosystem: &Ecosystem, index: u32) -> String {
+    match ecosystem {
+        Ecosystem::Evm => format!("m/44'/60'/0'/0/{}", index),
+        Ecosystem::Solana => format!("m/44'/501'/{}'/0'", index),
+        Ecosystem::Aptos => format!("m/44'/637'/{}'/0'/0'", index),
+    }
+}
+
+/// Converts a BIP-39 mnemonic phrase into its 64-byte seed
+pub fn mnemonic_to_seed(phrase: &str) -> Result<[u8; 64], CocoError> {
+    let normalized = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mnemonic = Mnemonic::<English>::new_from_phrase(&normalized)
+        .map_err(|e| CocoError::Validation(format!("Invalid mnemonic: {}", e)))?;
+    mnemonic
+        .to_seed(None)
+        .map_err(|e| CocoError::Crypto(format!("Failed to derive seed: {}", e)))
+}
+
+/// Parses a derivation path such as `m/44'/501'/0'/0'` into child indexes
+pub fn parse_path(path: &str) -> Result<Vec<u32>, CocoError> {
+    let mut segments = path.trim().split('/');
+    if segments.next() != Some("m") {
+        return Err(CocoError::Validation(format!(
+            "Derivation path must start with m/: {}",
+            path
+        )));
+    }
+
+    segments
+        .map(|segment| {
+            let (index, hardened) = match segment.strip_suffix('\'').or_else(|| segment.strip_suffix('h')) {
+                Some(index) => (index, true),
+                None => (segment, false),
+            };
+            let index: u32 = index.parse().map_err(|_| {
+                CocoError::Validation(format!("Invalid derivation path segment: {}", segment))
+            })?;
+            if index >= HARDENED_OFFSET {
+                return Err(CocoError::Validation(format!(
+                    "Derivation index out of range: {}",
+                    segment
+                )));
+            }
+            Ok(if hardened { index + HARDENED_OFFSET } else { index })
+        })
+        .collect()
+}
+
+/// Derives an ed25519 secret key from a seed following SLIP-10.
+/// ed25519 only supports hardened derivation, so every segment must be hardened.
+pub fn derive_ed25519(seed: &[u8], path: &str) -> Result<[u8; 32], CocoError> {
+    let (mut key, mut chain_code) = split_hmac(b"ed25519 seed", seed);
+
+    for index in parse_path(path)? {
+        if index < HARDENED_OFFSET {
+            return Err(CocoError::Validation(format!(
+                "ed25519 derivation requires hardened segments: {}",
+                path
+            )));
+        }
+
+        let mut data = Vec::with_capacity(37);
+        data.push(0u8);
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&index.to_be_bytes());
+        (key, chain_code) = split_hmac(&chain_code, &data);
+    }
+
+    Ok(key)
+}
+
+fn split_hmac(key: &[u8], data: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip10_ed25519_vector() {
+        // SLIP-10 test vector 1 for ed25519
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let master = derive_ed25519(&seed, "m").unwrap();
+        assert_eq!(
+            hex::encode(master),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+
+        let child = derive_ed25519(&seed, "m/0'/1'/2'/2'/1000000000'").unwrap();
+        assert_eq!(
+            hex::encode(child),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+    }
+
+    #[test]
+    fn test_hd_paths_parse() {
+        assert_eq!(hd_path(&Ecosystem::Evm, 3), "m/44'/60'/0'/0/3");
+        assert_eq!(hd_path(&Ecosystem::Solana, 3), "m/44'/501'/3'/0'");
+        assert_eq!(hd_path(&Ecosystem::Aptos, 3), "m/44'/637'/3'/0'/0'");
+
+        // ed25519 chains must only use hardened segments
+        assert!(derive_ed25519(&[0u8; 64], &hd_path(&Ecosystem::Solana, 2)).is_ok());
+        assert!(derive_ed25519(&[0u8; 64], &hd_path(&Ecosystem::Aptos, 2)).is_ok());
+    }
+
+    #[test]
+    fn test_generated_mnemonic_round_trips() {
+        let phrase = generate_mnemonic(12).unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 12);
+        assert!(mnemonic_to_seed(&phrase).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_non_hardened_ed25519_path() {
+        assert!(derive_ed25519(&[0u8; 64], "m/44'/501'/0").is_err());
+        assert!(parse_path("44'/501'").is_err());
+    }
+}
@@ -0,0 +1,950 @@
+use async_trait::async_trait;
+use ethers::abi::HumanReadableParser;
+use ethers::providers::{Http, JsonRpcClient, Middleware, Provider, RpcError};
+use ethers::signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer};
+use ethers::types::{
+    transaction::eip2718::TypedTransaction, transaction::eip2930::AccessList, Address, BlockNumber,
+    Bytes, Eip1559TransactionRequest, Eip2930TransactionRequest, NameOrAddress, Signature, H256, U256,
+};
+use ethers::utils::to_checksum;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::abi::{self, ContractAbi};
+use crate::adapters::signer::SoftwareSigner;
+use crate::adapters::traits::*;
+use crate::artifacts;
+use crate::error::CocoError;
+use crate::types::*;
+
+/// Default derivation path for EVM accounts (BIP-44 coin type 60)
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+/// How far a replacement's fees must exceed the original's; geth requires 10%
+const REPLACEMENT_FEE_BUMP_PERCENT: u64 = 15;
+/// Blocks of fee history sampled for priority fee tiers
+const FEE_HISTORY_BLOCKS: u64 = 10;
+/// Reward percentiles for the slow, normal and fast tiers
+const FEE_HISTORY_PERCENTILES: [f64; 3] = [25.0, 50.0, 75.0];
+/// Priority fee used when recent blocks carry no rewards (1 gwei)
+const FALLBACK_PRIORITY_FEE: u64 = 1_000_000_000;
+
+/// EVM adapter that talks to a node over JSON-RPC using ethers
+pub struct EvmAdapter<P: JsonRpcClient = Http> {
+    config: ChainConfig,
+    provider: Provider<P>,
+}
+
+impl EvmAdapter<Http> {
+    /// Creates an adapter connected to the chain's `rpc_url` over HTTP
+    pub fn new(config: ChainConfig) -> Result<Self, CocoError> {
+        let provider = Provider::<Http>::try_from(config.rpc_url.as_str()).map_err(|e| {
+            CocoError::Adapter(format!("Invalid RPC URL {}: {}", config.rpc_url, e))
+        })?;
+        Ok(Self::with_provider(config, provider))
+    }
+}
+
+impl<P: JsonRpcClient + Clone + 'static> EvmAdapter<P> {
+    /// Creates an adapter over an existing provider (e.g. a mock transport in tests)
+    pub fn with_provider(config: ChainConfig, provider: Provider<P>) -> Self {
+        Self { config, provider }
+    }
+
+    /// Fills chain ID, nonce, gas and fees for a transaction from the signer
+    async fn prepare_transaction(
+        &self,
+        signer: &dyn ExternalSigner,
+        tx: &TransactionRequest,
+    ) -> Result<TypedTransaction, CocoError> {
+        let from = parse_address(&signer.address())?;
+        let chain_id = self.provider.get_chainid().await.map_err(rpc_error)?;
+        let nonce = match tx.nonce {
+            Some(nonce) => U256::from(nonce),
+            None => self
+                .provider
+                .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+                .await
+                .map_err(rpc_error)?,
+        };
+
+        let mut request = to_typed_transaction(tx, Some(from))?;
+        request.set_chain_id(node_u64(chain_id, "chain id")?);
+        request.set_nonce(nonce);
+        self.provider
+            .fill_transaction(&mut request, None)
+            .await
+            .map_err(rpc_error)?;
+        Ok(request)
+    }
+
+    /// Receipts don't carry revert data, so replay the failed transaction as a
+    /// call on the state before its block to recover it
+    async fn replay_revert(&self, hash: H256, block_number: u64) -> Option<RevertInfo> {
+        let tx: ethers::types::Transaction = self.provider.get_transaction(hash).await.ok()??;
+        let request: TypedTransaction = (&tx).into();
+        let block = BlockNumber::Number(block_number.saturating_sub(1).into());
+        match self.provider.call(&request, Some(block.into())).await {
+            // Reverts that depended on earlier transactions in the block don't reproduce
+            Ok(_) => None,
+            Err(e) => revert_from_error(&e),
+        }
+    }
+
+    async fn sign_and_send(
+        &self,
+        signer: &dyn ExternalSigner,
+        request: TypedTransaction,
+    ) -> Result<TransactionResult, CocoError> {
+        let signature = signer.sign_transaction(&request.rlp()).await?;
+        let raw = signed_rlp(&request, &signature)?;
+
+        let pending = self
+            .provider
+            .send_raw_transaction(raw)
+            .await
+            .map_err(|e| CocoError::Adapter(format!("Failed to send transaction: {}", e)))?;
+
+        Ok(TransactionResult {
+            hash: format!("{:?}", pending.tx_hash()),
+            status: TransactionStatus::Pending,
+            nonce: request.nonce().map(|n| n.as_u64()),
+        })
+    }
+}
+
+#[async_trait]
+impl<P: JsonRpcClient + Clone + 'static> ChainAdapter for EvmAdapter<P> {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Evm
+    }
+
+    fn chain_id(&self) -> &str {
+        &self.config.id
+    }
+
+    async fn validate_connection(&self) -> Result<bool, CocoError> {
+        Ok(self.provider.get_chainid().await.is_ok())
+    }
+
+    async fn get_block_height(&self) -> Result<u64, CocoError> {
+        let block = self.provider.get_block_number().await.map_err(rpc_error)?;
+        Ok(block.as_u64())
+    }
+}
+
+#[async_trait]
+impl<P: JsonRpcClient + Clone + 'static> WalletAdapter for EvmAdapter<P> {
+    async fn generate_wallet(&self) -> Result<WalletData, CocoError> {
+        let local = LocalWallet::new(&mut rand::thread_rng());
+        Ok(wallet_data(&local))
+    }
+
+    async fn import_wallet(&self, private_key: &str) -> Result<WalletData, CocoError> {
+        let local = parse_private_key(private_key)?;
+        Ok(wallet_data(&local))
+    }
+
+    async fn import_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        derivation_path: Option<&str>,
+    ) -> Result<WalletData, CocoError> {
+        let local = MnemonicBuilder::<English>::default()
+            .phrase(mnemonic)
+            .derivation_path(derivation_path.unwrap_or(DEFAULT_DERIVATION_PATH))
+            .map_err(|e| CocoError::Validation(format!("Invalid derivation path: {}", e)))?
+            .build()
+            .map_err(|e| CocoError::Validation(format!("Invalid mnemonic: {}", e)))?;
+        Ok(wallet_data(&local))
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<Balance, CocoError> {
+        let address = parse_address(address)?;
+        let balance = self
+            .provider
+            .get_balance(address, None)
+            .await
+            .map_err(rpc_error)?;
+
+        Ok(Balance {
+            native: balance.to_string(),
+            native_decimals: 18,
+            native_symbol: self.config.native_currency.clone(),
+        })
+    }
+
+    async fn get_token_balances(&self, _address: &str) -> Result<Vec<TokenBalance>, CocoError> {
+        // ERC-20 holdings cannot be enumerated over plain JSON-RPC without an indexer
+        Ok(vec![])
+    }
+
+    async fn request_faucet(&self, address: &str) -> Result<String, CocoError> {
+        // Only local development nodes (anvil / hardhat) can mint funds over RPC
+        let target = parse_address(address)?;
+        let current = self
+            .provider
+            .get_balance(target, None)
+            .await
+            .map_err(rpc_error)?;
+        let topped_up = current + U256::exp10(18);
+
+        for method in ["anvil_setBalance", "hardhat_setBalance"] {
+            let result: Result<serde_json::Value, _> = self
+                .provider
+                .request(method, (target, topped_up))
+                .await;
+            if result.is_ok() {
+                return Ok(String::new());
+            }
+        }
+
+        Err(CocoError::Adapter(format!(
+            "No RPC faucet available on {}; use the network's web faucet instead",
+            self.config.name
+        )))
+    }
+
+    async fn sign_message(
+        &self,
+        wallet: &WalletData,
+        message: &[u8],
+    ) -> Result<Vec<u8>, CocoError> {
+        let local = local_wallet(wallet)?;
+        let signature = local
+            .sign_message(message)
+            .await
+            .map_err(|e| CocoError::Crypto(format!("Failed to sign message: {}", e)))?;
+        Ok(signature.to_vec())
+    }
+}
+
+#[async_trait]
+impl<P: JsonRpcClient + Clone + 'static> TransactionAdapter for EvmAdapter<P> {
+    async fn send_transaction(
+        &self,
+        wallet: &WalletData,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let signer = SoftwareSigner::from_wallet(Ecosystem::Evm, wallet)?;
+        self.send_transaction_with_signer(&signer, tx).await
+    }
+
+    async fn get_pending_nonce(&self, address: &str) -> Result<Option<u64>, CocoError> {
+        let nonce = self
+            .provider
+            .get_transaction_count(parse_address(address)?, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(rpc_error)?;
+        Ok(Some(node_u64(nonce, "nonce")?))
+    }
+
+    async fn send_transaction_with_signer(
+        &self,
+        signer: &dyn ExternalSigner,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let request = self.prepare_transaction(signer, &tx).await?;
+        self.sign_and_send(signer, request).await
+    }
+
+    async fn replace_transaction(
+        &self,
+        signer: &dyn ExternalSigner,
+        original_hash: &str,
+        mut tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let original = self
+            .provider
+            .get_transaction(parse_hash(original_hash)?)
+            .await
+            .map_err(rpc_error)?
+            .ok_or_else(|| {
+                CocoError::NotFound(format!("Transaction {} is no longer known to the node", original_hash))
+            })?;
+        if original.block_number.is_some() {
+            return Err(CocoError::Validation(format!(
+                "Transaction {} is already confirmed",
+                original_hash
+            )));
+        }
+        if original.from != parse_address(&signer.address())? {
+            return Err(CocoError::Validation(format!(
+                "Transaction {} was not sent by {}",
+                original_hash,
+                signer.address()
+            )));
+        }
+
+        tx.nonce = Some(node_u64(original.nonce, "nonce")?);
+        let mut request = self.prepare_transaction(signer, &tx).await?;
+        bump_fees(&mut request, &original);
+        self.sign_and_send(signer, request).await
+    }
+
+    async fn get_transaction(&self, hash: &str) -> Result<TransactionData, CocoError> {
+        let tx = self
+            .provider
+            .get_transaction(parse_hash(hash)?)
+            .await
+            .map_err(rpc_error)?
+            .ok_or_else(|| CocoError::NotFound("Transaction not found".into()))?;
+
+        let timestamp = match tx.block_number {
+            Some(number) => self
+                .provider
+                .get_block(number)
+                .await
+                .map_err(rpc_error)?
+                .and_then(|b| i64::try_from(b.timestamp).ok())
+                .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+                .unwrap_or_else(chrono::Utc::now),
+            None => chrono::Utc::now(),
+        };
+
+        Ok(TransactionData {
+            hash: format!("{:?}", tx.hash),
+            from: to_checksum(&tx.from, None),
+            to: tx.to.map(|a| to_checksum(&a, None)),
+            value: Some(tx.value.to_string()),
+            data: Some(tx.input.to_vec()),
+            block_number: tx.block_number.map(|n| n.as_u64()),
+            timestamp,
+        })
+    }
+
+    async fn get_transaction_receipt(&self, hash: &str) -> Result<TransactionReceipt, CocoError> {
+        let receipt = self
+            .provider
+            .get_transaction_receipt(parse_hash(hash)?)
+            .await
+            .map_err(rpc_error)?
+            .ok_or_else(|| CocoError::NotFound("Receipt not available yet".into()))?;
+
+        let block_number = receipt
+            .block_number
+            .ok_or_else(|| CocoError::NotFound("Receipt not available yet".into()))?;
+        let status = receipt.status.map(|s| s.as_u64() == 1).unwrap_or(false);
+        let revert = if status {
+            None
+        } else {
+            let replayed = self.replay_revert(receipt.transaction_hash, block_number.as_u64()).await;
+            Some(replayed.unwrap_or_else(|| abi::revert_info(&[], &[])))
+        };
+
+        Ok(TransactionReceipt {
+            hash: format!("{:?}", receipt.transaction_hash),
+            status,
+            block_number: block_number.as_u64(),
+            block_hash: receipt.block_hash.map(|h| format!("{:?}", h)),
+            gas_used: receipt
+                .gas_used
+                .map(|g| node_u64(g, "gas used"))
+                .transpose()?
+                .unwrap_or_default(),
+            fee: receipt
+                .gas_used
+                .zip(receipt.effective_gas_price)
+                .map(|(gas, price)| (gas * price).to_string()),
+            events: vec![],
+            logs: receipt
+                .logs
+                .iter()
+                .map(|log| Log {
+                    address: to_checksum(&log.address, None),
+                    topics: log.topics.iter().map(|t| format!("{:?}", t)).collect(),
+                    data: log.data.to_vec(),
+                })
+                .collect(),
+            revert,
+            contract_address: receipt.contract_address.map(|a| to_checksum(&a, None)),
+        })
+    }
+
+    async fn estimate_fees(&self, tx: &TransactionRequest) -> Result<FeeEstimate, CocoError> {
+        let gas_limit = match tx.gas_limit {
+            Some(gas_limit) => U256::from(gas_limit),
+            None => {
+                let request = to_typed_transaction(tx, None)?;
+                self.provider
+                    .estimate_gas(&request, None)
+                    .await
+                    .map_err(rpc_error)?
+            }
+        };
+
+        // Chains without a base fee haven't activated EIP-1559
+        let latest = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await
+            .map_err(rpc_error)?;
+        let [slow, normal, fast] = match latest.and_then(|b| b.base_fee_per_gas) {
+            Some(base_fee) => {
+                let history = self
+                    .provider
+                    .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &FEE_HISTORY_PERCENTILES)
+                    .await
+                    .map_err(rpc_error)?;
+                eip1559_tiers(base_fee, &history.reward)
+            }
+            None => legacy_tiers(self.provider.get_gas_price().await.map_err(rpc_error)?),
+        };
+
+        Ok(FeeEstimate {
+            gas_limit: node_u64(gas_limit, "gas limit")?,
+            slow: fee_tier(gas_limit, slow)?,
+            normal: fee_tier(gas_limit, normal)?,
+            fast: fee_tier(gas_limit, fast)?,
+        })
+    }
+
+    async fn simulate_transaction(
+        &self,
+        tx: &TransactionRequest,
+    ) -> Result<SimulationResult, CocoError> {
+        let from = tx.from.as_deref().map(parse_address).transpose()?;
+        let request = to_typed_transaction(tx, from)?;
+
+        match self.provider.call(&request, None).await {
+            Ok(return_data) => {
+                let gas_used = self
+                    .provider
+                    .estimate_gas(&request, None)
+                    .await
+                    .map(|g| node_u64(g, "gas estimate"))
+                    .unwrap_or(Ok(0))?;
+
+                Ok(SimulationResult {
+                    success: true,
+                    return_data: return_data.to_vec(),
+                    gas_used,
+                    logs: vec![],
+                    revert: None,
+                })
+            }
+            // A revert is a valid simulation outcome; transport failures are not
+            Err(e) => match revert_from_error(&e) {
+                Some(revert) => Ok(SimulationResult {
+                    success: false,
+                    return_data: e
+                        .as_error_response()
+                        .and_then(|r| r.as_revert_data())
+                        .map(|data| data.to_vec())
+                        .unwrap_or_default(),
+                    gas_used: 0,
+                    logs: vec![],
+                    revert: Some(revert),
+                }),
+                None => Err(rpc_error(e)),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<P: JsonRpcClient + Clone + 'static> ContractAdapter for EvmAdapter<P> {
+    fn deployment_request(
+        &self,
+        bytecode: &[u8],
+        constructor_args: &[u8],
+    ) -> Result<TransactionRequest, CocoError> {
+        if bytecode.is_empty() {
+            return Err(CocoError::Validation("Contract has no creation bytecode".into()));
+        }
+
+        // A creation transaction has no recipient; its data is the bytecode followed by the args
+        let mut code = bytecode.to_vec();
+        code.extend_from_slice(constructor_args);
+        Ok(TransactionRequest {
+            data: Some(code),
+            ..Default::default()
+        })
+    }
+
+    async fn call_contract(
+        &self,
+        address: &str,
+        function: &str,
+        args: Vec<ContractArg>,
+    ) -> Result<ContractCallResult, CocoError> {
+        // Without an ABI the function must be given as a human-readable signature,
+        // e.g. "function balanceOf(address) view returns (uint256)"
+        let signature = if function.trim_start().starts_with("function ") {
+            function.to_string()
+        } else {
+            format!("function {}", function)
+        };
+        let function = HumanReadableParser::parse_function(&signature).map_err(|e| {
+            CocoError::Validation(format!("Invalid function signature {}: {}", function, e))
+        })?;
+
+        let name = function.name.clone();
+        let abi = ContractAbi::from_function(function);
+        let data = abi.encode_call(&name, &args)?;
+        let tx = TransactionRequest {
+            to: Some(address.to_string()),
+            data: Some(data),
+            ..Default::default()
+        };
+
+        let return_data = self
+            .provider
+            .call(&to_typed_transaction(&tx, None)?, None)
+            .await
+            .map_err(rpc_error)?;
+
+        let decoded = abi
+            .decode_output(&name, args.len(), &return_data)
+            .unwrap_or(serde_json::Value::Null);
+
+        Ok(ContractCallResult {
+            return_data: return_data.to_vec(),
+            decoded,
+        })
+    }
+
+    fn encode_call(
+        &self,
+        abi: &[u8],
+        function: &str,
+        args: Vec<ContractArg>,
+    ) -> Result<Vec<u8>, CocoError> {
+        ContractAbi::parse(abi)?.encode_call(function, &args)
+    }
+
+    fn decode_events(
+        &self,
+        abi: &[u8],
+        logs: &[Log],
+    ) -> Result<Vec<DecodedEvent>, CocoError> {
+        ContractAbi::parse(abi)?.decode_logs(logs)
+    }
+}
+
+#[async_trait]
+impl<P: JsonRpcClient + Clone + 'static> DiscoveryAdapter for EvmAdapter<P> {
+    async fn discover_contracts(
+        &self,
+        project_path: &Path,
+    ) -> Result<Vec<DiscoveredContract>, CocoError> {
+        Ok(artifacts::discover_evm(project_path))
+    }
+
+    async fn fetch_contract_interface(
+        &self,
+        _address: &str,
+    ) -> Result<Option<Vec<u8>>, CocoError> {
+        // Verified interfaces live on block explorers, not on the node
+        Ok(None)
+    }
+}
+
+fn rpc_error(e: impl std::fmt::Display) -> CocoError {
+    CocoError::Adapter(format!("RPC request failed: {}", e))
+}
+
+/// Narrows a quantity the node returned, which a buggy or hostile node could
+/// make larger than any real one
+fn node_u64(value: U256, what: &str) -> Result<u64, CocoError> {
+    u64::try_from(value).map_err(|_| {
+        CocoError::Adapter(format!("Node returned an out-of-range {}: {}", what, value))
+    })
+}
+
+/// Describes an error the node returned for a call, or `None` for transport failures
+fn revert_from_error(e: &impl RpcError) -> Option<RevertInfo> {
+    let response = e.as_error_response()?;
+    Some(match response.as_revert_data() {
+        Some(data) => abi::revert_info(&data, &[]),
+        None => RevertInfo {
+            message: response.message.clone(),
+            ..Default::default()
+        },
+    })
+}
+
+fn parse_address(address: &str) -> Result<Address, CocoError> {
+    Address::from_str(address)
+        .map_err(|e| CocoError::Validation(format!("Invalid EVM address {}: {}", address, e)))
+}
+
+fn parse_hash(hash: &str) -> Result<H256, CocoError> {
+    H256::from_str(hash)
+        .map_err(|e| CocoError::Validation(format!("Invalid hash {}: {}", hash, e)))
+}
+
+/// Parses a decimal or 0x-prefixed hex amount in wei
+fn parse_amount(value: &str) -> Result<U256, CocoError> {
+    let parsed = if let Some(hex_value) = value.strip_prefix("0x") {
+        U256::from_str_radix(hex_value, 16).ok()
+    } else {
+        U256::from_dec_str(value).ok()
+    };
+    parsed.ok_or_else(|| CocoError::Validation(format!("Invalid amount: {}", value)))
+}
+
+pub(crate) fn parse_private_key(private_key: &str) -> Result<LocalWallet, CocoError> {
+    LocalWallet::from_str(private_key.trim().trim_start_matches("0x"))
+        .map_err(|e| CocoError::Validation(format!("Invalid EVM private key: {}", e)))
+}
+
+fn local_wallet(wallet: &WalletData) -> Result<LocalWallet, CocoError> {
+    let private_key = wallet.private_key.as_deref().ok_or_else(|| {
+        CocoError::Validation(format!("Wallet {} has no private key to sign with", wallet.address))
+    })?;
+    parse_private_key(private_key)
+}
+
+pub(crate) fn wallet_data(local: &LocalWallet) -> WalletData {
+    let verifying_key = local.signer().verifying_key();
+    WalletData {
+        address: to_checksum(&local.address(), None),
+        public_key: hex::encode(verifying_key.to_encoded_point(false).as_bytes()),
+        private_key_encrypted: None,
+        private_key: Some(format!("0x{}", hex::encode(local.signer().to_bytes()))),
+    }
+}
+
+fn to_typed_transaction(
+    tx: &TransactionRequest,
+    from: Option<Address>,
+) -> Result<TypedTransaction, CocoError> {
+    let mut request = ethers::types::TransactionRequest::new();
+    if let Some(from) = from {
+        request = request.from(from);
+    }
+    if let Some(to) = &tx.to {
+        request = request.to(NameOrAddress::Address(parse_address(to)?));
+    }
+    if let Some(value) = &tx.value {
+        request = request.value(parse_amount(value)?);
+    }
+    if let Some(data) = &tx.data {
+        request = request.data(Bytes::from(data.clone()));
+    }
+    if let Some(gas_limit) = tx.gas_limit {
+        request = request.gas(gas_limit);
+    }
+    let access_list = to_access_list(&tx.access_list)?;
+
+    match &tx.fee {
+        Some(FeePricing::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }) => {
+            let mut eip1559 = Eip1559TransactionRequest::new()
+                .max_fee_per_gas(parse_amount(max_fee_per_gas)?)
+                .max_priority_fee_per_gas(parse_amount(max_priority_fee_per_gas)?)
+                .access_list(access_list);
+            eip1559.from = request.from;
+            eip1559.to = request.to;
+            eip1559.gas = request.gas;
+            eip1559.value = request.value;
+            eip1559.data = request.data;
+            Ok(eip1559.into())
+        }
+        Some(FeePricing::ComputeUnit { .. }) => Err(CocoError::Validation(
+            "EVM transactions are priced per gas, not per compute unit".into(),
+        )),
+        fee => {
+            if let Some(FeePricing::Legacy { gas_price }) = fee {
+                request = request.gas_price(parse_amount(gas_price)?);
+            }
+            if access_list.0.is_empty() {
+                Ok(request.into())
+            } else {
+                Ok(Eip2930TransactionRequest::new(request, access_list).into())
+            }
+        }
+    }
+}
+
+fn to_access_list(items: &[AccessListItem]) -> Result<AccessList, CocoError> {
+    let items = items
+        .iter()
+        .map(|item| {
+            Ok(ethers::types::transaction::eip2930::AccessListItem {
+                address: parse_address(&item.address)?,
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|key| parse_hash(key))
+                    .collect::<Result<Vec<_>, CocoError>>()?,
+            })
+        })
+        .collect::<Result<Vec<_>, CocoError>>()?;
+    Ok(AccessList(items))
+}
+
+/// Slow, normal and fast EIP-1559 pricing: the tip is the average of each reward
+/// percentile over recent blocks, and the cap leaves room for the base fee to double
+fn eip1559_tiers(base_fee: U256, rewards: &[Vec<U256>]) -> [FeePricing; 3] {
+    std::array::from_fn(|tier| {
+        let samples: Vec<U256> = rewards.iter().filter_map(|block| block.get(tier).copied()).collect();
+        let tip = if samples.is_empty() {
+            U256::from(FALLBACK_PRIORITY_FEE)
+        } else {
+            samples.iter().fold(U256::zero(), |sum, r| sum + r) / samples.len()
+        };
+
+        FeePricing::Eip1559 {
+            max_fee_per_gas: (base_fee * U256::from(2) + tip).to_string(),
+            max_priority_fee_per_gas: tip.to_string(),
+        }
+    })
+}
+
+/// Slow, normal and fast pricing around the node's legacy gas price
+fn legacy_tiers(gas_price: U256) -> [FeePricing; 3] {
+    [gas_price * 9 / 10, gas_price, gas_price * 5 / 4].map(|price| FeePricing::Legacy {
+        gas_price: price.to_string(),
+    })
+}
+
+/// Prices a tier at its fee cap
+fn fee_tier(gas_limit: U256, pricing: FeePricing) -> Result<FeeTier, CocoError> {
+    let max_price = match &pricing {
+        FeePricing::Legacy { gas_price } => gas_price,
+        FeePricing::Eip1559 { max_fee_per_gas, .. } => max_fee_per_gas,
+        FeePricing::ComputeUnit { .. } => {
+            return Err(CocoError::Validation(
+                "Compute unit pricing does not apply to EVM transactions".into(),
+            ))
+        }
+    };
+    let total_fee = U256::from_dec_str(max_price)
+        .unwrap_or_default()
+        .checked_mul(gas_limit)
+        .ok_or_else(|| CocoError::Adapter(format!("Fee for {} gas overflows", gas_limit)))?;
+    Ok(FeeTier {
+        pricing,
+        total_fee: total_fee.to_string(),
+    })
+}
+
+/// Raises a replacement's fees to at least `REPLACEMENT_FEE_BUMP_PERCENT` above
+/// the transaction it replaces, keeping the current estimate if that is higher
+fn bump_fees(request: &mut TypedTransaction, original: &ethers::types::Transaction) {
+    let bump = |fee: U256| fee * (100 + REPLACEMENT_FEE_BUMP_PERCENT) / 100 + 1;
+
+    match request {
+        TypedTransaction::Eip1559(inner) => {
+            let original_max_fee = original.max_fee_per_gas.or(original.gas_price).unwrap_or_default();
+            let original_tip = original.max_priority_fee_per_gas.or(original.gas_price).unwrap_or_default();
+            inner.max_fee_per_gas = Some(inner.max_fee_per_gas.unwrap_or_default().max(bump(original_max_fee)));
+            inner.max_priority_fee_per_gas =
+                Some(inner.max_priority_fee_per_gas.unwrap_or_default().max(bump(original_tip)));
+        }
+        _ => {
+            let original_price = original.gas_price.or(original.max_fee_per_gas).unwrap_or_default();
+            let price = request.gas_price().unwrap_or_default().max(bump(original_price));
+            request.set_gas_price(price);
+        }
+    }
+}
+
+/// Attaches an `r || s || v` signature to a transaction, accepting either a raw
+/// recovery id or 27/28 for `v` and applying EIP-155 to legacy transactions
+fn signed_rlp(tx: &TypedTransaction, signature: &[u8]) -> Result<Bytes, CocoError> {
+    if signature.len() != 65 {
+        return Err(CocoError::Crypto(format!(
+            "Expected a 65-byte signature, got {} bytes",
+            signature.len()
+        )));
+    }
+
+    let recovery_id = match signature[64] {
+        v @ 0..=1 => v as u64,
+        v @ 27..=28 => (v - 27) as u64,
+        v => return Err(CocoError::Crypto(format!("Invalid signature recovery id: {}", v))),
+    };
+    let v = match (tx, tx.chain_id()) {
+        (TypedTransaction::Legacy(_), Some(chain_id)) => recovery_id + 35 + 2 * chain_id.as_u64(),
+        (TypedTransaction::Legacy(_), None) => recovery_id + 27,
+        _ => recovery_id,
+    };
+
+    Ok(tx.rlp_signed(&Signature {
+        r: U256::from_big_endian(&signature[..32]),
+        s: U256::from_big_endian(&signature[32..64]),
+        v,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::MockProvider;
+
+    const ERC20_ABI: &str = r#"[
+        {"type":"function","name":"transfer","stateMutability":"nonpayable",
+         "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],
+         "outputs":[{"name":"","type":"bool"}]},
+        {"type":"event","name":"Transfer","anonymous":false,
+         "inputs":[{"name":"from","type":"address","indexed":true},
+                   {"name":"to","type":"address","indexed":true},
+                   {"name":"value","type":"uint256","indexed":false}]}
+    ]"#;
+
+    fn adapter() -> (EvmAdapter<MockProvider>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let config = ChainConfig {
+            id: "anvil".to_string(),
+            name: "Anvil".to_string(),
+            ecosystem: Ecosystem::Evm,
+            rpc_url: "http://127.0.0.1:8545".to_string(),
+            native_currency: "ETH".to_string(),
+        };
+        (EvmAdapter::with_provider(config, provider), mock)
+    }
+
+    #[tokio::test]
+    async fn test_block_height_and_balance() {
+        let (adapter, mock) = adapter();
+
+        mock.push(U256::from(42u64)).unwrap();
+        assert_eq!(adapter.get_block_height().await.unwrap(), 42);
+
+        mock.push(U256::exp10(18)).unwrap();
+        let balance = adapter
+            .get_balance("0x742d35Cc6634C0532925a3b844Bc454e4438f44e")
+            .await
+            .unwrap();
+        assert_eq!(balance.native, "1000000000000000000");
+        assert_eq!(balance.native_decimals, 18);
+    }
+
+    #[tokio::test]
+    async fn test_missing_receipt_is_not_found() {
+        let (adapter, mock) = adapter();
+        mock.push(serde_json::Value::Null).unwrap();
+
+        let hash = format!("0x{}", "11".repeat(32));
+        let result = adapter.get_transaction_receipt(&hash).await;
+        assert!(matches!(result, Err(CocoError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_encode_call_and_decode_events() {
+        let (adapter, _mock) = adapter();
+        let to = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+        let data = adapter
+            .encode_call(
+                ERC20_ABI.as_bytes(),
+                "transfer",
+                vec![
+                    ContractArg { name: "to".into(), value: serde_json::json!(to) },
+                    ContractArg { name: "amount".into(), value: serde_json::json!("1000") },
+                ],
+            )
+            .unwrap();
+        assert_eq!(hex::encode(&data[..4]), "a9059cbb");
+        assert_eq!(data.len(), 4 + 64);
+
+        let abi: ethers::abi::Abi = serde_json::from_str(ERC20_ABI).unwrap();
+        let topic0 = abi.event("Transfer").unwrap().signature();
+        let from_topic = format!("0x{}{}", "00".repeat(12), "11".repeat(20));
+        let to_topic = format!("0x{}{}", "00".repeat(12), to.trim_start_matches("0x"));
+        let log = Log {
+            address: to.to_string(),
+            topics: vec![format!("{:?}", topic0), from_topic, to_topic],
+            data: ethers::abi::encode(&[ethers::abi::Token::Uint(U256::from(1000u64))]),
+        };
+
+        let events = adapter.decode_events(ERC20_ABI.as_bytes(), &[log]).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "Transfer");
+        assert_eq!(events[0].args["value"], "1000");
+        assert_eq!(events[0].args["to"], to);
+    }
+
+    #[tokio::test]
+    async fn test_external_signature_recovers_sender() {
+        let signer = SoftwareSigner::new(Ecosystem::Evm, &format!("0x{}", "42".repeat(32))).unwrap();
+        let from = parse_address(&signer.address()).unwrap();
+
+        let mut request = to_typed_transaction(
+            &TransactionRequest {
+                to: Some("0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string()),
+                value: Some("1000".to_string()),
+                ..Default::default()
+            },
+            Some(from),
+        )
+        .unwrap();
+        request.set_chain_id(31337u64);
+        request.set_nonce(7u64);
+        request.set_gas(21000u64);
+        request.set_gas_price(U256::exp10(9));
+
+        let signature = signer.sign_transaction(&request.rlp()).await.unwrap();
+        let raw = signed_rlp(&request, &signature).unwrap();
+
+        let (decoded, signature) = TypedTransaction::decode_signed(&ethers::utils::rlp::Rlp::new(&raw)).unwrap();
+        assert!([2 * 31337 + 35, 2 * 31337 + 36].contains(&signature.v));
+        assert_eq!(signature.recover(decoded.sighash()).unwrap(), from);
+    }
+
+    #[test]
+    fn test_eip1559_tiers_follow_reward_percentiles() {
+        let gwei = |n: u64| U256::from(n) * 1_000_000_000u64;
+        let rewards = vec![vec![gwei(1), gwei(2), gwei(4)], vec![gwei(1), gwei(4), gwei(6)]];
+
+        let [slow, normal, fast] = eip1559_tiers(gwei(10), &rewards);
+        assert_eq!(
+            normal,
+            FeePricing::Eip1559 {
+                max_fee_per_gas: gwei(23).to_string(),
+                max_priority_fee_per_gas: gwei(3).to_string(),
+            }
+        );
+        assert!(matches!(slow, FeePricing::Eip1559 { max_priority_fee_per_gas, .. } if max_priority_fee_per_gas == gwei(1).to_string()));
+        assert!(matches!(fast, FeePricing::Eip1559 { max_priority_fee_per_gas, .. } if max_priority_fee_per_gas == gwei(5).to_string()));
+
+        // Blocks without rewards fall back to a 1 gwei tip
+        let [_, empty, _] = eip1559_tiers(gwei(10), &[]);
+        assert!(matches!(empty, FeePricing::Eip1559 { max_priority_fee_per_gas, .. } if max_priority_fee_per_gas == gwei(1).to_string()));
+    }
+
+    #[test]
+    fn test_bad_fee_inputs_are_errors() {
+        let compute = FeePricing::ComputeUnit { micro_lamports_per_unit: 1 };
+        assert!(matches!(fee_tier(U256::from(21_000u64), compute), Err(CocoError::Validation(_))));
+
+        let huge = FeePricing::Legacy { gas_price: U256::MAX.to_string() };
+        assert!(matches!(fee_tier(U256::from(2u64), huge), Err(CocoError::Adapter(_))));
+        assert!(matches!(node_u64(U256::MAX, "gas limit"), Err(CocoError::Adapter(_))));
+        assert_eq!(node_u64(U256::from(21_000u64), "gas limit").unwrap(), 21_000);
+    }
+
+    #[test]
+    fn test_replacement_fees_exceed_original() {
+        let original = ethers::types::Transaction {
+            gas_price: Some(U256::from(100u64)),
+            ..Default::default()
+        };
+
+        // The node's current price is lower than the stuck transaction's
+        let mut request = to_typed_transaction(&TransactionRequest::default(), None).unwrap();
+        request.set_gas_price(U256::from(50u64));
+        bump_fees(&mut request, &original);
+        assert_eq!(request.gas_price(), Some(U256::from(116u64)));
+
+        // The node's current price already clears the bump
+        request.set_gas_price(U256::from(500u64));
+        bump_fees(&mut request, &original);
+        assert_eq!(request.gas_price(), Some(U256::from(500u64)));
+    }
+
+    #[tokio::test]
+    async fn test_import_wallet_derives_checksummed_address() {
+        let (adapter, _mock) = adapter();
+        // Well-known anvil account #0
+        let wallet = adapter
+            .import_wallet("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+            .await
+            .unwrap();
+        assert_eq!(wallet.address, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    }
+}
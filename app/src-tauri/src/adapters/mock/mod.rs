@@ -1,9 +1,12 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 use std::sync::RwLock;
 
+use crate::abi::{self, ContractAbi};
+use crate::adapters::solana;
 use crate::adapters::traits::*;
+use crate::artifacts;
 use crate::error::CocoError;
 use crate::types::*;
 
@@ -13,23 +16,47 @@ pub struct MockAdapter {
     wallets: RwLock<HashMap<String, MockWalletState>>,
     transactions: RwLock<HashMap<String, MockTransaction>>,
     block_height: RwLock<u64>,
+    /// Error a later send fails with before broadcast, after this many more succeed
+    fail_send: RwLock<Option<(usize, String)>>,
+    /// Whether sends are mined as soon as they are broadcast
+    auto_mine: RwLock<bool>,
+    /// Logs the next broadcast transaction's receipt carries
+    next_logs: RwLock<Vec<Log>>,
 }
 
 struct MockWalletState {
     balance: u128,
+    /// Next nonce the mock node expects, one past the highest used
     nonce: u64,
+    used_nonces: BTreeSet<u64>,
+    /// Nonces of accepted sends, in the order they arrived
+    sent_nonces: Vec<u64>,
+}
+
+impl MockWalletState {
+    fn new(balance: u128) -> Self {
+        Self {
+            balance,
+            nonce: 0,
+            used_nonces: BTreeSet::new(),
+            sent_nonces: vec![],
+        }
+    }
 }
 
 struct MockTransaction {
     hash: String,
+    nonce: u64,
     status: MockTxStatus,
     data: TransactionData,
+    logs: Vec<Log>,
 }
 
 enum MockTxStatus {
     Pending,
     Confirmed,
-    Failed(String),
+    /// Reverted, with the revert data
+    Failed(Vec<u8>),
 }
 
 impl MockAdapter {
@@ -39,6 +66,9 @@ impl MockAdapter {
             wallets: RwLock::new(HashMap::new()),
             transactions: RwLock::new(HashMap::new()),
             block_height: RwLock::new(1000),
+            fail_send: RwLock::new(None),
+            auto_mine: RwLock::new(false),
+            next_logs: RwLock::new(vec![]),
         }
     }
 
@@ -47,7 +77,7 @@ impl MockAdapter {
         let mut wallets = self.wallets.write().unwrap();
         wallets.insert(
             address.to_string(),
-            MockWalletState { balance, nonce: 0 },
+            MockWalletState::new(balance),
         );
     }
 
@@ -56,6 +86,162 @@ impl MockAdapter {
         let mut height = self.block_height.write().unwrap();
         *height += count;
     }
+
+    /// Marks nonces below `nonce` as used, as if another client had sent them
+    pub fn set_nonce(&self, address: &str, nonce: u64) {
+        let mut wallets = self.wallets.write().unwrap();
+        let wallet = wallets
+            .entry(address.to_string())
+            .or_insert_with(|| MockWalletState::new(0));
+        wallet.used_nonces.extend(0..nonce);
+        wallet.nonce = wallet.nonce.max(nonce);
+    }
+
+    /// Nonces of the sends accepted from an address, in arrival order
+    pub fn sent_nonces(&self, address: &str) -> Vec<u64> {
+        let wallets = self.wallets.read().unwrap();
+        wallets
+            .get(address)
+            .map(|w| w.sent_nonces.clone())
+            .unwrap_or_default()
+    }
+
+    /// Makes the next send fail before broadcast with the given error
+    pub fn fail_next_send(&self, message: &str) {
+        self.fail_send_after(0, message);
+    }
+
+    /// Lets `successes` more sends through, then fails the one after with the given error
+    pub fn fail_send_after(&self, successes: usize, message: &str) {
+        *self.fail_send.write().unwrap() = Some((successes, message.to_string()));
+    }
+
+    /// Mines every later send in the current block as soon as it is broadcast
+    pub fn set_auto_mine(&self, enabled: bool) {
+        *self.auto_mine.write().unwrap() = enabled;
+    }
+
+    /// Makes the next broadcast transaction emit `logs` once mined successfully
+    pub fn emit_on_next_send(&self, logs: Vec<Log>) {
+        *self.next_logs.write().unwrap() = logs;
+    }
+
+    /// Mines a pending transaction, successfully or as a revert
+    pub fn mine_transaction(&self, hash: &str, success: bool) {
+        let height = *self.block_height.read().unwrap();
+        let mut txs = self.transactions.write().unwrap();
+        if let Some(tx) = txs.get_mut(hash) {
+            tx.status = if success {
+                MockTxStatus::Confirmed
+            } else {
+                MockTxStatus::Failed(vec![])
+            };
+            tx.data.block_number = Some(height);
+        }
+    }
+
+    /// Mines a pending transaction as a revert with the given revert data
+    pub fn revert_transaction(&self, hash: &str, data: Vec<u8>) {
+        self.mine_transaction(hash, false);
+        if let Some(tx) = self.transactions.write().unwrap().get_mut(hash) {
+            tx.status = MockTxStatus::Failed(data);
+        }
+    }
+
+    /// Drops a mined transaction back into the mempool, as a reorg would
+    pub fn reorg_transaction(&self, hash: &str) {
+        let mut txs = self.transactions.write().unwrap();
+        if let Some(tx) = txs.get_mut(hash) {
+            tx.status = MockTxStatus::Pending;
+            tx.data.block_number = None;
+        }
+    }
+
+    fn record_send(&self, from: &str, tx: TransactionRequest) -> Result<TransactionResult, CocoError> {
+        {
+            let mut fail_send = self.fail_send.write().unwrap();
+            match fail_send.as_mut() {
+                Some((0, _)) => {
+                    let (_, message) = fail_send.take().unwrap();
+                    return Err(CocoError::Adapter(message));
+                }
+                Some((successes, _)) => *successes -= 1,
+                None => {}
+            }
+        }
+
+        let nonce = {
+            let mut wallets = self.wallets.write().unwrap();
+            let wallet = wallets
+                .entry(from.to_string())
+                .or_insert_with(|| MockWalletState::new(0));
+            let nonce = tx.nonce.unwrap_or(wallet.nonce);
+            if !wallet.used_nonces.insert(nonce) {
+                return Err(CocoError::Adapter(format!(
+                    "Failed to send transaction: nonce too low: {}",
+                    nonce
+                )));
+            }
+            wallet.sent_nonces.push(nonce);
+            wallet.nonce = wallet.nonce.max(nonce + 1);
+            nonce
+        };
+
+        Ok(self.insert_pending(from, nonce, &tx))
+    }
+
+    fn insert_pending(&self, from: &str, nonce: u64, tx: &TransactionRequest) -> TransactionResult {
+        let hash = format!("0x{:064x}", rand::random::<u128>());
+
+        self.transactions.write().unwrap().insert(
+            hash.clone(),
+            MockTransaction {
+                hash: hash.clone(),
+                nonce,
+                status: MockTxStatus::Pending,
+                data: TransactionData {
+                    hash: hash.clone(),
+                    from: from.to_string(),
+                    to: tx.to.clone(),
+                    value: tx.value.clone(),
+                    data: tx.data.clone(),
+                    block_number: None,
+                    timestamp: chrono::Utc::now(),
+                },
+                logs: std::mem::take(&mut *self.next_logs.write().unwrap()),
+            },
+        );
+        if *self.auto_mine.read().unwrap() {
+            self.mine_transaction(&hash, true);
+        }
+
+        TransactionResult {
+            hash,
+            status: TransactionStatus::Pending,
+            nonce: Some(nonce),
+        }
+    }
+}
+
+/// An EVM mock for the seeded Sepolia chain, so tests can also register it
+/// for services backed by `crate::db::test_db`
+#[cfg(test)]
+pub fn mock_adapter() -> MockAdapter {
+    MockAdapter::new(ChainConfig {
+        id: "ethereum-sepolia".to_string(),
+        name: "Mock".to_string(),
+        ecosystem: Ecosystem::Evm,
+        rpc_url: String::new(),
+        native_currency: "ETH".to_string(),
+    })
+}
+
+/// Address a mock creation transaction deploys to, derived from its hash
+fn created_address(tx: &MockTransaction) -> Option<String> {
+    tx.data
+        .to
+        .is_none()
+        .then(|| format!("0x{}", &tx.hash[tx.hash.len() - 40..]))
 }
 
 #[async_trait]
@@ -88,6 +274,7 @@ impl WalletAdapter for MockAdapter {
             address,
             public_key: "mock_public_key".to_string(),
             private_key_encrypted: None,
+            private_key: None,
         })
     }
 
@@ -101,6 +288,7 @@ impl WalletAdapter for MockAdapter {
             address,
             public_key: "mock_public_key".to_string(),
             private_key_encrypted: None,
+            private_key: None,
         })
     }
 
@@ -134,10 +322,7 @@ impl WalletAdapter for MockAdapter {
         } else {
             wallets.insert(
                 address.to_string(),
-                MockWalletState {
-                    balance: 1_000_000_000_000_000_000,
-                    nonce: 0,
-                },
+                MockWalletState::new(1_000_000_000_000_000_000),
             );
         }
         Ok("mock_faucet_tx_hash".to_string())
@@ -160,30 +345,57 @@ impl TransactionAdapter for MockAdapter {
         wallet: &WalletData,
         tx: TransactionRequest,
     ) -> Result<TransactionResult, CocoError> {
-        let hash = format!("0x{:064x}", rand::random::<u128>());
+        self.record_send(&wallet.address, tx)
+    }
 
-        let mut txs = self.transactions.write().unwrap();
-        txs.insert(
-            hash.clone(),
-            MockTransaction {
-                hash: hash.clone(),
-                status: MockTxStatus::Pending,
-                data: TransactionData {
-                    hash: hash.clone(),
-                    from: wallet.address.clone(),
-                    to: tx.to.clone(),
-                    value: tx.value.clone(),
-                    data: tx.data.clone(),
-                    block_number: None,
-                    timestamp: chrono::Utc::now(),
-                },
-            },
-        );
+    async fn send_transaction_with_signer(
+        &self,
+        signer: &dyn ExternalSigner,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        self.record_send(&signer.address(), tx)
+    }
 
-        Ok(TransactionResult {
-            hash,
-            status: TransactionStatus::Pending,
-        })
+    async fn replace_transaction(
+        &self,
+        signer: &dyn ExternalSigner,
+        original_hash: &str,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let from = signer.address();
+        let nonce = {
+            let mut txs = self.transactions.write().unwrap();
+            let original = txs
+                .get(original_hash)
+                .ok_or_else(|| CocoError::NotFound("Transaction not found".into()))?;
+            if !matches!(original.status, MockTxStatus::Pending) {
+                return Err(CocoError::Validation(format!(
+                    "Transaction {} is already confirmed",
+                    original_hash
+                )));
+            }
+            if original.data.from != from {
+                return Err(CocoError::Validation(format!(
+                    "Transaction {} was not sent by {}",
+                    original_hash, from
+                )));
+            }
+
+            // The replacement evicts the original from the mock mempool
+            let nonce = original.nonce;
+            txs.remove(original_hash);
+            nonce
+        };
+
+        if let Some(wallet) = self.wallets.write().unwrap().get_mut(&from) {
+            wallet.sent_nonces.push(nonce);
+        }
+        Ok(self.insert_pending(&from, nonce, &tx))
+    }
+
+    async fn get_pending_nonce(&self, address: &str) -> Result<Option<u64>, CocoError> {
+        let wallets = self.wallets.read().unwrap();
+        Ok(Some(wallets.get(address).map(|w| w.nonce).unwrap_or(0)))
     }
 
     async fn get_transaction(&self, hash: &str) -> Result<TransactionData, CocoError> {
@@ -199,6 +411,12 @@ impl TransactionAdapter for MockAdapter {
             .get(hash)
             .ok_or_else(|| CocoError::NotFound("Transaction not found".into()))?;
 
+        let block_number = tx
+            .data
+            .block_number
+            .unwrap_or(*self.block_height.read().unwrap());
+        let block_hash = Some(format!("0x{:064x}", block_number));
+
         match &tx.status {
             MockTxStatus::Pending => {
                 Err(CocoError::NotFound("Receipt not available yet".into()))
@@ -206,25 +424,43 @@ impl TransactionAdapter for MockAdapter {
             MockTxStatus::Confirmed => Ok(TransactionReceipt {
                 hash: hash.to_string(),
                 status: true,
-                block_number: *self.block_height.read().unwrap(),
+                block_number,
+                block_hash,
                 gas_used: 21000,
+                fee: Some("21000000000000".to_string()),
                 events: vec![],
+                logs: tx.logs.clone(),
+                revert: None,
+                contract_address: created_address(tx),
             }),
-            MockTxStatus::Failed(_) => Ok(TransactionReceipt {
+            MockTxStatus::Failed(data) => Ok(TransactionReceipt {
                 hash: hash.to_string(),
                 status: false,
-                block_number: *self.block_height.read().unwrap(),
+                block_number,
+                block_hash,
                 gas_used: 21000,
+                fee: Some("21000000000000".to_string()),
                 events: vec![],
+                logs: vec![],
+                revert: Some(abi::revert_info(data, &[])),
+                contract_address: None,
             }),
         }
     }
 
     async fn estimate_fees(&self, _tx: &TransactionRequest) -> Result<FeeEstimate, CocoError> {
+        // 1, 2 and 3 gwei
+        let tier = |gwei: u64| FeeTier {
+            pricing: FeePricing::Legacy {
+                gas_price: (gwei * 1_000_000_000).to_string(),
+            },
+            total_fee: (gwei * 21_000_000_000_000).to_string(),
+        };
         Ok(FeeEstimate {
             gas_limit: 21000,
-            gas_price: "1000000000".to_string(), // 1 gwei
-            total_fee: "21000000000000".to_string(),
+            slow: tier(1),
+            normal: tier(2),
+            fast: tier(3),
         })
     }
 
@@ -237,28 +473,40 @@ impl TransactionAdapter for MockAdapter {
             return_data: vec![],
             gas_used: 21000,
             logs: vec![],
+            revert: None,
         })
     }
 }
 
 #[async_trait]
 impl ContractAdapter for MockAdapter {
-    async fn deploy_contract(
+    fn deployment_request(
         &self,
-        _wallet: &WalletData,
-        _bytecode: &[u8],
-        _constructor_args: Option<Vec<u8>>,
-    ) -> Result<DeploymentResult, CocoError> {
-        let address = format!("0x{:040x}", rand::random::<u64>());
-        let tx_hash = format!("0x{:064x}", rand::random::<u128>());
-
-        Ok(DeploymentResult {
-            address,
-            transaction_hash: tx_hash,
-            block_number: *self.block_height.read().unwrap(),
+        bytecode: &[u8],
+        constructor_args: &[u8],
+    ) -> Result<TransactionRequest, CocoError> {
+        Ok(TransactionRequest {
+            data: Some([bytecode, constructor_args].concat()),
+            ..Default::default()
         })
     }
 
+    async fn deploy_program(
+        &self,
+        signer: &dyn ExternalSigner,
+        program: &[u8],
+        program_keypair: &str,
+    ) -> Result<(String, TransactionResult), CocoError> {
+        // The program lands at its keypair's address, like on Solana
+        let program_id = solana::wallet_data(&solana::parse_secret_key(program_keypair)?).address;
+        let tx = TransactionRequest {
+            to: Some(program_id.clone()),
+            data: Some(program.to_vec()),
+            ..Default::default()
+        };
+        Ok((program_id, self.record_send(&signer.address(), tx)?))
+    }
+
     async fn call_contract(
         &self,
         _address: &str,
@@ -273,19 +521,19 @@ impl ContractAdapter for MockAdapter {
 
     fn encode_call(
         &self,
-        _abi: &[u8],
-        _function: &str,
-        _args: Vec<ContractArg>,
+        abi: &[u8],
+        function: &str,
+        args: Vec<ContractArg>,
     ) -> Result<Vec<u8>, CocoError> {
-        Ok(vec![0u8; 32])
+        ContractAbi::parse(abi)?.encode_call(function, &args)
     }
 
     fn decode_events(
         &self,
-        _abi: &[u8],
-        _logs: &[Log],
+        abi: &[u8],
+        logs: &[Log],
     ) -> Result<Vec<DecodedEvent>, CocoError> {
-        Ok(vec![])
+        ContractAbi::parse(abi)?.decode_logs(logs)
     }
 }
 
@@ -293,16 +541,10 @@ impl ContractAdapter for MockAdapter {
 impl DiscoveryAdapter for MockAdapter {
     async fn discover_contracts(
         &self,
-        _project_path: &Path,
+        project_path: &Path,
     ) -> Result<Vec<DiscoveredContract>, CocoError> {
-        // Return mock contracts for testing
-        Ok(vec![DiscoveredContract {
-            name: "MockToken".to_string(),
-            path: "out/MockToken.sol/MockToken.json".to_string(),
-            interface_type: InterfaceType::Abi,
-            is_interface: false,
-            is_dependency: false,
-        }])
+        // Stands in for an EVM chain, so it reads Foundry and Hardhat output
+        Ok(artifacts::discover_evm(project_path))
     }
 
     async fn fetch_contract_interface(
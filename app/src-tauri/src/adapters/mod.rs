@@ -5,30 +5,45 @@ use tokio::sync::RwLock;
 use crate::error::CocoError;
 use crate::types::{ChainConfig, Ecosystem};
 
+pub mod aptos;
+pub mod derivation;
+pub mod evm;
 pub mod mock;
+pub mod nonce;
 pub mod traits;
 pub mod postgres;
+pub mod signer;
+pub mod solana;
 
+pub use nonce::NonceManager;
+pub use signer::SoftwareSigner;
 pub use traits::*;
 
 /// Registry that manages all chain adapters
 pub struct AdapterRegistry {
     adapters: RwLock<HashMap<String, Arc<dyn FullAdapter>>>,
+    nonces: Arc<NonceManager>,
 }
 
 impl AdapterRegistry {
     pub fn new() -> Self {
         Self {
             adapters: RwLock::new(HashMap::new()),
+            nonces: Arc::new(NonceManager::new()),
         }
     }
 
+    /// Nonce manager shared by every send through the registered adapters
+    pub fn nonce_manager(&self) -> Arc<NonceManager> {
+        self.nonces.clone()
+    }
+
     /// Registers a new chain adapter
     pub async fn register(&self, config: ChainConfig) -> Result<(), CocoError> {
         let adapter: Arc<dyn FullAdapter> = match config.ecosystem {
-            Ecosystem::Evm => Arc::new(mock::MockAdapter::new(config.clone())),
-            Ecosystem::Solana => Arc::new(mock::MockAdapter::new(config.clone())),
-            Ecosystem::Aptos => Arc::new(mock::MockAdapter::new(config.clone())),
+            Ecosystem::Evm => Arc::new(evm::EvmAdapter::new(config.clone())?),
+            Ecosystem::Solana => Arc::new(solana::SolanaAdapter::new(config.clone())?),
+            Ecosystem::Aptos => Arc::new(aptos::AptosAdapter::new(config.clone())?),
         };
 
         let mut adapters = self.adapters.write().await;
@@ -36,12 +51,34 @@ impl AdapterRegistry {
         Ok(())
     }
 
+    /// Registers an adapter that was built elsewhere, such as a mock
+    pub async fn insert(&self, chain_id: &str, adapter: Arc<dyn FullAdapter>) {
+        let mut adapters = self.adapters.write().await;
+        adapters.insert(chain_id.to_string(), adapter);
+    }
+
     /// Gets an adapter by chain ID
     pub async fn get(&self, chain_id: &str) -> Option<Arc<dyn FullAdapter>> {
         let adapters = self.adapters.read().await;
         adapters.get(chain_id).cloned()
     }
 
+    /// Gets the adapter for a chain, registering one from `config` on first use
+    pub async fn get_or_register(
+        &self,
+        config: ChainConfig,
+    ) -> Result<Arc<dyn FullAdapter>, CocoError> {
+        if let Some(adapter) = self.get(&config.id).await {
+            return Ok(adapter);
+        }
+
+        let chain_id = config.id.clone();
+        self.register(config).await?;
+        self.get(&chain_id)
+            .await
+            .ok_or_else(|| CocoError::Adapter(format!("Failed to register adapter for {}", chain_id)))
+    }
+
     /// Gets an adapter by ecosystem type
     pub fn get_adapter(&self, _ecosystem: &Ecosystem) -> Option<Arc<dyn FullAdapter>> {
         // For now return None - adapters are registered per chain, not per ecosystem
@@ -50,6 +87,8 @@ impl AdapterRegistry {
 
     /// Removes an adapter
     pub async fn remove(&self, chain_id: &str) -> Option<Arc<dyn FullAdapter>> {
+        // A changed endpoint may be a different node, so cached nonces no longer apply
+        self.nonces.forget_chain(chain_id).await;
         let mut adapters = self.adapters.write().await;
         adapters.remove(chain_id)
     }
@@ -76,11 +115,9 @@ pub fn create_adapter(
         return Ok(Arc::new(mock::MockAdapter::new(config)));
     }
 
-    // For now, always use mock adapter
-    // Real implementations would be added here
     match config.ecosystem {
-        Ecosystem::Evm => Ok(Arc::new(mock::MockAdapter::new(config))),
-        Ecosystem::Solana => Ok(Arc::new(mock::MockAdapter::new(config))),
-        Ecosystem::Aptos => Ok(Arc::new(mock::MockAdapter::new(config))),
+        Ecosystem::Evm => Ok(Arc::new(evm::EvmAdapter::new(config)?)),
+        Ecosystem::Solana => Ok(Arc::new(solana::SolanaAdapter::new(config)?)),
+        Ecosystem::Aptos => Ok(Arc::new(aptos::AptosAdapter::new(config)?)),
     }
 }
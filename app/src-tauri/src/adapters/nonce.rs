@@ -0,0 +1,221 @@
+use std::collections::{BTreeSet, HashMap};
+use tokio::sync::Mutex;
+
+use crate::adapters::traits::{ExternalSigner, FullAdapter};
+use crate::error::CocoError;
+use crate::types::{TransactionRequest, TransactionResult};
+
+/// Hands out sequential nonces per (chain, address) so concurrent sends from
+/// the same wallet don't collide. Chains without account nonces (Solana) pass
+/// straight through.
+pub struct NonceManager {
+    accounts: Mutex<HashMap<(String, String), AccountNonces>>,
+}
+
+/// Nonce bookkeeping for one account
+struct AccountNonces {
+    /// Next nonce never handed out
+    next: u64,
+    /// Nonces handed back by sends that failed before broadcast, reused first
+    released: BTreeSet<u64>,
+}
+
+impl AccountNonces {
+    /// Reserves a released nonce if there is one, otherwise the next fresh one
+    fn take(&mut self) -> u64 {
+        if let Some(nonce) = self.released.pop_first() {
+            return nonce;
+        }
+        self.next += 1;
+        self.next - 1
+    }
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self {
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reserves the next nonce for an account, asking the node on first use.
+    /// Returns `None` when the chain has no account nonces.
+    pub async fn next(&self, adapter: &dyn FullAdapter, address: &str) -> Result<Option<u64>, CocoError> {
+        let key = (adapter.chain_id().to_string(), address.to_string());
+        if let Some(account) = self.accounts.lock().await.get_mut(&key) {
+            return Ok(Some(account.take()));
+        }
+
+        // Ask the node without holding the lock so a slow RPC call doesn't stall
+        // every other account. Concurrent first uses all fetch, the first insert wins.
+        let Some(pending) = adapter.get_pending_nonce(address).await? else {
+            return Ok(None);
+        };
+        let mut accounts = self.accounts.lock().await;
+        let account = accounts.entry(key).or_insert_with(|| AccountNonces {
+            next: pending,
+            released: BTreeSet::new(),
+        });
+        Ok(Some(account.take()))
+    }
+
+    /// Hands back a nonce whose transaction was never broadcast so it isn't left as a gap
+    pub async fn release(&self, chain_id: &str, address: &str, nonce: u64) {
+        let mut accounts = self.accounts.lock().await;
+        let Some(account) = accounts.get_mut(&(chain_id.to_string(), address.to_string())) else {
+            return;
+        };
+        if nonce >= account.next {
+            return;
+        }
+
+        account.released.insert(nonce);
+        // Released nonces at the top of the range just shrink it
+        while account.next > 0 && account.released.remove(&(account.next - 1)) {
+            account.next -= 1;
+        }
+    }
+
+    /// Forgets an account's nonces so the next send asks the node again
+    pub async fn resync(&self, chain_id: &str, address: &str) {
+        self.accounts
+            .lock()
+            .await
+            .remove(&(chain_id.to_string(), address.to_string()));
+    }
+
+    /// Forgets every account on a chain, e.g. after its RPC endpoint changes
+    pub async fn forget_chain(&self, chain_id: &str) {
+        self.accounts.lock().await.retain(|(chain, _), _| chain != chain_id);
+    }
+
+    /// Sends a transaction with the next nonce for the signer. Resyncs and retries
+    /// once if the node reports the nonce as already used, and releases the
+    /// nonce if the send fails before broadcast.
+    pub async fn send(
+        &self,
+        adapter: &dyn FullAdapter,
+        signer: &dyn ExternalSigner,
+        mut tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        // A caller-chosen nonce (e.g. replacing a stuck transaction) bypasses the manager
+        if tx.nonce.is_some() {
+            return adapter.send_transaction_with_signer(signer, tx).await;
+        }
+
+        let chain_id = adapter.chain_id().to_string();
+        let address = signer.address();
+        let mut resynced = false;
+
+        loop {
+            tx.nonce = self.next(adapter, &address).await?;
+            let Some(nonce) = tx.nonce else {
+                return adapter.send_transaction_with_signer(signer, tx).await;
+            };
+
+            match adapter.send_transaction_with_signer(signer, tx.clone()).await {
+                Ok(result) => return Ok(result),
+                Err(e) if !resynced && is_nonce_too_low(&e) => {
+                    self.resync(&chain_id, &address).await;
+                    resynced = true;
+                }
+                Err(e) => {
+                    self.release(&chain_id, &address, nonce).await;
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a node rejected a transaction because its nonce was already used
+fn is_nonce_too_low(error: &CocoError) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("nonce too low") || message.contains("sequence_number_too_old")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mock::{mock_adapter, MockAdapter};
+    use crate::adapters::{ChainAdapter, SoftwareSigner};
+    use crate::types::Ecosystem;
+    use std::sync::Arc;
+
+    fn setup() -> (Arc<MockAdapter>, SoftwareSigner, NonceManager) {
+        let adapter = mock_adapter();
+        let signer = SoftwareSigner::new(Ecosystem::Evm, &format!("0x{}", "11".repeat(32))).unwrap();
+        adapter.seed_wallet(&signer.address(), 10u128.pow(18));
+        (Arc::new(adapter), signer, NonceManager::new())
+    }
+
+    fn transfer() -> TransactionRequest {
+        TransactionRequest {
+            to: Some("0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string()),
+            value: Some("1".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_sends_get_sequential_nonces() {
+        let (adapter, signer, nonces) = setup();
+
+        let (a, b, c) = tokio::join!(
+            nonces.send(adapter.as_ref(), &signer, transfer()),
+            nonces.send(adapter.as_ref(), &signer, transfer()),
+            nonces.send(adapter.as_ref(), &signer, transfer()),
+        );
+        for result in [a, b, c] {
+            result.unwrap();
+        }
+
+        let mut sent = adapter.sent_nonces(&signer.address());
+        sent.sort();
+        assert_eq!(sent, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_resyncs_when_nonce_too_low() {
+        let (adapter, signer, nonces) = setup();
+        nonces.send(adapter.as_ref(), &signer, transfer()).await.unwrap();
+
+        // Another client sent from the same account behind the manager's back
+        adapter.set_nonce(&signer.address(), 5);
+        nonces.send(adapter.as_ref(), &signer, transfer()).await.unwrap();
+
+        assert_eq!(adapter.sent_nonces(&signer.address()), vec![0, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_releases_nonce() {
+        let (adapter, signer, nonces) = setup();
+        nonces.send(adapter.as_ref(), &signer, transfer()).await.unwrap();
+
+        adapter.fail_next_send("insufficient funds for gas");
+        assert!(nonces.send(adapter.as_ref(), &signer, transfer()).await.is_err());
+        nonces.send(adapter.as_ref(), &signer, transfer()).await.unwrap();
+
+        assert_eq!(adapter.sent_nonces(&signer.address()), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_released_gap_is_reused_first() {
+        let (adapter, signer, nonces) = setup();
+        let address = signer.address();
+
+        assert_eq!(nonces.next(adapter.as_ref(), &address).await.unwrap(), Some(0));
+        assert_eq!(nonces.next(adapter.as_ref(), &address).await.unwrap(), Some(1));
+        assert_eq!(nonces.next(adapter.as_ref(), &address).await.unwrap(), Some(2));
+
+        nonces.release(adapter.chain_id(), &address, 1).await;
+        assert_eq!(nonces.next(adapter.as_ref(), &address).await.unwrap(), Some(1));
+        assert_eq!(nonces.next(adapter.as_ref(), &address).await.unwrap(), Some(3));
+    }
+}
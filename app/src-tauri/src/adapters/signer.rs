@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use ed25519_dalek::{Signer as _, SigningKey};
+use ethers::signers::{LocalWallet, Signer as _};
+use ethers::utils::keccak256;
+
+use crate::adapters::traits::ExternalSigner;
+use crate::adapters::{aptos, evm, solana};
+use crate::error::CocoError;
+use crate::types::{Ecosystem, WalletData};
+
+/// Signs with a private key held in memory. Local wallets sign through it, and
+/// it stands in for a hardware signer in tests.
+pub struct SoftwareSigner {
+    key: SoftwareKey,
+    wallet: WalletData,
+}
+
+enum SoftwareKey {
+    Evm(LocalWallet),
+    Ed25519(SigningKey),
+}
+
+impl SoftwareSigner {
+    /// Parses a private key in any format the ecosystem's adapter accepts
+    pub fn new(ecosystem: Ecosystem, private_key: &str) -> Result<Self, CocoError> {
+        let (key, wallet) = match ecosystem {
+            Ecosystem::Evm => {
+                let local = evm::parse_private_key(private_key)?;
+                let wallet = evm::wallet_data(&local);
+                (SoftwareKey::Evm(local), wallet)
+            }
+            Ecosystem::Solana => {
+                let key = solana::parse_secret_key(private_key)?;
+                let wallet = solana::wallet_data(&key);
+                (SoftwareKey::Ed25519(key), wallet)
+            }
+            Ecosystem::Aptos => {
+                let key = aptos::parse_private_key(private_key)?;
+                let wallet = aptos::wallet_data(&key);
+                (SoftwareKey::Ed25519(key), wallet)
+            }
+        };
+        Ok(Self { key, wallet })
+    }
+
+    /// Builds a signer from a wallet's decrypted keys
+    pub fn from_wallet(ecosystem: Ecosystem, wallet: &WalletData) -> Result<Self, CocoError> {
+        let private_key = wallet.private_key.as_deref().ok_or_else(|| {
+            CocoError::Validation(format!("Wallet {} has no private key to sign with", wallet.address))
+        })?;
+        Self::new(ecosystem, private_key)
+    }
+}
+
+#[async_trait]
+impl ExternalSigner for SoftwareSigner {
+    fn address(&self) -> String {
+        self.wallet.address.clone()
+    }
+
+    fn public_key(&self) -> String {
+        self.wallet.public_key.clone()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, CocoError> {
+        match &self.key {
+            SoftwareKey::Evm(local) => {
+                let signature = local
+                    .sign_message(message)
+                    .await
+                    .map_err(|e| CocoError::Crypto(format!("Failed to sign message: {}", e)))?;
+                Ok(signature.to_vec())
+            }
+            SoftwareKey::Ed25519(key) => Ok(key.sign(message).to_bytes().to_vec()),
+        }
+    }
+
+    async fn sign_transaction(&self, payload: &[u8]) -> Result<Vec<u8>, CocoError> {
+        match &self.key {
+            SoftwareKey::Evm(local) => {
+                let signature = local
+                    .sign_hash(keccak256(payload).into())
+                    .map_err(|e| CocoError::Crypto(format!("Failed to sign transaction: {}", e)))?;
+                Ok(signature.to_vec())
+            }
+            SoftwareKey::Ed25519(key) => Ok(key.sign(payload).to_bytes().to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature as Ed25519Signature, Verifier};
+    use ethers::types::Signature;
+
+    const EVM_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[tokio::test]
+    async fn test_evm_signatures_recover_to_address() {
+        let signer = SoftwareSigner::new(Ecosystem::Evm, EVM_KEY).unwrap();
+        let address = signer.address().parse().unwrap();
+
+        let signature = Signature::try_from(signer.sign_message(b"hello").await.unwrap().as_slice()).unwrap();
+        assert!(signature.verify("hello", address).is_ok());
+
+        let payload = b"unsigned transaction";
+        let signature = Signature::try_from(signer.sign_transaction(payload).await.unwrap().as_slice()).unwrap();
+        assert_eq!(signature.recover(keccak256(payload)).unwrap(), address);
+    }
+
+    #[tokio::test]
+    async fn test_solana_signature_verifies_against_address() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let private_key = bs58::encode(key.to_keypair_bytes()).into_string();
+        let signer = SoftwareSigner::new(Ecosystem::Solana, &private_key).unwrap();
+        assert_eq!(signer.address(), solana::wallet_data(&key).address);
+
+        let bytes: [u8; 64] = signer.sign_transaction(b"message").await.unwrap().try_into().unwrap();
+        assert!(key
+            .verifying_key()
+            .verify(b"message", &Ed25519Signature::from_bytes(&bytes))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_wallet_without_key_cannot_sign() {
+        let wallet = WalletData {
+            address: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
+            public_key: String::new(),
+            private_key_encrypted: None,
+            private_key: None,
+        };
+        assert!(matches!(
+            SoftwareSigner::from_wallet(Ecosystem::Evm, &wallet),
+            Err(CocoError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+//! Anchor workspace discovery: IDLs from `target/idl`, with program IDs taken
+//! from `Anchor.toml`, the deploy keypairs in `target/deploy` or the IDL itself.
+
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::idl::{parse_idl, program_address};
+use super::transaction::{encode_pubkey, Pubkey};
+use crate::types::{DiscoveredContract, InterfaceType};
+
+/// The Anchor.toml cluster an RPC endpoint belongs to, if it names one
+pub fn cluster_for(rpc_url: &str) -> Option<&'static str> {
+    let url = rpc_url.to_lowercase();
+    if url.contains("devnet") {
+        Some("devnet")
+    } else if url.contains("testnet") {
+        Some("testnet")
+    } else if url.contains("mainnet") {
+        Some("mainnet")
+    } else if ["localhost", "127.0.0.1", "0.0.0.0"].iter().any(|host| url.contains(host)) {
+        Some("localnet")
+    } else {
+        None
+    }
+}
+
+/// Program IDs declared in Anchor.toml for a cluster, falling back to the
+/// `[provider]` cluster when the endpoint doesn't name one
+fn declared_programs(project: &Path, cluster: Option<&str>) -> Option<toml::Table> {
+    let text = std::fs::read_to_string(project.join("Anchor.toml")).ok()?;
+    let config: toml::Table = toml::from_str(&text).ok()?;
+
+    let cluster = match cluster {
+        Some(cluster) => cluster.to_string(),
+        None => config.get("provider")?.get("cluster")?.as_str()?.to_lowercase(),
+    };
+    config.get("programs")?.get(&cluster)?.as_table().cloned()
+}
+
+/// Public key of a `solana-keygen` keypair file: the last 32 of its 64 bytes
+fn keypair_address(path: &Path) -> Option<String> {
+    let bytes: Vec<u8> = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    if bytes.len() != 64 {
+        return None;
+    }
+    let key: Pubkey = bytes[32..].try_into().ok()?;
+    Some(encode_pubkey(&key))
+}
+
+/// Finds the programs of an Anchor workspace that have been built
+pub fn discover(project: &Path, cluster: Option<&str>) -> Vec<DiscoveredContract> {
+    let idl_dir = project.join("target").join("idl");
+    if !idl_dir.is_dir() {
+        return vec![];
+    }
+    let programs = declared_programs(project, cluster).unwrap_or_default();
+
+    WalkDir::new(&idl_dir)
+        .max_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| {
+            let text = std::fs::read_to_string(entry.path()).ok()?;
+            let idl = parse_idl(text.as_bytes()).ok()?;
+            let stem = entry.path().file_stem()?.to_str()?;
+            // 0.30+ IDLs move the program name under metadata
+            let name = idl
+                .pointer("/metadata/name")
+                .or_else(|| idl.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or(stem)
+                .to_string();
+
+            let deployed_address = programs
+                .get(&name)
+                .and_then(|id| id.as_str())
+                .map(str::to_string)
+                .or_else(|| {
+                    let keypair = format!("{}-keypair.json", name);
+                    keypair_address(&project.join("target").join("deploy").join(keypair))
+                })
+                .or_else(|| program_address(&idl).map(str::to_string));
+
+            Some(DiscoveredContract {
+                name,
+                path: entry
+                    .path()
+                    .strip_prefix(project)
+                    .unwrap_or(entry.path())
+                    .to_string_lossy()
+                    .to_string(),
+                interface_type: InterfaceType::Idl,
+                is_interface: false,
+                is_dependency: false,
+                abi: None,
+                bytecode: None,
+                compiler_version: None,
+                idl: Some(text),
+                move_definition: None,
+                deployed_address,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_cluster_for_rpc_url() {
+        assert_eq!(cluster_for("https://api.devnet.solana.com"), Some("devnet"));
+        assert_eq!(cluster_for("https://api.mainnet-beta.solana.com"), Some("mainnet"));
+        assert_eq!(cluster_for("http://127.0.0.1:8899"), Some("localnet"));
+        assert_eq!(cluster_for("https://rpc.example.com"), None);
+    }
+
+    #[test]
+    fn test_discovers_anchor_programs() {
+        let dir = std::env::temp_dir().join(format!("coco-anchor-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("target/idl")).unwrap();
+        std::fs::create_dir_all(dir.join("target/deploy")).unwrap();
+        std::fs::write(
+            dir.join("Anchor.toml"),
+            "[provider]\ncluster = \"Localnet\"\n\n[programs.devnet]\ncounter = \"Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("target/idl/counter.json"),
+            r#"{"version": "0.1.0", "name": "counter", "instructions": []}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("target/idl/escrow.json"),
+            r#"{"address": "11111111111111111111111111111111", "metadata": {"name": "escrow"}, "instructions": []}"#,
+        )
+        .unwrap();
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let keypair: Vec<u8> = key.to_keypair_bytes().to_vec();
+        std::fs::write(
+            dir.join("target/deploy/counter-keypair.json"),
+            serde_json::to_string(&keypair).unwrap(),
+        )
+        .unwrap();
+
+        let devnet = discover(&dir, Some("devnet"));
+        let localnet = discover(&dir, None);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(devnet.len(), 2);
+        assert_eq!(devnet[0].name, "counter");
+        assert_eq!(
+            devnet[0].deployed_address.as_deref(),
+            Some("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS")
+        );
+        assert!(devnet[0].idl.as_deref().unwrap().contains("\"instructions\""));
+
+        // No localnet entry, so the deploy keypair decides
+        let expected = encode_pubkey(&key.verifying_key().to_bytes());
+        assert_eq!(localnet[0].deployed_address, Some(expected));
+        // The IDL's own address is the last resort
+        assert_eq!(localnet[1].name, "escrow");
+        assert_eq!(
+            localnet[1].deployed_address.as_deref(),
+            Some("11111111111111111111111111111111")
+        );
+    }
+}
@@ -0,0 +1,698 @@
+//! Anchor IDL support for both the legacy (<= 0.29) and the 0.30+ spec formats.
+//! Values are borsh-encoded straight from JSON, so no generated types are needed.
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+use super::transaction::{
+    decode_pubkey, encode_pubkey, find_program_address, ASSOCIATED_TOKEN_PROGRAM_ID,
+    SYSTEM_PROGRAM_ID, SYSVAR_CLOCK_ID, SYSVAR_RENT_ID, TOKEN_PROGRAM_ID,
+};
+use crate::error::CocoError;
+use crate::types::{ContractArg, DecodedEvent, InstructionAccount};
+
+/// Payload key prefix for explicitly supplied instruction accounts
+pub const ACCOUNT_ARG_PREFIX: &str = "account:";
+/// Payload key prefix for explicitly supplied PDA seeds
+pub const PDA_SEED_ARG_PREFIX: &str = "pda_seed:";
+
+pub fn parse_idl(idl: &[u8]) -> Result<Value, CocoError> {
+    serde_json::from_slice(idl)
+        .map_err(|e| CocoError::Serialization(format!("Invalid IDL JSON: {}", e)))
+}
+
+/// Program address declared by the IDL, if any
+pub fn program_address(idl: &Value) -> Option<&str> {
+    idl.get("address")
+        .or_else(|| idl.pointer("/metadata/address"))
+        .and_then(|a| a.as_str())
+}
+
+pub fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a == b || snake_case(a) == snake_case(b)
+}
+
+fn items<'a>(idl: &'a Value, key: &str) -> &'a [Value] {
+    idl.get(key)
+        .and_then(|v| v.as_array())
+        .map(|v| v.as_slice())
+        .unwrap_or(&[])
+}
+
+fn name_of(item: &Value) -> &str {
+    item.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+pub fn find_instruction<'a>(idl: &'a Value, name: &str) -> Result<&'a Value, CocoError> {
+    items(idl, "instructions")
+        .iter()
+        .find(|ix| same_name(name_of(ix), name))
+        .ok_or_else(|| CocoError::NotFound(format!("Instruction not found in IDL: {}", name)))
+}
+
+/// Explicit discriminator (0.30+) or the first 8 bytes of sha256("<namespace>:<name>")
+fn discriminator(item: &Value, preimage: &str) -> Vec<u8> {
+    if let Some(bytes) = item.get("discriminator").and_then(|d| d.as_array()) {
+        return bytes.iter().filter_map(|b| b.as_u64()).map(|b| b as u8).collect();
+    }
+    Sha256::digest(preimage.as_bytes())[..8].to_vec()
+}
+
+/// Encodes instruction data: discriminator followed by borsh-encoded arguments
+pub fn encode_instruction(
+    idl: &Value,
+    name: &str,
+    args: &[ContractArg],
+) -> Result<Vec<u8>, CocoError> {
+    let ix = find_instruction(idl, name)?;
+    let ix_name = name_of(ix);
+    let mut data = discriminator(ix, &format!("global:{}", snake_case(ix_name)));
+
+    for (i, field) in items(ix, "args").iter().enumerate() {
+        let field_name = name_of(field);
+        let value = find_arg(args, field_name, i).ok_or_else(|| {
+            CocoError::Validation(format!("Missing argument {} for {}", field_name, ix_name))
+        })?;
+
+        encode_value(idl, &field["type"], value, &mut data).map_err(|e| {
+            CocoError::Validation(format!("Invalid value for {}: {}", field_name, e))
+        })?;
+    }
+
+    Ok(data)
+}
+
+/// Matches an argument by name, then `argN`, then position among plain arguments
+fn find_arg<'a>(args: &'a [ContractArg], name: &str, index: usize) -> Option<&'a Value> {
+    let plain: Vec<&ContractArg> = args.iter().filter(|a| !a.name.contains(':')).collect();
+
+    plain
+        .iter()
+        .find(|a| same_name(&a.name, name))
+        .or_else(|| plain.iter().find(|a| a.name == format!("arg{}", index)))
+        .or_else(|| plain.get(index))
+        .map(|a| &a.value)
+}
+
+/// Payload values usually arrive as strings, so structured types accept JSON text
+fn structured(value: &Value) -> Cow<'_, Value> {
+    match value {
+        Value::String(s) => match serde_json::from_str::<Value>(s) {
+            Ok(parsed) if parsed.is_array() || parsed.is_object() => Cow::Owned(parsed),
+            _ => Cow::Borrowed(value),
+        },
+        _ => Cow::Borrowed(value),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn lookup_type<'a>(idl: &'a Value, ty: &Value) -> Result<&'a Value, String> {
+    let name = match ty {
+        Value::String(s) => s.as_str(),
+        other => other.get("name").and_then(|n| n.as_str()).unwrap_or(""),
+    };
+
+    items(idl, "types")
+        .iter()
+        .chain(items(idl, "accounts").iter())
+        .find(|t| name_of(t) == name && t.get("type").is_some())
+        .map(|t| &t["type"])
+        .ok_or_else(|| format!("type {} is not defined in the IDL", name))
+}
+
+fn encode_value(idl: &Value, ty: &Value, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    match ty {
+        Value::String(primitive) => encode_primitive(primitive, value, out),
+        Value::Object(map) => {
+            if let Some(inner) = map.get("vec") {
+                let list = as_list(value)?;
+                out.extend_from_slice(&(list.len() as u32).to_le_bytes());
+                for item in list.iter() {
+                    encode_value(idl, inner, item, out)?;
+                }
+                Ok(())
+            } else if let Some(inner) = map.get("option") {
+                if is_none(value) {
+                    out.push(0);
+                    Ok(())
+                } else {
+                    out.push(1);
+                    encode_value(idl, inner, value, out)
+                }
+            } else if let Some(inner) = map.get("coption") {
+                if is_none(value) {
+                    out.extend_from_slice(&0u32.to_le_bytes());
+                    Ok(())
+                } else {
+                    out.extend_from_slice(&1u32.to_le_bytes());
+                    encode_value(idl, inner, value, out)
+                }
+            } else if let Some(array) = map.get("array").and_then(|a| a.as_array()) {
+                let inner = array.first().ok_or("array type has no element type")?;
+                let len = array.get(1).and_then(|n| n.as_u64()).ok_or("array length must be a constant")?;
+                let list = as_list(value)?;
+                if list.len() as u64 != len {
+                    return Err(format!("expected {} elements, got {}", len, list.len()));
+                }
+                for item in list.iter() {
+                    encode_value(idl, inner, item, out)?;
+                }
+                Ok(())
+            } else if let Some(defined) = map.get("defined") {
+                encode_defined(idl, lookup_type(idl, defined)?, value, out)
+            } else {
+                Err(format!("unsupported type {}", ty))
+            }
+        }
+        _ => Err(format!("unsupported type {}", ty)),
+    }
+}
+
+fn is_none(value: &Value) -> bool {
+    matches!(value, Value::Null) || matches!(value, Value::String(s) if s.trim().is_empty() || s.trim() == "null")
+}
+
+fn as_list(value: &Value) -> Result<Vec<Value>, String> {
+    match structured(value).as_ref() {
+        Value::Array(items) => Ok(items.clone()),
+        Value::String(s) if s.trim().is_empty() => Ok(vec![]),
+        Value::String(s) => Ok(s.split(',').map(|v| Value::String(v.trim().to_string())).collect()),
+        other => Err(format!("expected a list, got {}", other)),
+    }
+}
+
+fn encode_primitive(primitive: &str, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    let text = scalar_text(value);
+    match primitive {
+        "bool" => match text.as_str() {
+            "true" | "1" => out.push(1),
+            "false" | "0" => out.push(0),
+            _ => return Err(format!("expected a boolean, got {}", text)),
+        },
+        "u8" | "u16" | "u32" | "u64" | "u128" => {
+            let bits: u32 = primitive[1..].parse().unwrap_or(64);
+            let parsed: u128 = text.parse().map_err(|_| format!("expected {}, got {}", primitive, text))?;
+            if bits < 128 && parsed >> bits != 0 {
+                return Err(format!("{} is out of range for {}", text, primitive));
+            }
+            out.extend_from_slice(&parsed.to_le_bytes()[..(bits / 8) as usize]);
+        }
+        "i8" | "i16" | "i32" | "i64" | "i128" => {
+            let bits: u32 = primitive[1..].parse().unwrap_or(64);
+            let parsed: i128 = text.parse().map_err(|_| format!("expected {}, got {}", primitive, text))?;
+            if bits < 128 {
+                let limit = 1i128 << (bits - 1);
+                if parsed < -limit || parsed >= limit {
+                    return Err(format!("{} is out of range for {}", text, primitive));
+                }
+            }
+            out.extend_from_slice(&parsed.to_le_bytes()[..(bits / 8) as usize]);
+        }
+        "f32" => {
+            let parsed: f32 = text.parse().map_err(|_| format!("expected f32, got {}", text))?;
+            out.extend_from_slice(&parsed.to_le_bytes());
+        }
+        "f64" => {
+            let parsed: f64 = text.parse().map_err(|_| format!("expected f64, got {}", text))?;
+            out.extend_from_slice(&parsed.to_le_bytes());
+        }
+        "string" => {
+            let s = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+        "publicKey" | "pubkey" => {
+            let key = decode_pubkey(&text).map_err(|e| e.to_string())?;
+            out.extend_from_slice(&key);
+        }
+        "bytes" => {
+            let bytes = parse_bytes(value)?;
+            out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            out.extend_from_slice(&bytes);
+        }
+        other => return Err(format!("unsupported type {}", other)),
+    }
+    Ok(())
+}
+
+/// Bytes as a 0x-prefixed hex string or a JSON array of numbers
+fn parse_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    match structured(value).as_ref() {
+        Value::Array(items) => items
+            .iter()
+            .map(|b| b.as_u64().filter(|b| *b <= 255).map(|b| b as u8).ok_or("byte values must be 0-255".to_string()))
+            .collect(),
+        Value::String(s) => hex::decode(s.trim().trim_start_matches("0x")).map_err(|e| format!("invalid hex: {}", e)),
+        other => Err(format!("expected bytes, got {}", other)),
+    }
+}
+
+fn encode_defined(idl: &Value, def: &Value, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    match def.get("kind").and_then(|k| k.as_str()) {
+        Some("struct") => encode_fields(idl, def.get("fields").unwrap_or(&Value::Null), &structured(value), out),
+        Some("enum") => {
+            let variants = items(def, "variants");
+            let parsed = structured(value);
+            let (variant_name, fields_value) = match parsed.as_ref() {
+                Value::Object(map) if map.len() == 1 => {
+                    let (k, v) = map.iter().next().unwrap();
+                    (k.clone(), v.clone())
+                }
+                other => (scalar_text(other), Value::Null),
+            };
+            let index = variants
+                .iter()
+                .position(|v| same_name(name_of(v), &variant_name))
+                .ok_or_else(|| format!("unknown enum variant {}", variant_name))?;
+            out.push(index as u8);
+            match variants[index].get("fields") {
+                Some(fields) => encode_fields(idl, fields, &fields_value, out),
+                None => Ok(()),
+            }
+        }
+        Some("type") | Some("alias") => {
+            let alias = def.get("alias").or_else(|| def.get("value")).ok_or("alias has no target type")?;
+            encode_value(idl, alias, value, out)
+        }
+        other => Err(format!("unsupported type definition kind {:?}", other)),
+    }
+}
+
+/// Named fields come from an object (or positional array); tuple fields from an array
+fn encode_fields(idl: &Value, fields: &Value, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    let fields = fields.as_array().map(|f| f.as_slice()).unwrap_or(&[]);
+
+    for (i, field) in fields.iter().enumerate() {
+        let (name, ty) = match field.get("type") {
+            Some(ty) => (Some(name_of(field)), ty),
+            None => (None, field),
+        };
+
+        let item = match (name, value) {
+            (Some(name), Value::Object(map)) => map
+                .iter()
+                .find(|(k, _)| same_name(k, name))
+                .map(|(_, v)| v)
+                .ok_or_else(|| format!("missing field {}", name))?,
+            (_, Value::Array(items)) => items.get(i).ok_or_else(|| format!("missing field {}", i))?,
+            _ => return Err(format!("expected an object or array, got {}", value)),
+        };
+
+        encode_value(idl, ty, item, out)?;
+    }
+    Ok(())
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if data.len() < len {
+        return Err("unexpected end of data".into());
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+fn take_len(data: &mut &[u8]) -> Result<usize, String> {
+    let bytes = take(data, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+}
+
+fn decode_value(idl: &Value, ty: &Value, data: &mut &[u8]) -> Result<Value, String> {
+    match ty {
+        Value::String(primitive) => decode_primitive(primitive, data),
+        Value::Object(map) => {
+            if let Some(inner) = map.get("vec") {
+                let len = take_len(data)?;
+                (0..len).map(|_| decode_value(idl, inner, data)).collect::<Result<Vec<_>, _>>().map(Value::Array)
+            } else if let Some(inner) = map.get("option") {
+                match take(data, 1)?[0] {
+                    0 => Ok(Value::Null),
+                    _ => decode_value(idl, inner, data),
+                }
+            } else if let Some(inner) = map.get("coption") {
+                match take_len(data)? {
+                    0 => Ok(Value::Null),
+                    _ => decode_value(idl, inner, data),
+                }
+            } else if let Some(array) = map.get("array").and_then(|a| a.as_array()) {
+                let inner = array.first().ok_or("array type has no element type")?;
+                let len = array.get(1).and_then(|n| n.as_u64()).ok_or("array length must be a constant")?;
+                (0..len).map(|_| decode_value(idl, inner, data)).collect::<Result<Vec<_>, _>>().map(Value::Array)
+            } else if let Some(defined) = map.get("defined") {
+                decode_defined(idl, lookup_type(idl, defined)?, data)
+            } else {
+                Err(format!("unsupported type {}", ty))
+            }
+        }
+        _ => Err(format!("unsupported type {}", ty)),
+    }
+}
+
+fn decode_primitive(primitive: &str, data: &mut &[u8]) -> Result<Value, String> {
+    let value = match primitive {
+        "bool" => Value::Bool(take(data, 1)?[0] != 0),
+        "u8" | "u16" | "u32" | "u64" | "u128" => {
+            let size = primitive[1..].parse::<usize>().unwrap_or(64) / 8;
+            let mut buf = [0u8; 16];
+            buf[..size].copy_from_slice(take(data, size)?);
+            let parsed = u128::from_le_bytes(buf);
+            // 64-bit and wider integers lose precision as JSON numbers
+            if size <= 4 {
+                Value::from(parsed as u64)
+            } else {
+                Value::String(parsed.to_string())
+            }
+        }
+        "i8" | "i16" | "i32" | "i64" | "i128" => {
+            let size = primitive[1..].parse::<usize>().unwrap_or(64) / 8;
+            let bytes = take(data, size)?;
+            let fill = if bytes[size - 1] & 0x80 != 0 { 0xff } else { 0 };
+            let mut buf = [fill; 16];
+            buf[..size].copy_from_slice(bytes);
+            let parsed = i128::from_le_bytes(buf);
+            if size <= 4 {
+                Value::from(parsed as i64)
+            } else {
+                Value::String(parsed.to_string())
+            }
+        }
+        "f32" => Value::from(f32::from_le_bytes(take(data, 4)?.try_into().unwrap()) as f64),
+        "f64" => Value::from(f64::from_le_bytes(take(data, 8)?.try_into().unwrap())),
+        "string" => {
+            let len = take_len(data)?;
+            Value::String(String::from_utf8_lossy(take(data, len)?).to_string())
+        }
+        "publicKey" | "pubkey" => Value::String(encode_pubkey(&take(data, 32)?.try_into().unwrap())),
+        "bytes" => {
+            let len = take_len(data)?;
+            Value::String(format!("0x{}", hex::encode(take(data, len)?)))
+        }
+        other => return Err(format!("unsupported type {}", other)),
+    };
+    Ok(value)
+}
+
+fn decode_defined(idl: &Value, def: &Value, data: &mut &[u8]) -> Result<Value, String> {
+    match def.get("kind").and_then(|k| k.as_str()) {
+        Some("struct") => decode_fields(idl, def.get("fields").unwrap_or(&Value::Null), data),
+        Some("enum") => {
+            let index = take(data, 1)?[0] as usize;
+            let variant = items(def, "variants")
+                .get(index)
+                .ok_or_else(|| format!("unknown enum variant index {}", index))?;
+            match variant.get("fields") {
+                Some(fields) => {
+                    let mut map = Map::new();
+                    map.insert(name_of(variant).to_string(), decode_fields(idl, fields, data)?);
+                    Ok(Value::Object(map))
+                }
+                None => Ok(Value::String(name_of(variant).to_string())),
+            }
+        }
+        Some("type") | Some("alias") => {
+            let alias = def.get("alias").or_else(|| def.get("value")).ok_or("alias has no target type")?;
+            decode_value(idl, alias, data)
+        }
+        other => Err(format!("unsupported type definition kind {:?}", other)),
+    }
+}
+
+fn decode_fields(idl: &Value, fields: &Value, data: &mut &[u8]) -> Result<Value, String> {
+    let fields = fields.as_array().map(|f| f.as_slice()).unwrap_or(&[]);
+    let named = fields.first().is_some_and(|f| f.get("type").is_some());
+
+    if named {
+        let mut map = Map::new();
+        for field in fields {
+            map.insert(name_of(field).to_string(), decode_value(idl, &field["type"], data)?);
+        }
+        Ok(Value::Object(map))
+    } else {
+        fields
+            .iter()
+            .map(|ty| decode_value(idl, ty, data))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+}
+
+/// Decodes an Anchor event from `Program data:` bytes; returns None for unknown events
+pub fn decode_event(idl: &Value, data: &[u8]) -> Option<DecodedEvent> {
+    items(idl, "events").iter().find_map(|event| {
+        let name = name_of(event);
+        let disc = discriminator(event, &format!("event:{}", name));
+        let mut body = data.strip_prefix(disc.as_slice())?;
+
+        // Legacy IDLs inline the fields; 0.30+ moves them into `types`
+        let fields = match event.get("fields") {
+            Some(fields) => fields.clone(),
+            None => lookup_type(idl, &Value::String(name.to_string())).ok()?.get("fields")?.clone(),
+        };
+
+        let args = decode_fields(idl, &fields, &mut body).ok()?;
+        Some(DecodedEvent { name: name.to_string(), args })
+    })
+}
+
+fn flatten_accounts<'a>(accounts: &'a [Value], out: &mut Vec<&'a Value>) {
+    for account in accounts {
+        match account.get("accounts").and_then(|a| a.as_array()) {
+            Some(nested) => flatten_accounts(nested, out),
+            None => out.push(account),
+        }
+    }
+}
+
+fn flag(account: &Value, keys: &[&str]) -> bool {
+    keys.iter().any(|k| account.get(*k).and_then(|v| v.as_bool()).unwrap_or(false))
+}
+
+fn well_known_account(name: &str) -> Option<&'static str> {
+    match snake_case(name).as_str() {
+        "system_program" => Some(SYSTEM_PROGRAM_ID),
+        "token_program" => Some(TOKEN_PROGRAM_ID),
+        "associated_token_program" => Some(ASSOCIATED_TOKEN_PROGRAM_ID),
+        "rent" => Some(SYSVAR_RENT_ID),
+        "clock" => Some(SYSVAR_CLOCK_ID),
+        _ => None,
+    }
+}
+
+/// Resolves the accounts for an instruction from `account:<name>` / `pda_seed:<name>`
+/// payload args, fixed IDL addresses, PDA seeds, well-known programs and the signer.
+pub fn resolve_accounts(
+    idl: &Value,
+    name: &str,
+    args: &[ContractArg],
+    signer: &str,
+    program_id: &str,
+) -> Result<Vec<InstructionAccount>, CocoError> {
+    let ix = find_instruction(idl, name)?;
+    let mut accounts = Vec::new();
+    flatten_accounts(items(ix, "accounts"), &mut accounts);
+
+    let explicit = |prefix: &str, account: &str| {
+        args.iter()
+            .find(|a| a.name.strip_prefix(prefix).is_some_and(|n| same_name(n, account)))
+            .map(|a| scalar_text(&a.value))
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut resolved: Vec<Option<String>> = accounts
+        .iter()
+        .map(|account| {
+            let account_name = name_of(account);
+            explicit(ACCOUNT_ARG_PREFIX, account_name)
+                .or_else(|| account.get("address").and_then(|a| a.as_str()).map(String::from))
+                .or_else(|| well_known_account(account_name).map(String::from))
+                .or_else(|| flag(account, &["signer", "isSigner"]).then(|| signer.to_string()))
+        })
+        .collect();
+
+    // PDAs may depend on other accounts, so keep resolving until nothing changes
+    loop {
+        let mut progressed = false;
+        for (i, account) in accounts.iter().enumerate() {
+            if resolved[i].is_some() {
+                continue;
+            }
+            let account_name = name_of(account);
+
+            let seeds = match explicit(PDA_SEED_ARG_PREFIX, account_name) {
+                Some(manual) => Some(parse_manual_seeds(&manual)),
+                None => match account.get("pda") {
+                    Some(pda) => pda_seeds(idl, ix, pda, args, &accounts, &resolved)?,
+                    None => None,
+                },
+            };
+
+            if let Some(seeds) = seeds {
+                let program = match account.pointer("/pda/program") {
+                    Some(program) => match seed_bytes(idl, ix, program, args, &accounts, &resolved)? {
+                        Some(bytes) => bytes,
+                        None => continue,
+                    },
+                    None => decode_pubkey(program_id)?.to_vec(),
+                };
+                let program: [u8; 32] = program
+                    .try_into()
+                    .map_err(|_| CocoError::Validation(format!("Invalid PDA program for {}", account_name)))?;
+                let (address, _) = find_program_address(&seeds, &program)?;
+                resolved[i] = Some(encode_pubkey(&address));
+                progressed = true;
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+
+    accounts
+        .iter()
+        .zip(resolved)
+        .map(|(account, address)| {
+            let account_name = name_of(account);
+            let is_signer = flag(account, &["signer", "isSigner"]);
+            let optional = flag(account, &["optional", "isOptional"]);
+
+            match address {
+                Some(pubkey) => Ok(InstructionAccount {
+                    pubkey,
+                    is_signer,
+                    is_writable: flag(account, &["writable", "isMut"]),
+                }),
+                // Anchor passes the program ID in place of an omitted optional account
+                None if optional => Ok(InstructionAccount {
+                    pubkey: program_id.to_string(),
+                    is_signer: false,
+                    is_writable: false,
+                }),
+                None => Err(CocoError::Validation(format!(
+                    "Missing account {} for {}",
+                    account_name,
+                    name_of(ix)
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Seeds from the payload: a JSON array or comma-separated list where each seed is
+/// 0x-hex, a base58 public key, or plain UTF-8 text
+fn parse_manual_seeds(value: &str) -> Vec<Vec<u8>> {
+    let parts: Vec<String> = match serde_json::from_str::<Vec<Value>>(value) {
+        Ok(items) => items.iter().map(scalar_text).collect(),
+        Err(_) => value.split(',').map(|s| s.trim().to_string()).collect(),
+    };
+
+    parts
+        .into_iter()
+        .map(|part| {
+            if let Some(hex_part) = part.strip_prefix("0x") {
+                if let Ok(bytes) = hex::decode(hex_part) {
+                    return bytes;
+                }
+            }
+            match decode_pubkey(&part) {
+                Ok(key) => key.to_vec(),
+                Err(_) => part.into_bytes(),
+            }
+        })
+        .collect()
+}
+
+/// Returns None while a seed depends on an account that is not resolved yet
+fn pda_seeds(
+    idl: &Value,
+    ix: &Value,
+    pda: &Value,
+    args: &[ContractArg],
+    accounts: &[&Value],
+    resolved: &[Option<String>],
+) -> Result<Option<Vec<Vec<u8>>>, CocoError> {
+    let mut seeds = Vec::new();
+    for seed in items(pda, "seeds") {
+        match seed_bytes(idl, ix, seed, args, accounts, resolved)? {
+            Some(bytes) => seeds.push(bytes),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(seeds))
+}
+
+fn seed_bytes(
+    idl: &Value,
+    ix: &Value,
+    seed: &Value,
+    args: &[ContractArg],
+    accounts: &[&Value],
+    resolved: &[Option<String>],
+) -> Result<Option<Vec<u8>>, CocoError> {
+    let path = seed.get("path").and_then(|p| p.as_str()).unwrap_or("");
+
+    match seed.get("kind").and_then(|k| k.as_str()) {
+        Some("const") => match &seed["value"] {
+            Value::String(s) => Ok(Some(s.as_bytes().to_vec())),
+            Value::Array(_) => parse_bytes(&seed["value"]).map(Some).map_err(CocoError::Validation),
+            other => Err(CocoError::Validation(format!("Unsupported PDA seed value {}", other))),
+        },
+        Some("account") => {
+            let index = accounts.iter().position(|a| same_name(name_of(a), path));
+            match index.and_then(|i| resolved[i].as_deref()) {
+                Some(address) => Ok(Some(decode_pubkey(address)?.to_vec())),
+                None if index.is_none() => Err(CocoError::Validation(format!(
+                    "Unsupported PDA seed path {}",
+                    path
+                ))),
+                None => Ok(None),
+            }
+        }
+        Some("arg") => {
+            let arg_def = items(ix, "args")
+                .iter()
+                .find(|a| same_name(name_of(a), path))
+                .ok_or_else(|| CocoError::Validation(format!("Unsupported PDA seed path {}", path)))?;
+            let index = items(ix, "args").iter().position(|a| std::ptr::eq(a, arg_def)).unwrap_or(0);
+            let value = find_arg(args, path, index)
+                .ok_or_else(|| CocoError::Validation(format!("Missing argument {} for PDA seed", path)))?;
+
+            // Strings and byte seeds are used raw, without the borsh length prefix
+            let bytes = match arg_def["type"].as_str() {
+                Some("string") => scalar_text(value).into_bytes(),
+                Some("bytes") => parse_bytes(value).map_err(CocoError::Validation)?,
+                _ => {
+                    let mut out = Vec::new();
+                    encode_value(idl, &arg_def["type"], value, &mut out).map_err(CocoError::Validation)?;
+                    out
+                }
+            };
+            Ok(Some(bytes))
+        }
+        other => Err(CocoError::Validation(format!("Unsupported PDA seed kind {:?}", other))),
+    }
+}
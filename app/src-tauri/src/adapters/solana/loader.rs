@@ -0,0 +1,90 @@
+//! Instructions of the upgradeable BPF loader, which deploys a program by
+//! writing its binary to a buffer account and then moving it into the
+//! program's data account.
+
+use super::transaction::{
+    decode_pubkey, find_program_address, AccountMeta, Instruction, Pubkey, SYSTEM_PROGRAM_ID,
+    SYSVAR_CLOCK_ID, SYSVAR_RENT_ID,
+};
+use crate::error::CocoError;
+
+pub const BPF_LOADER_UPGRADEABLE_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+/// Buffer account header: state tag and optional authority
+pub const BUFFER_METADATA_SIZE: usize = 37;
+/// Program account: state tag and the program data address
+pub const PROGRAM_SIZE: usize = 36;
+/// Bytes of the binary sent per write, keeping each transaction under the
+/// 1232-byte packet limit
+pub const WRITE_CHUNK_SIZE: usize = 1000;
+
+pub fn loader_id() -> Pubkey {
+    decode_pubkey(BPF_LOADER_UPGRADEABLE_ID).expect("upgradeable loader id is valid")
+}
+
+/// Sets up `buffer`, already allocated and owned by the loader, to be written by `authority`
+pub fn initialize_buffer(buffer: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: loader_id(),
+        accounts: vec![
+            AccountMeta { pubkey: buffer, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: authority, is_signer: false, is_writable: false },
+        ],
+        data: 0u32.to_le_bytes().to_vec(),
+    }
+}
+
+/// Writes `bytes` of the program binary at `offset` in the buffer
+pub fn write(buffer: Pubkey, authority: Pubkey, offset: u32, bytes: &[u8]) -> Instruction {
+    let mut data = Vec::with_capacity(16 + bytes.len());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&offset.to_le_bytes());
+    data.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    data.extend_from_slice(bytes);
+
+    Instruction {
+        program_id: loader_id(),
+        accounts: vec![
+            AccountMeta { pubkey: buffer, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: authority, is_signer: true, is_writable: false },
+        ],
+        data,
+    }
+}
+
+/// Deploys the buffer's binary to `program`, whose data account can later
+/// hold an upgraded binary of up to `max_data_len` bytes
+pub fn deploy_with_max_data_len(
+    payer: Pubkey,
+    program: Pubkey,
+    buffer: Pubkey,
+    authority: Pubkey,
+    max_data_len: u64,
+) -> Result<Instruction, CocoError> {
+    let (program_data, _) = program_data_address(&program)?;
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&max_data_len.to_le_bytes());
+
+    let readonly = |key: &str| -> Result<AccountMeta, CocoError> {
+        Ok(AccountMeta { pubkey: decode_pubkey(key)?, is_signer: false, is_writable: false })
+    };
+    Ok(Instruction {
+        program_id: loader_id(),
+        accounts: vec![
+            AccountMeta { pubkey: payer, is_signer: true, is_writable: true },
+            AccountMeta { pubkey: program_data, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: program, is_signer: false, is_writable: true },
+            AccountMeta { pubkey: buffer, is_signer: false, is_writable: true },
+            readonly(SYSVAR_RENT_ID)?,
+            readonly(SYSVAR_CLOCK_ID)?,
+            readonly(SYSTEM_PROGRAM_ID)?,
+            AccountMeta { pubkey: authority, is_signer: true, is_writable: false },
+        ],
+        data,
+    })
+}
+
+/// Account holding a deployed program's binary
+pub fn program_data_address(program: &Pubkey) -> Result<(Pubkey, u8), CocoError> {
+    find_program_address(&[program.to_vec()], &loader_id())
+}
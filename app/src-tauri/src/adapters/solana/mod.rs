@@ -0,0 +1,1159 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::adapters::derivation;
+use crate::adapters::signer::SoftwareSigner;
+use crate::adapters::traits::*;
+use crate::error::CocoError;
+use crate::types::*;
+
+pub mod anchor;
+pub mod idl;
+pub mod loader;
+pub mod rpc;
+pub mod transaction;
+
+pub use rpc::{HttpTransport, RpcTransport};
+use transaction::{
+    compile_message, decode_pubkey, encode_pubkey, serialize_transaction, Instruction, Message,
+    TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+/// Default derivation path used by Phantom and the Solana CLI
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Most signatures `getSignatureStatuses` accepts per call
+const MAX_SIGNATURE_STATUSES: usize = 256;
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const CONFIRM_ATTEMPTS: u32 = 120;
+
+/// Solana adapter that talks to a node over JSON-RPC
+pub struct SolanaAdapter<T: RpcTransport = HttpTransport> {
+    config: ChainConfig,
+    rpc: T,
+}
+
+impl SolanaAdapter<HttpTransport> {
+    /// Creates an adapter connected to the chain's `rpc_url` over HTTP
+    pub fn new(config: ChainConfig) -> Result<Self, CocoError> {
+        let rpc = HttpTransport::new(&config.rpc_url)?;
+        Ok(Self::with_transport(config, rpc))
+    }
+}
+
+impl<T: RpcTransport> SolanaAdapter<T> {
+    /// Creates an adapter over an existing transport (e.g. a recorded stand-in in tests)
+    pub fn with_transport(config: ChainConfig, rpc: T) -> Self {
+        Self { config, rpc }
+    }
+
+    async fn latest_blockhash(&self) -> Result<[u8; 32], CocoError> {
+        let result = self
+            .rpc
+            .call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))
+            .await?;
+        let blockhash = result
+            .pointer("/value/blockhash")
+            .and_then(|b| b.as_str())
+            .ok_or_else(|| CocoError::Adapter("getLatestBlockhash returned no blockhash".into()))?;
+        decode_pubkey(blockhash)
+    }
+
+    async fn rent_exempt_balance(&self, space: usize) -> Result<u64, CocoError> {
+        self.rpc
+            .call("getMinimumBalanceForRentExemption", json!([space]))
+            .await?
+            .as_u64()
+            .ok_or_else(|| CocoError::Adapter("getMinimumBalanceForRentExemption returned no balance".into()))
+    }
+
+    /// Signs the instructions with `signer` as fee payer and `keypairs` as any
+    /// other signers, sends them and returns the signature
+    async fn send_signed(
+        &self,
+        signer: &dyn ExternalSigner,
+        instructions: &[Instruction],
+        keypairs: &[&SigningKey],
+        blockhash: [u8; 32],
+    ) -> Result<String, CocoError> {
+        let payer = decode_pubkey(&signer.address())?;
+        let message = compile_message(payer, instructions, blockhash);
+
+        let mut signatures = Vec::with_capacity(message.signers.len());
+        for key in &message.signers {
+            let signature = if *key == payer {
+                signer
+                    .sign_transaction(&message.bytes)
+                    .await?
+                    .try_into()
+                    .map_err(|_| CocoError::Crypto("Signer returned an invalid ed25519 signature".into()))?
+            } else {
+                keypairs
+                    .iter()
+                    .find(|k| k.verifying_key().to_bytes() == *key)
+                    .map(|k| k.sign(&message.bytes).to_bytes())
+                    .ok_or_else(|| {
+                        CocoError::Validation(format!(
+                            "Instruction requires an additional signer {}",
+                            encode_pubkey(key)
+                        ))
+                    })?
+            };
+            signatures.push(signature);
+        }
+        let wire = serialize_transaction(&signatures, &message.bytes);
+
+        let result = self
+            .rpc
+            .call(
+                "sendTransaction",
+                json!([BASE64.encode(wire), {
+                    "encoding": "base64",
+                    "preflightCommitment": "confirmed",
+                }]),
+            )
+            .await?;
+
+        Ok(result.as_str().map(String::from).unwrap_or_else(|| bs58::encode(signatures[0]).into_string()))
+    }
+
+    /// Waits until every signature is confirmed, failing on the first that errored
+    async fn confirm_signatures(&self, signatures: &[String]) -> Result<(), CocoError> {
+        for batch in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+            let mut attempts = 0;
+            loop {
+                let result = self.rpc.call("getSignatureStatuses", json!([batch])).await?;
+                let statuses = result.get("value").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+                let mut confirmed = 0;
+                for (signature, status) in batch.iter().zip(&statuses) {
+                    if let Some(err) = status.get("err").filter(|e| !e.is_null()) {
+                        return Err(CocoError::Adapter(format!("Transaction {} failed: {}", signature, err)));
+                    }
+                    let level = status.get("confirmationStatus").and_then(|c| c.as_str());
+                    if matches!(level, Some("confirmed" | "finalized")) {
+                        confirmed += 1;
+                    }
+                }
+                if confirmed == batch.len() {
+                    break;
+                }
+
+                attempts += 1;
+                if attempts >= CONFIRM_ATTEMPTS {
+                    return Err(CocoError::Adapter(format!(
+                        "{} of {} transactions did not confirm in time",
+                        batch.len() - confirmed,
+                        batch.len()
+                    )));
+                }
+                tokio::time::sleep(CONFIRM_POLL_INTERVAL).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn fetch_transaction(&self, signature: &str) -> Result<Value, CocoError> {
+        self.rpc
+            .call(
+                "getTransaction",
+                json!([signature, {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }]),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl<T: RpcTransport> ChainAdapter for SolanaAdapter<T> {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Solana
+    }
+
+    fn chain_id(&self) -> &str {
+        &self.config.id
+    }
+
+    async fn validate_connection(&self) -> Result<bool, CocoError> {
+        Ok(self.rpc.call("getHealth", json!([])).await.is_ok())
+    }
+
+    async fn get_block_height(&self) -> Result<u64, CocoError> {
+        let slot = self
+            .rpc
+            .call("getSlot", json!([{ "commitment": "confirmed" }]))
+            .await?;
+        slot.as_u64()
+            .ok_or_else(|| CocoError::Adapter("getSlot returned a non-numeric slot".into()))
+    }
+}
+
+#[async_trait]
+impl<T: RpcTransport> WalletAdapter for SolanaAdapter<T> {
+    async fn generate_wallet(&self) -> Result<WalletData, CocoError> {
+        let key = SigningKey::generate(&mut rand::rngs::OsRng);
+        Ok(wallet_data(&key))
+    }
+
+    async fn import_wallet(&self, private_key: &str) -> Result<WalletData, CocoError> {
+        let key = parse_secret_key(private_key)?;
+        Ok(wallet_data(&key))
+    }
+
+    async fn import_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        derivation_path: Option<&str>,
+    ) -> Result<WalletData, CocoError> {
+        let seed = derivation::mnemonic_to_seed(mnemonic)?;
+        let secret = derivation::derive_ed25519(
+            &seed,
+            derivation_path.unwrap_or(DEFAULT_DERIVATION_PATH),
+        )?;
+        Ok(wallet_data(&SigningKey::from_bytes(&secret)))
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<Balance, CocoError> {
+        decode_pubkey(address)?;
+        let result = self
+            .rpc
+            .call("getBalance", json!([address, { "commitment": "confirmed" }]))
+            .await?;
+        let lamports = result
+            .get("value")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| CocoError::Adapter("getBalance returned no value".into()))?;
+
+        Ok(Balance {
+            native: lamports.to_string(),
+            native_decimals: 9,
+            native_symbol: self.config.native_currency.clone(),
+        })
+    }
+
+    async fn get_token_balances(&self, address: &str) -> Result<Vec<TokenBalance>, CocoError> {
+        decode_pubkey(address)?;
+        let mut balances = Vec::new();
+
+        for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            let result = self
+                .rpc
+                .call(
+                    "getTokenAccountsByOwner",
+                    json!([address, { "programId": program_id }, { "encoding": "jsonParsed" }]),
+                )
+                .await?;
+
+            let accounts = result.get("value").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            for account in accounts {
+                let Some(info) = account.pointer("/account/data/parsed/info") else {
+                    continue;
+                };
+                let mint = info.get("mint").and_then(|m| m.as_str()).unwrap_or_default();
+                let amount = info.pointer("/tokenAmount/amount").and_then(|a| a.as_str()).unwrap_or("0");
+                let decimals = info.pointer("/tokenAmount/decimals").and_then(|d| d.as_u64()).unwrap_or(0);
+
+                // Token symbols live in metadata accounts; show the shortened mint instead
+                balances.push(TokenBalance {
+                    address: mint.to_string(),
+                    symbol: mint.chars().take(4).collect(),
+                    decimals: decimals as u8,
+                    balance: amount.to_string(),
+                });
+            }
+        }
+
+        Ok(balances)
+    }
+
+    async fn request_faucet(&self, address: &str) -> Result<String, CocoError> {
+        decode_pubkey(address)?;
+        let signature = self
+            .rpc
+            .call("requestAirdrop", json!([address, LAMPORTS_PER_SOL]))
+            .await?;
+        signature
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| CocoError::Adapter("requestAirdrop returned no signature".into()))
+    }
+
+    async fn sign_message(
+        &self,
+        wallet: &WalletData,
+        message: &[u8],
+    ) -> Result<Vec<u8>, CocoError> {
+        let key = signing_key(wallet)?;
+        Ok(key.sign(message).to_bytes().to_vec())
+    }
+}
+
+#[async_trait]
+impl<T: RpcTransport> TransactionAdapter for SolanaAdapter<T> {
+    async fn send_transaction(
+        &self,
+        wallet: &WalletData,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let signer = SoftwareSigner::from_wallet(Ecosystem::Solana, wallet)?;
+        self.send_transaction_with_signer(&signer, tx).await
+    }
+
+    async fn send_transaction_with_signer(
+        &self,
+        signer: &dyn ExternalSigner,
+        tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        let payer = decode_pubkey(&signer.address())?;
+        let instructions = to_instructions(&tx, Some(payer))?;
+
+        let blockhash = self.latest_blockhash().await?;
+        Ok(TransactionResult {
+            hash: self.send_signed(signer, &instructions, &[], blockhash).await?,
+            status: TransactionStatus::Pending,
+            nonce: None,
+        })
+    }
+
+    async fn get_transaction(&self, hash: &str) -> Result<TransactionData, CocoError> {
+        let tx = self.fetch_transaction(hash).await?;
+        if tx.is_null() {
+            return Err(CocoError::NotFound("Transaction not found".into()));
+        }
+
+        let keys = tx.pointer("/transaction/message/accountKeys").and_then(|k| k.as_array()).cloned().unwrap_or_default();
+        let first_ix = tx.pointer("/transaction/message/instructions/0");
+        let key_at = |index: Option<u64>| {
+            index.and_then(|i| keys.get(i as usize)).and_then(|k| k.as_str()).map(String::from)
+        };
+
+        Ok(TransactionData {
+            hash: hash.to_string(),
+            from: key_at(Some(0)).unwrap_or_default(),
+            to: key_at(first_ix.and_then(|ix| ix.get("programIdIndex")).and_then(|i| i.as_u64())),
+            value: None,
+            data: first_ix
+                .and_then(|ix| ix.get("data"))
+                .and_then(|d| d.as_str())
+                .and_then(|d| bs58::decode(d).into_vec().ok()),
+            block_number: tx.get("slot").and_then(|s| s.as_u64()),
+            timestamp: tx
+                .get("blockTime")
+                .and_then(|t| t.as_i64())
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                .unwrap_or_else(chrono::Utc::now),
+        })
+    }
+
+    async fn get_transaction_receipt(&self, hash: &str) -> Result<TransactionReceipt, CocoError> {
+        let tx = self.fetch_transaction(hash).await?;
+        if tx.is_null() {
+            return Err(CocoError::NotFound("Receipt not available yet".into()));
+        }
+
+        let meta = tx.get("meta").cloned().unwrap_or(Value::Null);
+        Ok(TransactionReceipt {
+            hash: hash.to_string(),
+            status: meta.get("err").unwrap_or(&Value::Null).is_null(),
+            revert: instruction_error(&meta),
+            block_number: tx.get("slot").and_then(|s| s.as_u64()).unwrap_or_default(),
+            block_hash: None,
+            gas_used: meta.get("computeUnitsConsumed").and_then(|c| c.as_u64()).unwrap_or_default(),
+            fee: meta.get("fee").and_then(|f| f.as_u64()).map(|f| f.to_string()),
+            events: vec![],
+            logs: program_logs(meta.get("logMessages")),
+            contract_address: None,
+        })
+    }
+
+    async fn estimate_fees(&self, tx: &TransactionRequest) -> Result<FeeEstimate, CocoError> {
+        // Base fee is charged per signature; the fee payer always signs
+        let extra_signers = tx.accounts.iter().filter(|a| a.is_signer).count() as u64;
+        let signatures = 1 + extra_signers.saturating_sub(1);
+        let compute_units = tx.gas_limit.unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT);
+
+        // Priority fees paid recently by transactions that lock the same accounts
+        let writable: Vec<&str> = tx
+            .accounts
+            .iter()
+            .filter(|a| a.is_writable)
+            .map(|a| a.pubkey.as_str())
+            .collect();
+        let recent = self
+            .rpc
+            .call("getRecentPrioritizationFees", json!([writable]))
+            .await?;
+        let mut fees: Vec<u64> = recent
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|f| f.get("prioritizationFee").and_then(|p| p.as_u64()))
+            .collect();
+        fees.sort_unstable();
+
+        let tier = |percent: usize| {
+            let micro_lamports_per_unit = percentile(&fees, percent);
+            let priority_fee = (micro_lamports_per_unit as u128 * compute_units as u128)
+                .div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+            FeeTier {
+                pricing: FeePricing::ComputeUnit { micro_lamports_per_unit },
+                total_fee: (LAMPORTS_PER_SIGNATURE as u128 * signatures as u128 + priority_fee).to_string(),
+            }
+        };
+
+        Ok(FeeEstimate {
+            gas_limit: compute_units,
+            slow: tier(25),
+            normal: tier(50),
+            fast: tier(75),
+        })
+    }
+
+    async fn simulate_transaction(
+        &self,
+        tx: &TransactionRequest,
+    ) -> Result<SimulationResult, CocoError> {
+        let payer = tx
+            .accounts
+            .iter()
+            .find(|a| a.is_signer)
+            .map(|a| decode_pubkey(&a.pubkey))
+            .transpose()?
+            .ok_or_else(|| {
+                CocoError::Validation("Simulation needs a signer account to pay fees".into())
+            })?;
+
+        let instructions = to_instructions(tx, Some(payer))?;
+        // The node swaps in a fresh blockhash, and signatures are not verified
+        let Message { signers, bytes } = compile_message(payer, &instructions, [0u8; 32]);
+        let wire = serialize_transaction(&vec![[0u8; 64]; signers.len()], &bytes);
+
+        let result = self
+            .rpc
+            .call(
+                "simulateTransaction",
+                json!([BASE64.encode(wire), {
+                    "encoding": "base64",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true,
+                    "commitment": "confirmed",
+                }]),
+            )
+            .await?;
+
+        let value = result.get("value").cloned().unwrap_or(Value::Null);
+        Ok(SimulationResult {
+            revert: instruction_error(&value),
+            success: value.get("err").unwrap_or(&Value::Null).is_null(),
+            return_data: value
+                .pointer("/returnData/data/0")
+                .and_then(|d| d.as_str())
+                .and_then(|d| BASE64.decode(d).ok())
+                .unwrap_or_default(),
+            gas_used: value.get("unitsConsumed").and_then(|u| u.as_u64()).unwrap_or_default(),
+            logs: program_logs(value.get("logs")),
+        })
+    }
+}
+
+#[async_trait]
+impl<T: RpcTransport> ContractAdapter for SolanaAdapter<T> {
+    fn deployment_request(
+        &self,
+        _bytecode: &[u8],
+        _constructor_args: &[u8],
+    ) -> Result<TransactionRequest, CocoError> {
+        Err(CocoError::Adapter(
+            "Solana programs are deployed from their binary with deploy_program".into(),
+        ))
+    }
+
+    async fn deploy_program(
+        &self,
+        signer: &dyn ExternalSigner,
+        program: &[u8],
+        program_keypair: &str,
+    ) -> Result<(String, TransactionResult), CocoError> {
+        if program.is_empty() {
+            return Err(CocoError::Validation("Program binary is empty".into()));
+        }
+        let program_key = parse_secret_key(program_keypair)?;
+        let program_id = program_key.verifying_key().to_bytes();
+        let payer = decode_pubkey(&signer.address())?;
+        let loader_id = loader::loader_id();
+
+        let existing = self
+            .rpc
+            .call("getAccountInfo", json!([encode_pubkey(&program_id), { "encoding": "base64" }]))
+            .await?;
+        if existing.get("value").is_some_and(|v| !v.is_null()) {
+            return Err(CocoError::Validation(format!(
+                "Program {} is already deployed; upgrade it with `anchor upgrade` or `solana program deploy`",
+                encode_pubkey(&program_id)
+            )));
+        }
+
+        // Upload the binary to a fresh buffer, the way `solana program deploy` does
+        let buffer_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let buffer = buffer_key.verifying_key().to_bytes();
+        let buffer_len = loader::BUFFER_METADATA_SIZE + program.len();
+        let buffer_rent = self.rent_exempt_balance(buffer_len).await?;
+        let create_buffer = [
+            Instruction::create_account(payer, buffer, buffer_rent, buffer_len as u64, loader_id),
+            loader::initialize_buffer(buffer, payer),
+        ];
+        let blockhash = self.latest_blockhash().await?;
+        let created = self.send_signed(signer, &create_buffer, &[&buffer_key], blockhash).await?;
+        self.confirm_signatures(&[created]).await?;
+
+        let blockhash = self.latest_blockhash().await?;
+        let mut writes = Vec::new();
+        for (index, chunk) in program.chunks(loader::WRITE_CHUNK_SIZE).enumerate() {
+            let offset = (index * loader::WRITE_CHUNK_SIZE) as u32;
+            let write = loader::write(buffer, payer, offset, chunk);
+            writes.push(self.send_signed(signer, &[write], &[], blockhash).await?);
+        }
+        self.confirm_signatures(&writes).await?;
+
+        // Leave room for upgrades to double the program's size, as the CLI does
+        let program_rent = self.rent_exempt_balance(loader::PROGRAM_SIZE).await?;
+        let deploy = [
+            Instruction::create_account(payer, program_id, program_rent, loader::PROGRAM_SIZE as u64, loader_id),
+            loader::deploy_with_max_data_len(payer, program_id, buffer, payer, 2 * program.len() as u64)?,
+        ];
+        let blockhash = self.latest_blockhash().await?;
+        let hash = self.send_signed(signer, &deploy, &[&program_key], blockhash).await?;
+
+        Ok((
+            encode_pubkey(&program_id),
+            TransactionResult {
+                hash,
+                status: TransactionStatus::Pending,
+                nonce: None,
+            },
+        ))
+    }
+
+    async fn call_contract(
+        &self,
+        address: &str,
+        _function: &str,
+        _args: Vec<ContractArg>,
+    ) -> Result<ContractCallResult, CocoError> {
+        // Solana has no view calls; reads go straight to account data
+        let result = self
+            .rpc
+            .call("getAccountInfo", json!([address, { "encoding": "base64" }]))
+            .await?;
+        let account = result
+            .get("value")
+            .filter(|v| !v.is_null())
+            .ok_or_else(|| CocoError::NotFound(format!("Account not found: {}", address)))?;
+
+        let return_data = account
+            .pointer("/data/0")
+            .and_then(|d| d.as_str())
+            .and_then(|d| BASE64.decode(d).ok())
+            .unwrap_or_default();
+
+        Ok(ContractCallResult {
+            return_data,
+            decoded: json!({
+                "lamports": account.get("lamports"),
+                "owner": account.get("owner"),
+                "executable": account.get("executable"),
+            }),
+        })
+    }
+
+    fn encode_call(
+        &self,
+        abi: &[u8],
+        function: &str,
+        args: Vec<ContractArg>,
+    ) -> Result<Vec<u8>, CocoError> {
+        let idl = idl::parse_idl(abi)?;
+        idl::encode_instruction(&idl, function, &args)
+    }
+
+    fn resolve_accounts(
+        &self,
+        abi: &[u8],
+        function: &str,
+        args: &[ContractArg],
+        signer: &str,
+        program: &str,
+    ) -> Result<Vec<InstructionAccount>, CocoError> {
+        let idl = idl::parse_idl(abi)?;
+        let program = if program.is_empty() {
+            idl::program_address(&idl).unwrap_or_default().to_string()
+        } else {
+            program.to_string()
+        };
+        idl::resolve_accounts(&idl, function, args, signer, &program)
+    }
+
+    fn decode_events(
+        &self,
+        abi: &[u8],
+        logs: &[Log],
+    ) -> Result<Vec<DecodedEvent>, CocoError> {
+        let idl = idl::parse_idl(abi)?;
+        Ok(logs
+            .iter()
+            .filter_map(|log| idl::decode_event(&idl, &log.data))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<T: RpcTransport> DiscoveryAdapter for SolanaAdapter<T> {
+    async fn discover_contracts(
+        &self,
+        project_path: &Path,
+    ) -> Result<Vec<DiscoveredContract>, CocoError> {
+        // Anchor writes IDLs to target/idl after `anchor build`
+        Ok(anchor::discover(project_path, anchor::cluster_for(&self.config.rpc_url)))
+    }
+
+    async fn fetch_contract_interface(
+        &self,
+        _address: &str,
+    ) -> Result<Option<Vec<u8>>, CocoError> {
+        Ok(None)
+    }
+}
+
+/// Accepts a base58 keypair (Phantom export), a JSON byte array (Solana CLI keypair
+/// file) or hex, each as either a 64-byte keypair or a 32-byte secret
+pub(crate) fn parse_secret_key(private_key: &str) -> Result<SigningKey, CocoError> {
+    let trimmed = private_key.trim();
+
+    let bytes = if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(trimmed)
+            .map_err(|e| CocoError::Validation(format!("Invalid keypair JSON: {}", e)))?
+    } else if let Some(hex_key) = trimmed.strip_prefix("0x") {
+        hex::decode(hex_key)
+            .map_err(|e| CocoError::Validation(format!("Invalid hex private key: {}", e)))?
+    } else {
+        bs58::decode(trimmed)
+            .into_vec()
+            .map_err(|e| CocoError::Validation(format!("Invalid Solana private key: {}", e)))?
+    };
+
+    match bytes.len() {
+        32 => Ok(SigningKey::from_bytes(&bytes.try_into().unwrap())),
+        64 => {
+            let keypair: [u8; 64] = bytes.try_into().unwrap();
+            SigningKey::from_keypair_bytes(&keypair).map_err(|_| {
+                CocoError::Validation("Keypair public key does not match its secret key".into())
+            })
+        }
+        len => Err(CocoError::Validation(format!(
+            "Invalid Solana private key length: {} bytes",
+            len
+        ))),
+    }
+}
+
+fn signing_key(wallet: &WalletData) -> Result<SigningKey, CocoError> {
+    let private_key = wallet.private_key.as_deref().ok_or_else(|| {
+        CocoError::Validation(format!("Wallet {} has no private key to sign with", wallet.address))
+    })?;
+    parse_secret_key(private_key)
+}
+
+pub(crate) fn wallet_data(key: &SigningKey) -> WalletData {
+    let address = encode_pubkey(&key.verifying_key().to_bytes());
+    WalletData {
+        public_key: address.clone(),
+        address,
+        private_key_encrypted: None,
+        private_key: Some(bs58::encode(key.to_keypair_bytes()).into_string()),
+    }
+}
+
+/// Builds a request's instructions, preceded by compute budget instructions
+/// when it sets a compute unit limit or price
+fn to_instructions(tx: &TransactionRequest, payer: Option<[u8; 32]>) -> Result<Vec<Instruction>, CocoError> {
+    let mut instructions = Vec::new();
+    if let Some(limit) = tx.gas_limit {
+        let limit = u32::try_from(limit)
+            .map_err(|_| CocoError::Validation(format!("Compute unit limit too large: {}", limit)))?;
+        instructions.push(Instruction::set_compute_unit_limit(limit));
+    }
+    match &tx.fee {
+        Some(FeePricing::ComputeUnit { micro_lamports_per_unit }) => {
+            instructions.push(Instruction::set_compute_unit_price(*micro_lamports_per_unit));
+        }
+        Some(_) => {
+            return Err(CocoError::Validation(
+                "Solana priority fees are priced per compute unit".into(),
+            ))
+        }
+        None => {}
+    }
+
+    instructions.push(to_instruction(tx, payer)?);
+    Ok(instructions)
+}
+
+/// Value at `percent` of an ascending list, or zero when it's empty
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    sorted[(sorted.len() - 1) * percent / 100]
+}
+
+/// Builds the instruction for a request: program call when `data` is set,
+/// otherwise a native SOL transfer of `value` lamports to `to`
+fn to_instruction(tx: &TransactionRequest, payer: Option<[u8; 32]>) -> Result<Instruction, CocoError> {
+    let to = tx
+        .to
+        .as_deref()
+        .ok_or_else(|| CocoError::Validation("Solana transactions need a target address".into()))?;
+
+    match &tx.data {
+        Some(data) => Instruction::from_request(to, &tx.accounts, data.clone()),
+        None => {
+            let from = payer.ok_or_else(|| {
+                CocoError::Validation("Transfers need a fee payer".into())
+            })?;
+            let lamports = tx
+                .value
+                .as_deref()
+                .unwrap_or("0")
+                .parse::<u64>()
+                .map_err(|_| CocoError::Validation(format!("Invalid lamport amount: {:?}", tx.value)))?;
+            Ok(Instruction::transfer(from, decode_pubkey(to)?, lamports))
+        }
+    }
+}
+
+/// Describes a failed transaction's `err`, e.g. `{"InstructionError":[0,{"Custom":6001}]}`
+fn instruction_error(meta: &Value) -> Option<RevertInfo> {
+    let err = meta.get("err").filter(|e| !e.is_null())?;
+    let message = match err.pointer("/InstructionError/1/Custom").and_then(|c| c.as_u64()) {
+        Some(code) => format!("Program error {} (0x{:x}) in instruction {}", code, code, err.pointer("/InstructionError/0").unwrap_or(&Value::Null)),
+        None => err.to_string(),
+    };
+    Some(RevertInfo {
+        message,
+        ..Default::default()
+    })
+}
+
+/// Extracts `Program data:` entries (Anchor events) with the program that emitted them
+fn program_logs(messages: Option<&Value>) -> Vec<Log> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut logs = Vec::new();
+
+    let lines = messages.and_then(|m| m.as_array()).cloned().unwrap_or_default();
+    for line in lines.iter().filter_map(|l| l.as_str()) {
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if let Ok(bytes) = BASE64.decode(data.trim()) {
+                logs.push(Log {
+                    address: stack.last().cloned().unwrap_or_default(),
+                    topics: vec![],
+                    data: bytes,
+                });
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            let program = parts.next().unwrap_or_default();
+            match parts.next() {
+                Some("invoke") => stack.push(program.to_string()),
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    logs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use transaction::Pubkey;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::Mutex;
+
+    /// Replays canned RPC results and records the requests made
+    #[derive(Default)]
+    struct RecordedRpc {
+        responses: Mutex<HashMap<String, VecDeque<Value>>>,
+        requests: Mutex<Vec<(String, Value)>>,
+    }
+
+    impl RecordedRpc {
+        fn respond(&self, method: &str, result: Value) {
+            self.responses
+                .lock()
+                .unwrap()
+                .entry(method.to_string())
+                .or_default()
+                .push_back(result);
+        }
+    }
+
+    #[async_trait]
+    impl RpcTransport for RecordedRpc {
+        async fn call(&self, method: &str, params: Value) -> Result<Value, CocoError> {
+            self.requests.lock().unwrap().push((method.to_string(), params));
+            self.responses
+                .lock()
+                .unwrap()
+                .get_mut(method)
+                .and_then(|q| q.pop_front())
+                .ok_or_else(|| CocoError::Adapter(format!("No recorded response for {}", method)))
+        }
+    }
+
+    const IDL: &str = r#"{
+        "version": "0.1.0",
+        "name": "counter",
+        "instructions": [{
+            "name": "increment",
+            "accounts": [
+                {"name": "counter", "isMut": true, "isSigner": false,
+                 "pda": {"seeds": [{"kind": "const", "type": "string", "value": "counter"},
+                                   {"kind": "account", "type": "publicKey", "path": "authority"}]}},
+                {"name": "authority", "isMut": false, "isSigner": true},
+                {"name": "systemProgram", "isMut": false, "isSigner": false}
+            ],
+            "args": [{"name": "amount", "type": "u64"}, {"name": "memo", "type": {"option": "string"}}]
+        }],
+        "events": [{"name": "Incremented", "fields": [
+            {"name": "authority", "type": "publicKey", "index": false},
+            {"name": "total", "type": "u64", "index": false}
+        ]}]
+    }"#;
+
+    const PROGRAM_ID: &str = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS";
+
+    fn adapter() -> SolanaAdapter<RecordedRpc> {
+        let config = ChainConfig {
+            id: "solana-localnet".to_string(),
+            name: "Localnet".to_string(),
+            ecosystem: Ecosystem::Solana,
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            native_currency: "SOL".to_string(),
+        };
+        SolanaAdapter::with_transport(config, RecordedRpc::default())
+    }
+
+    fn arg(name: &str, value: &str) -> ContractArg {
+        ContractArg { name: name.to_string(), value: json!(value) }
+    }
+
+    #[tokio::test]
+    async fn test_slot_and_balances() {
+        let adapter = adapter();
+        let owner = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+        adapter.rpc.respond("getSlot", json!(312));
+        adapter.rpc.respond("getBalance", json!({ "context": { "slot": 312 }, "value": 1_500_000_000u64 }));
+        adapter.rpc.respond("getTokenAccountsByOwner", json!({ "value": [{
+            "account": { "data": { "parsed": { "info": {
+                "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "tokenAmount": { "amount": "2500000", "decimals": 6 }
+            }}}}
+        }]}));
+        adapter.rpc.respond("getTokenAccountsByOwner", json!({ "value": [] }));
+
+        assert_eq!(adapter.get_block_height().await.unwrap(), 312);
+
+        let balance = adapter.get_balance(owner).await.unwrap();
+        assert_eq!(balance.native, "1500000000");
+        assert_eq!(balance.native_decimals, 9);
+
+        let tokens = adapter.get_token_balances(owner).await.unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].balance, "2500000");
+        assert_eq!(tokens[0].decimals, 6);
+    }
+
+    #[tokio::test]
+    async fn test_encode_call_and_resolve_accounts() {
+        let adapter = adapter();
+        let wallet = adapter.generate_wallet().await.unwrap();
+        let args = vec![arg("amount", "5"), arg("memo", "")];
+
+        let data = adapter.encode_call(IDL.as_bytes(), "increment", args.clone()).unwrap();
+        let discriminator = &Sha256::digest(b"global:increment")[..8];
+        assert_eq!(&data[..8], discriminator);
+        assert_eq!(&data[8..16], &5u64.to_le_bytes());
+        assert_eq!(data[16], 0);
+
+        let accounts = adapter
+            .resolve_accounts(IDL.as_bytes(), "increment", &args, &wallet.address, PROGRAM_ID)
+            .unwrap();
+        let (expected_pda, _) = transaction::find_program_address(
+            &[b"counter".to_vec(), decode_pubkey(&wallet.address).unwrap().to_vec()],
+            &decode_pubkey(PROGRAM_ID).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(accounts[0].pubkey, encode_pubkey(&expected_pda));
+        assert!(accounts[0].is_writable);
+        assert_eq!(accounts[1].pubkey, wallet.address);
+        assert!(accounts[1].is_signer);
+        assert_eq!(accounts[2].pubkey, transaction::SYSTEM_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_send_transfer_signs_message() {
+        let adapter = adapter();
+        let wallet = adapter.generate_wallet().await.unwrap();
+        let recipient = adapter.generate_wallet().await.unwrap();
+        adapter.rpc.respond("getLatestBlockhash", json!({ "value": {
+            "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+            "lastValidBlockHeight": 400
+        }}));
+        adapter.rpc.respond("sendTransaction", json!("5sig"));
+
+        let tx = TransactionRequest {
+            to: Some(recipient.address.clone()),
+            value: Some("1000".to_string()),
+            ..Default::default()
+        };
+        let result = adapter.send_transaction(&wallet, tx).await.unwrap();
+        assert_eq!(result.hash, "5sig");
+
+        let requests = adapter.rpc.requests.lock().unwrap();
+        let (_, params) = requests.iter().find(|(m, _)| m == "sendTransaction").unwrap();
+        let wire = BASE64.decode(params[0].as_str().unwrap()).unwrap();
+
+        // One signature, then the message it signs
+        assert_eq!(wire[0], 1);
+        let signature = ed25519_dalek::Signature::from_slice(&wire[1..65]).unwrap();
+        let message = &wire[65..];
+        let signer = signing_key(&wallet).unwrap().verifying_key();
+        assert!(signer.verify_strict(message, &signature).is_ok());
+        assert_eq!(&message[..3], &[1, 0, 1]);
+    }
+
+    /// Program, accounts and data of a compiled instruction
+    type DecodedInstruction = (Pubkey, Vec<Pubkey>, Vec<u8>);
+
+    /// Splits a wire transaction into its signatures, message, account keys and instructions
+    fn decode_wire(wire: &[u8]) -> (Vec<[u8; 64]>, Vec<u8>, Vec<Pubkey>, Vec<DecodedInstruction>) {
+        fn length(bytes: &[u8], at: &mut usize) -> usize {
+            let (mut len, mut shift) = (0, 0);
+            loop {
+                let byte = bytes[*at];
+                *at += 1;
+                len |= ((byte & 0x7f) as usize) << shift;
+                if byte & 0x80 == 0 {
+                    return len;
+                }
+                shift += 7;
+            }
+        }
+
+        let mut at = 0;
+        let signatures = (0..length(wire, &mut at))
+            .map(|i| wire[at + i * 64..at + (i + 1) * 64].try_into().unwrap())
+            .collect::<Vec<[u8; 64]>>();
+        at += signatures.len() * 64;
+        let message = wire[at..].to_vec();
+
+        let mut at = 3;
+        let keys: Vec<Pubkey> = (0..length(&message, &mut at))
+            .map(|_| {
+                at += 32;
+                message[at - 32..at].try_into().unwrap()
+            })
+            .collect();
+        at += 32;
+        let instructions = (0..length(&message, &mut at))
+            .map(|_| {
+                let program = keys[message[at] as usize];
+                at += 1;
+                let accounts = (0..length(&message, &mut at))
+                    .map(|_| {
+                        at += 1;
+                        keys[message[at - 1] as usize]
+                    })
+                    .collect();
+                let len = length(&message, &mut at);
+                at += len;
+                (program, accounts, message[at - len..at].to_vec())
+            })
+            .collect();
+        (signatures, message, keys, instructions)
+    }
+
+    #[tokio::test]
+    async fn test_deploy_program_uploads_through_a_buffer() {
+        let adapter = adapter();
+        let payer = SoftwareSigner::new(Ecosystem::Solana, &format!("0x{}", "22".repeat(32))).unwrap();
+        let program_key = SigningKey::from_bytes(&[7u8; 32]);
+        let keypair = serde_json::to_string(&program_key.to_keypair_bytes().to_vec()).unwrap();
+        let program_id = program_key.verifying_key().to_bytes();
+        let binary: Vec<u8> = (0..2500u32).map(|i| i as u8).collect();
+
+        // An existing program is upgraded with the CLI instead
+        adapter.rpc.respond("getAccountInfo", json!({ "value": { "lamports": 1 } }));
+        let deployed = adapter.deploy_program(&payer, &binary, &keypair).await;
+        assert!(matches!(deployed, Err(CocoError::Validation(ref m)) if m.contains("already deployed")));
+
+        adapter.rpc.respond("getAccountInfo", json!({ "value": null }));
+        adapter.rpc.respond("getMinimumBalanceForRentExemption", json!(18_000_000));
+        adapter.rpc.respond("getMinimumBalanceForRentExemption", json!(1_141_440));
+        for _ in 0..3 {
+            adapter.rpc.respond("getLatestBlockhash", json!({ "value": {
+                "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                "lastValidBlockHeight": 400
+            }}));
+        }
+        for signature in ["create", "write-0", "write-1", "write-2", "deploy"] {
+            adapter.rpc.respond("sendTransaction", json!(signature));
+        }
+        let confirmed = json!({ "confirmationStatus": "confirmed", "err": null });
+        adapter.rpc.respond("getSignatureStatuses", json!({ "value": [confirmed] }));
+        adapter.rpc.respond("getSignatureStatuses", json!({ "value": [confirmed, confirmed, confirmed] }));
+
+        let (address, sent) = adapter.deploy_program(&payer, &binary, &keypair).await.unwrap();
+        assert_eq!(address, encode_pubkey(&program_id));
+        assert_eq!(sent.hash, "deploy");
+
+        let requests = adapter.rpc.requests.lock().unwrap();
+        let rent_sizes: Vec<_> = requests
+            .iter()
+            .filter(|(m, _)| m == "getMinimumBalanceForRentExemption")
+            .map(|(_, params)| params[0].clone())
+            .collect();
+        assert_eq!(rent_sizes, vec![json!(2537), json!(36)]);
+        let sent: Vec<Vec<u8>> = requests
+            .iter()
+            .filter(|(m, _)| m == "sendTransaction")
+            .map(|(_, params)| BASE64.decode(params[0].as_str().unwrap()).unwrap())
+            .collect();
+        assert_eq!(sent.len(), 5);
+        assert!(sent.iter().all(|wire| wire.len() <= 1232));
+
+        let payer_key = decode_pubkey(&payer.address()).unwrap();
+        let loader_id = loader::loader_id();
+        let verify = |key: &Pubkey, message: &[u8], signature: &[u8; 64]| {
+            let key = ed25519_dalek::VerifyingKey::from_bytes(key).unwrap();
+            key.verify_strict(message, &ed25519_dalek::Signature::from_bytes(signature)).is_ok()
+        };
+
+        // A new buffer, funded by the payer and signed by both
+        let (signatures, message, keys, instructions) = decode_wire(&sent[0]);
+        let buffer = instructions[0].1[1];
+        assert_eq!(&keys[..2], &[payer_key, buffer]);
+        assert!(verify(&payer_key, &message, &signatures[0]) && verify(&buffer, &message, &signatures[1]));
+        assert_eq!(&instructions[0].2[4..12], &18_000_000u64.to_le_bytes());
+        assert_eq!(&instructions[0].2[12..20], &2537u64.to_le_bytes());
+        assert_eq!(&instructions[0].2[20..], &loader_id);
+        assert_eq!((instructions[1].0, instructions[1].2.as_slice()), (loader_id, &[0u8, 0, 0, 0][..]));
+
+        // The writes reassemble the binary
+        let mut uploaded = vec![0u8; binary.len()];
+        for wire in &sent[1..4] {
+            let (signatures, message, _, instructions) = decode_wire(wire);
+            assert_eq!(signatures.len(), 1);
+            assert!(verify(&payer_key, &message, &signatures[0]));
+            let (program, accounts, data) = &instructions[0];
+            assert_eq!((*program, accounts.as_slice()), (loader_id, &[buffer, payer_key][..]));
+            let offset = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+            let len = u64::from_le_bytes(data[8..16].try_into().unwrap()) as usize;
+            uploaded[offset..offset + len].copy_from_slice(&data[16..]);
+        }
+        assert_eq!(uploaded, binary);
+
+        // The program account is created at the keypair's address, then the buffer deployed to it
+        let (signatures, message, keys, instructions) = decode_wire(&sent[4]);
+        assert_eq!(&keys[..2], &[payer_key, program_id]);
+        assert!(verify(&program_id, &message, &signatures[1]));
+        assert_eq!(&instructions[0].2[12..20], &36u64.to_le_bytes());
+        let (program_data, _) = loader::program_data_address(&program_id).unwrap();
+        let (program, accounts, data) = &instructions[1];
+        assert_eq!(*program, loader_id);
+        assert_eq!(&accounts[..4], &[payer_key, program_data, program_id, buffer]);
+        assert_eq!(accounts[7], payer_key);
+        assert_eq!(&data[..4], &2u32.to_le_bytes());
+        assert_eq!(&data[4..], &5000u64.to_le_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_fee_tiers_from_recent_priority_fees() {
+        let adapter = adapter();
+        adapter.rpc.respond("getRecentPrioritizationFees", json!([
+            { "slot": 1, "prioritizationFee": 0 },
+            { "slot": 2, "prioritizationFee": 4000 },
+            { "slot": 3, "prioritizationFee": 1000 },
+            { "slot": 4, "prioritizationFee": 2000 },
+            { "slot": 5, "prioritizationFee": 3000 },
+        ]));
+
+        let tx = TransactionRequest {
+            gas_limit: Some(100_000),
+            ..Default::default()
+        };
+        let estimate = adapter.estimate_fees(&tx).await.unwrap();
+
+        assert_eq!(estimate.gas_limit, 100_000);
+        assert_eq!(estimate.slow.pricing, FeePricing::ComputeUnit { micro_lamports_per_unit: 1000 });
+        assert_eq!(estimate.fast.pricing, FeePricing::ComputeUnit { micro_lamports_per_unit: 3000 });
+        // 5000 lamports for the signature plus 2000 micro-lamports over 100k units
+        assert_eq!(estimate.normal.total_fee, "5200");
+    }
+
+    #[tokio::test]
+    async fn test_receipt_decodes_anchor_events() {
+        let adapter = adapter();
+        adapter.rpc.respond("getTransaction", Value::Null);
+        assert!(matches!(
+            adapter.get_transaction_receipt("pending").await,
+            Err(CocoError::NotFound(_))
+        ));
+
+        let authority = decode_pubkey(PROGRAM_ID).unwrap();
+        let mut event = Sha256::digest(b"event:Incremented")[..8].to_vec();
+        event.extend_from_slice(&authority);
+        event.extend_from_slice(&7u64.to_le_bytes());
+
+        adapter.rpc.respond("getTransaction", json!({
+            "slot": 99,
+            "meta": {
+                "err": null,
+                "computeUnitsConsumed": 4200,
+                "logMessages": [
+                    format!("Program {} invoke [1]", PROGRAM_ID),
+                    "Program log: Instruction: Increment".to_string(),
+                    format!("Program data: {}", BASE64.encode(&event)),
+                    format!("Program {} success", PROGRAM_ID),
+                ]
+            }
+        }));
+
+        let receipt = adapter.get_transaction_receipt("confirmed").await.unwrap();
+        assert!(receipt.status);
+        assert_eq!(receipt.gas_used, 4200);
+        assert_eq!(receipt.logs[0].address, PROGRAM_ID);
+
+        let events = adapter.decode_events(IDL.as_bytes(), &receipt.logs).unwrap();
+        assert_eq!(events[0].name, "Incremented");
+        assert_eq!(events[0].args["total"], "7");
+        assert_eq!(events[0].args["authority"], PROGRAM_ID);
+    }
+}
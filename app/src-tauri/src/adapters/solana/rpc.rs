@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::CocoError;
+
+/// Transport for Solana JSON-RPC calls, swappable for a recorded stand-in in tests
+#[async_trait]
+pub trait RpcTransport: Send + Sync {
+    /// Sends a request and returns its `result` field
+    async fn call(&self, method: &str, params: Value) -> Result<Value, CocoError>;
+}
+
+/// JSON-RPC over HTTP
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    next_id: AtomicU64,
+}
+
+impl HttpTransport {
+    pub fn new(url: &str) -> Result<Self, CocoError> {
+        reqwest::Url::parse(url)
+            .map_err(|e| CocoError::Adapter(format!("Invalid RPC URL {}: {}", url, e)))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            next_id: AtomicU64::new(1),
+        })
+    }
+}
+
+#[async_trait]
+impl RpcTransport for HttpTransport {
+    async fn call(&self, method: &str, params: Value) -> Result<Value, CocoError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| CocoError::Adapter(format!("RPC request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| CocoError::Adapter(format!("Invalid RPC response: {}", e)))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(rpc_error(method, error));
+        }
+
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+}
+
+/// Formats a JSON-RPC error object, including program logs from failed preflights
+pub fn rpc_error(method: &str, error: &Value) -> CocoError {
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or("unknown error");
+
+    let logs = error
+        .pointer("/data/logs")
+        .and_then(|l| l.as_array())
+        .map(|logs| {
+            logs.iter()
+                .filter_map(|l| l.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|l| !l.is_empty());
+
+    match logs {
+        Some(logs) => CocoError::Adapter(format!("{} failed: {}\n{}", method, message, logs)),
+        None => CocoError::Adapter(format!("{} failed: {}", method, message)),
+    }
+}
@@ -0,0 +1,247 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+use crate::error::CocoError;
+use crate::types::InstructionAccount;
+
+pub type Pubkey = [u8; 32];
+
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const SYSVAR_RENT_ID: &str = "SysvarRent111111111111111111111111111111111";
+pub const SYSVAR_CLOCK_ID: &str = "SysvarC1ock11111111111111111111111111111111";
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+/// A single instruction ready to be compiled into a message
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl Instruction {
+    /// Builds an instruction from the adapter-level request fields
+    pub fn from_request(
+        program_id: &str,
+        accounts: &[InstructionAccount],
+        data: Vec<u8>,
+    ) -> Result<Self, CocoError> {
+        Ok(Self {
+            program_id: decode_pubkey(program_id)?,
+            accounts: accounts
+                .iter()
+                .map(|a| {
+                    Ok(AccountMeta {
+                        pubkey: decode_pubkey(&a.pubkey)?,
+                        is_signer: a.is_signer,
+                        is_writable: a.is_writable,
+                    })
+                })
+                .collect::<Result<Vec<_>, CocoError>>()?,
+            data,
+        })
+    }
+
+    /// System program transfer of `lamports` from `from` to `to`
+    pub fn transfer(from: Pubkey, to: Pubkey, lamports: u64) -> Self {
+        let mut data = Vec::with_capacity(12);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&lamports.to_le_bytes());
+
+        Self {
+            program_id: [0u8; 32],
+            accounts: vec![
+                AccountMeta { pubkey: from, is_signer: true, is_writable: true },
+                AccountMeta { pubkey: to, is_signer: false, is_writable: true },
+            ],
+            data,
+        }
+    }
+
+    /// System program instruction funding `new` with `lamports` and allocating
+    /// `space` bytes owned by `owner`; both accounts sign
+    pub fn create_account(from: Pubkey, new: Pubkey, lamports: u64, space: u64, owner: Pubkey) -> Self {
+        let mut data = Vec::with_capacity(52);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&lamports.to_le_bytes());
+        data.extend_from_slice(&space.to_le_bytes());
+        data.extend_from_slice(&owner);
+
+        Self {
+            program_id: [0u8; 32],
+            accounts: vec![
+                AccountMeta { pubkey: from, is_signer: true, is_writable: true },
+                AccountMeta { pubkey: new, is_signer: true, is_writable: true },
+            ],
+            data,
+        }
+    }
+
+    /// Compute budget instruction capping the compute units the transaction may use
+    pub fn set_compute_unit_limit(units: u32) -> Self {
+        let mut data = vec![2u8];
+        data.extend_from_slice(&units.to_le_bytes());
+        Self::compute_budget(data)
+    }
+
+    /// Compute budget instruction setting the priority fee in micro-lamports per compute unit
+    pub fn set_compute_unit_price(micro_lamports: u64) -> Self {
+        let mut data = vec![3u8];
+        data.extend_from_slice(&micro_lamports.to_le_bytes());
+        Self::compute_budget(data)
+    }
+
+    fn compute_budget(data: Vec<u8>) -> Self {
+        Self {
+            program_id: decode_pubkey(COMPUTE_BUDGET_PROGRAM_ID).expect("compute budget program id is valid"),
+            accounts: vec![],
+            data,
+        }
+    }
+}
+
+/// A compiled legacy message along with the accounts that must sign it
+pub struct Message {
+    pub signers: Vec<Pubkey>,
+    pub bytes: Vec<u8>,
+}
+
+/// Compiles instructions into a legacy message with `payer` as the fee payer
+pub fn compile_message(payer: Pubkey, instructions: &[Instruction], blockhash: Pubkey) -> Message {
+    let mut keys: Vec<AccountMeta> = vec![AccountMeta {
+        pubkey: payer,
+        is_signer: true,
+        is_writable: true,
+    }];
+
+    let mut add = |meta: AccountMeta| match keys.iter_mut().find(|k| k.pubkey == meta.pubkey) {
+        Some(existing) => {
+            existing.is_signer |= meta.is_signer;
+            existing.is_writable |= meta.is_writable;
+        }
+        None => keys.push(meta),
+    };
+
+    for ix in instructions {
+        for meta in &ix.accounts {
+            add(*meta);
+        }
+        add(AccountMeta {
+            pubkey: ix.program_id,
+            is_signer: false,
+            is_writable: false,
+        });
+    }
+
+    // Signers first, then writable before readonly; the sort is stable so the payer stays first
+    keys.sort_by_key(|k| (!k.is_signer, !k.is_writable));
+
+    let num_signers = keys.iter().filter(|k| k.is_signer).count();
+    let readonly_signed = keys.iter().filter(|k| k.is_signer && !k.is_writable).count();
+    let readonly_unsigned = keys.iter().filter(|k| !k.is_signer && !k.is_writable).count();
+    let index_of = |key: &Pubkey| keys.iter().position(|k| &k.pubkey == key).unwrap_or(0) as u8;
+
+    let mut bytes = vec![num_signers as u8, readonly_signed as u8, readonly_unsigned as u8];
+    encode_length(&mut bytes, keys.len());
+    for key in &keys {
+        bytes.extend_from_slice(&key.pubkey);
+    }
+    bytes.extend_from_slice(&blockhash);
+
+    encode_length(&mut bytes, instructions.len());
+    for ix in instructions {
+        bytes.push(index_of(&ix.program_id));
+        encode_length(&mut bytes, ix.accounts.len());
+        for meta in &ix.accounts {
+            bytes.push(index_of(&meta.pubkey));
+        }
+        encode_length(&mut bytes, ix.data.len());
+        bytes.extend_from_slice(&ix.data);
+    }
+
+    Message {
+        signers: keys.iter().take(num_signers).map(|k| k.pubkey).collect(),
+        bytes,
+    }
+}
+
+/// Serializes a transaction from its signatures and message
+pub fn serialize_transaction(signatures: &[[u8; 64]], message: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + signatures.len() * 64 + message.len());
+    encode_length(&mut bytes, signatures.len());
+    for signature in signatures {
+        bytes.extend_from_slice(signature);
+    }
+    bytes.extend_from_slice(message);
+    bytes
+}
+
+/// Solana's compact-u16 length prefix
+fn encode_length(bytes: &mut Vec<u8>, len: usize) {
+    let mut remaining = len as u16;
+    loop {
+        let mut byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        if remaining == 0 {
+            bytes.push(byte);
+            break;
+        }
+        byte |= 0x80;
+        bytes.push(byte);
+    }
+}
+
+pub fn decode_pubkey(value: &str) -> Result<Pubkey, CocoError> {
+    let bytes = bs58::decode(value.trim())
+        .into_vec()
+        .map_err(|e| CocoError::Validation(format!("Invalid Solana address {}: {}", value, e)))?;
+    bytes
+        .try_into()
+        .map_err(|_| CocoError::Validation(format!("Invalid Solana address {}: expected 32 bytes", value)))
+}
+
+pub fn encode_pubkey(key: &Pubkey) -> String {
+    bs58::encode(key).into_string()
+}
+
+/// Whether the bytes are a valid ed25519 point, i.e. could have a private key
+pub fn is_on_curve(key: &Pubkey) -> bool {
+    CompressedEdwardsY(*key).decompress().is_some()
+}
+
+/// Finds the program derived address and bump seed for the given seeds
+pub fn find_program_address(seeds: &[Vec<u8>], program_id: &Pubkey) -> Result<(Pubkey, u8), CocoError> {
+    if seeds.len() > 15 || seeds.iter().any(|s| s.len() > 32) {
+        return Err(CocoError::Validation(
+            "PDA seeds exceed the maximum count or length".into(),
+        ));
+    }
+
+    for bump in (0..=255u8).rev() {
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(b"ProgramDerivedAddress");
+
+        let candidate: Pubkey = hasher.finalize().into();
+        if !is_on_curve(&candidate) {
+            return Ok((candidate, bump));
+        }
+    }
+
+    Err(CocoError::Validation(
+        "Unable to find a valid program address for seeds".into(),
+    ))
+}
@@ -75,17 +75,82 @@ pub trait TransactionAdapter: ChainAdapter {
         &self,
         tx: &TransactionRequest,
     ) -> Result<SimulationResult, CocoError>;
+
+    /// Next nonce the node expects from an account, counting pending transactions.
+    /// `None` on chains without account nonces.
+    async fn get_pending_nonce(&self, _address: &str) -> Result<Option<u64>, CocoError> {
+        Ok(None)
+    }
+
+    /// Sends a transaction signed by an external signer (hardware wallet, remote signer)
+    async fn send_transaction_with_signer(
+        &self,
+        _signer: &dyn ExternalSigner,
+        _tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        Err(CocoError::Adapter(format!(
+            "External signers are not supported on chain {}",
+            self.chain_id()
+        )))
+    }
+
+    /// Sends `tx` with the nonce of the pending transaction `original_hash` and a
+    /// fee high enough for the node to drop the original in its favour
+    async fn replace_transaction(
+        &self,
+        _signer: &dyn ExternalSigner,
+        _original_hash: &str,
+        _tx: TransactionRequest,
+    ) -> Result<TransactionResult, CocoError> {
+        Err(CocoError::Adapter(format!(
+            "Replacing pending transactions is not supported on chain {}",
+            self.chain_id()
+        )))
+    }
+}
+
+/// Signs on behalf of a wallet whose private key is not stored in the app
+#[async_trait]
+pub trait ExternalSigner: Send + Sync {
+    /// Address of the account the signer controls
+    fn address(&self) -> String;
+
+    /// Public key, encoded the same way as `WalletData::public_key`
+    fn public_key(&self) -> String;
+
+    /// Signs an arbitrary message using the chain's message signing scheme
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, CocoError>;
+
+    /// Signs a transaction's signing payload: the unsigned RLP encoding on EVM
+    /// (returning `r || s || v`), the serialized message on Solana, and the
+    /// signing message on Aptos
+    async fn sign_transaction(&self, payload: &[u8]) -> Result<Vec<u8>, CocoError>;
 }
 
 #[async_trait]
 pub trait ContractAdapter: ChainAdapter {
-    /// Deploys a contract
-    async fn deploy_contract(
+    /// Builds the transaction that deploys a contract from its creation bytecode
+    /// and encoded constructor arguments, to be sent like any other
+    fn deployment_request(
         &self,
-        wallet: &WalletData,
         bytecode: &[u8],
-        constructor_args: Option<Vec<u8>>,
-    ) -> Result<DeploymentResult, CocoError>;
+        constructor_args: &[u8],
+    ) -> Result<TransactionRequest, CocoError>;
+
+    /// Deploys a program binary (Solana) to the address of `program_keypair`,
+    /// paid for and upgradeable by `signer`. Returns the program's address and
+    /// the final transaction, which deploys it once the binary is uploaded.
+    async fn deploy_program(
+        &self,
+        _signer: &dyn ExternalSigner,
+        _program: &[u8],
+        _program_keypair: &str,
+    ) -> Result<(String, TransactionResult), CocoError> {
+        Err(CocoError::Adapter(format!(
+            "Program deployment is not supported on chain {}",
+            self.chain_id()
+        )))
+    }
 
     /// Calls a read-only contract function
     async fn call_contract(
@@ -103,6 +168,18 @@ pub trait ContractAdapter: ChainAdapter {
         args: Vec<ContractArg>,
     ) -> Result<Vec<u8>, CocoError>;
 
+    /// Resolves the accounts an instruction touches (Solana); other chains need none
+    fn resolve_accounts(
+        &self,
+        _abi: &[u8],
+        _function: &str,
+        _args: &[ContractArg],
+        _signer: &str,
+        _program: &str,
+    ) -> Result<Vec<InstructionAccount>, CocoError> {
+        Ok(vec![])
+    }
+
     /// Decodes contract events/logs
     fn decode_events(
         &self,
@@ -0,0 +1,340 @@
+//! Reads compiled contracts from a project's build output: Foundry's `out/`,
+//! Hardhat's `artifacts/` and Anchor's `target/deploy/`.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::error::{CocoError, Result};
+use crate::types::{DiscoveredContract, InterfaceType};
+
+/// A compiled contract ready to deploy
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    /// Creation bytecode (EVM) or program binary (Solana)
+    pub bytecode: Vec<u8>,
+    /// ABI JSON from the artifact, when it has one
+    pub abi: Option<String>,
+    /// Keypair file of the address a Solana program deploys to
+    pub program_keypair: Option<String>,
+}
+
+/// Finds the build artifact for a contract, trying Foundry, Hardhat and then
+/// Anchor output. `source` is the contract's source file, used to pick between
+/// contracts of the same name in different files.
+pub fn find(project: &Path, name: &str, source: Option<&Path>) -> Result<Artifact> {
+    let source_file = source.and_then(|s| s.file_name()).and_then(|s| s.to_str());
+
+    for dir in ["out", "artifacts"] {
+        if let Some(path) = find_json(&project.join(dir), name, source_file) {
+            return read_json(&path);
+        }
+    }
+
+    let deploy = project.join("target").join("deploy");
+    for candidate in [name.to_string(), name.to_lowercase().replace('-', "_")] {
+        let path = deploy.join(format!("{}.so", candidate));
+        if path.is_file() {
+            let keypair = deploy.join(format!("{}-keypair.json", candidate));
+            return Ok(Artifact {
+                bytecode: std::fs::read(&path)?,
+                abi: None,
+                program_keypair: std::fs::read_to_string(keypair).ok(),
+            });
+        }
+    }
+
+    Err(CocoError::NotFound(format!(
+        "No build artifact found for {}; build the project first",
+        name
+    )))
+}
+
+/// Decodes creation bytecode given as hex, with or without a `0x` prefix
+pub fn decode_bytecode(name: &str, hex_code: &str) -> Result<Vec<u8>> {
+    let hex_code = hex_code.trim().trim_start_matches("0x");
+    if hex_code.contains("__") {
+        return Err(CocoError::Validation(format!(
+            "{} has unlinked library references; link them before deploying",
+            name
+        )));
+    }
+    hex::decode(hex_code)
+        .map_err(|e| CocoError::Validation(format!("Invalid bytecode for {}: {}", name, e)))
+}
+
+/// Every contract in a Foundry or Hardhat build, with its ABI, creation bytecode,
+/// source file and compiler version. Interfaces and abstract contracts have no
+/// bytecode; sources under `lib/` or `node_modules` are dependencies.
+pub fn discover_evm(project: &Path) -> Vec<DiscoveredContract> {
+    let mut build_infos = HashMap::new();
+    let mut discovered = Vec::new();
+
+    for dir in ["out", "artifacts"] {
+        let root = project.join(dir);
+        if !root.is_dir() {
+            continue;
+        }
+
+        for entry in WalkDir::new(&root)
+            .into_iter()
+            // Build info holds every source and output of a compile, not contracts
+            .filter_entry(|e| e.file_name() != "build-info")
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy();
+                name.ends_with(".json") && !name.ends_with(".dbg.json")
+            })
+        {
+            if let Some(contract) = evm_contract(project, entry.path(), &mut build_infos) {
+                discovered.push(contract);
+            }
+        }
+    }
+
+    discovered
+}
+
+/// Reads one Foundry or Hardhat artifact, skipping files that aren't contracts
+fn evm_contract(
+    project: &Path,
+    path: &Path,
+    build_infos: &mut HashMap<PathBuf, Option<String>>,
+) -> Option<DiscoveredContract> {
+    let artifact: Value = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+    let abi = artifact.get("abi").filter(|abi| abi.is_array())?;
+
+    // Foundry's metadata names the source and contract that were compiled;
+    // Hardhat has them at the top level
+    let metadata = match artifact.get("metadata").or_else(|| artifact.get("rawMetadata")) {
+        Some(Value::String(raw)) => serde_json::from_str(raw).unwrap_or(Value::Null),
+        Some(metadata) => metadata.clone(),
+        None => Value::Null,
+    };
+    let target = metadata
+        .pointer("/settings/compilationTarget")
+        .and_then(|t| t.as_object())
+        .and_then(|t| t.iter().next());
+
+    let stem = path.file_stem()?.to_str()?;
+    let name = target
+        .and_then(|(_, name)| name.as_str())
+        .or_else(|| artifact.get("contractName").and_then(|n| n.as_str()))
+        // Foundry adds the compiler version when several are used: Token.0.8.19.json
+        .unwrap_or_else(|| stem.split('.').next().unwrap_or(stem))
+        .to_string();
+    let source = target
+        .map(|(source, _)| source.as_str())
+        .or_else(|| artifact.get("sourceName").and_then(|s| s.as_str()))
+        .or_else(|| artifact.pointer("/ast/absolutePath").and_then(|s| s.as_str()))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            path.strip_prefix(project)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string()
+        });
+
+    // Forge tests and scripts aren't contracts to interact with
+    if source.ends_with(".t.sol") || source.ends_with(".s.sol") {
+        return None;
+    }
+
+    let compiler_version = metadata
+        .pointer("/compiler/version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| hardhat_compiler_version(path, build_infos));
+    let bytecode = match artifact.get("bytecode") {
+        Some(Value::String(code)) => Some(code.as_str()),
+        Some(code) => code.get("object").and_then(|o| o.as_str()),
+        None => None,
+    }
+    .filter(|code| !code.trim_start_matches("0x").is_empty());
+
+    Some(DiscoveredContract {
+        name,
+        is_interface: bytecode.is_none(),
+        is_dependency: source.starts_with("lib/")
+            || source.starts_with('@')
+            || source.contains("node_modules"),
+        path: source,
+        interface_type: InterfaceType::Abi,
+        abi: Some(abi.to_string()),
+        bytecode: bytecode.map(str::to_string),
+        compiler_version,
+        idl: None,
+        move_definition: None,
+        deployed_address: None,
+    })
+}
+
+/// Only the part of a Hardhat build info needed here
+#[derive(Deserialize)]
+struct BuildInfo {
+    #[serde(rename = "solcVersion")]
+    solc_version: String,
+}
+
+/// Compiler version of a Hardhat artifact, from the build info its `.dbg.json` points at
+fn hardhat_compiler_version(
+    artifact: &Path,
+    build_infos: &mut HashMap<PathBuf, Option<String>>,
+) -> Option<String> {
+    let dbg: Value = serde_json::from_slice(&std::fs::read(artifact.with_extension("dbg.json")).ok()?).ok()?;
+    let build_info = artifact.parent()?.join(dbg.get("buildInfo")?.as_str()?);
+
+    build_infos
+        .entry(build_info)
+        .or_insert_with_key(|path| {
+            let info: BuildInfo = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+            Some(info.solc_version)
+        })
+        .clone()
+}
+
+/// `<name>.json` under `dir`, preferring the one in the source file's directory
+/// (`out/Token.sol/Token.json`, `artifacts/contracts/Token.sol/Token.json`)
+fn find_json(dir: &Path, name: &str, source_file: Option<&str>) -> Option<PathBuf> {
+    if !dir.is_dir() {
+        return None;
+    }
+
+    let file_name = format!("{}.json", name);
+    let matches: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name().to_str() == Some(file_name.as_str()))
+        .map(|e| e.into_path())
+        .collect();
+
+    matches
+        .iter()
+        .find(|path| {
+            let parent = path.parent().and_then(|p| p.file_name()).and_then(|p| p.to_str());
+            source_file.is_some() && parent == source_file
+        })
+        .or_else(|| matches.first())
+        .cloned()
+}
+
+/// Reads bytecode and ABI from a Foundry (`bytecode.object`) or Hardhat (`bytecode`) artifact
+fn read_json(path: &Path) -> Result<Artifact> {
+    let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let artifact: Value = serde_json::from_slice(&std::fs::read(path)?)?;
+
+    let bytecode = match artifact.get("bytecode") {
+        Some(Value::String(code)) => Some(code.as_str()),
+        Some(code) => code.get("object").and_then(|o| o.as_str()),
+        None => None,
+    }
+    .unwrap_or_default();
+    let bytecode = decode_bytecode(name, bytecode)?;
+    if bytecode.is_empty() {
+        return Err(CocoError::Validation(format!(
+            "{} has no creation bytecode; interfaces and abstract contracts can't be deployed",
+            name
+        )));
+    }
+
+    Ok(Artifact {
+        bytecode,
+        abi: artifact.get("abi").map(|abi| abi.to_string()),
+        program_keypair: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_foundry_hardhat_and_anchor_artifacts() {
+        let project = std::env::temp_dir().join(format!("coco-artifacts-{}", uuid::Uuid::new_v4()));
+        let foundry = project.join("out").join("Token.sol");
+        std::fs::create_dir_all(&foundry).unwrap();
+        std::fs::write(
+            foundry.join("Token.json"),
+            r#"{"abi": [], "bytecode": {"object": "0x6080", "sourceMap": ""}}"#,
+        )
+        .unwrap();
+        let hardhat = project.join("artifacts").join("contracts").join("Vault.sol");
+        std::fs::create_dir_all(&hardhat).unwrap();
+        std::fs::write(hardhat.join("Vault.json"), r#"{"abi": [], "bytecode": "0x6001"}"#).unwrap();
+        std::fs::write(hardhat.join("IVault.json"), r#"{"abi": [], "bytecode": "0x"}"#).unwrap();
+        let anchor = project.join("target").join("deploy");
+        std::fs::create_dir_all(&anchor).unwrap();
+        std::fs::write(anchor.join("token_faucet.so"), [0x7f, b'E', b'L', b'F']).unwrap();
+        std::fs::write(anchor.join("token_faucet-keypair.json"), "[1,2,3]").unwrap();
+
+        let token = find(&project, "Token", Some(Path::new("src/Token.sol"))).unwrap();
+        assert_eq!(token.bytecode, vec![0x60, 0x80]);
+        assert_eq!(token.abi.as_deref(), Some("[]"));
+        assert_eq!(find(&project, "Vault", None).unwrap().bytecode, vec![0x60, 0x01]);
+        assert!(matches!(find(&project, "IVault", None), Err(CocoError::Validation(_))));
+        let program = find(&project, "token-faucet", Some(Path::new("target/idl/token_faucet.json"))).unwrap();
+        assert_eq!(program.bytecode, b"\x7fELF");
+        assert_eq!(program.program_keypair.as_deref(), Some("[1,2,3]"));
+        assert_eq!(token.program_keypair, None);
+        assert!(matches!(find(&project, "Missing", None), Err(CocoError::NotFound(_))));
+
+        std::fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn test_discovers_evm_build_output() {
+        let project = std::env::temp_dir().join(format!("coco-discovery-{}", uuid::Uuid::new_v4()));
+        let write = |path: &str, contents: &str| {
+            let path = project.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+        write(
+            "out/Token.sol/Token.json",
+            r#"{"abi": [], "bytecode": {"object": "0x6080"},
+                "metadata": {"compiler": {"version": "0.8.24+commit.e11b9ed9"},
+                             "settings": {"compilationTarget": {"src/Token.sol": "Token"}}}}"#,
+        );
+        write(
+            "out/IERC20.sol/IERC20.json",
+            r#"{"abi": [], "bytecode": {"object": "0x"},
+                "metadata": {"settings": {"compilationTarget": {"lib/openzeppelin-contracts/contracts/token/ERC20/IERC20.sol": "IERC20"}}}}"#,
+        );
+        write(
+            "out/Token.t.sol/TokenTest.json",
+            r#"{"abi": [], "bytecode": {"object": "0x6080"},
+                "metadata": {"settings": {"compilationTarget": {"test/Token.t.sol": "TokenTest"}}}}"#,
+        );
+        write(
+            "artifacts/contracts/Vault.sol/Vault.json",
+            r#"{"contractName": "Vault", "sourceName": "contracts/Vault.sol", "abi": [], "bytecode": "0x6001"}"#,
+        );
+        write(
+            "artifacts/contracts/Vault.sol/Vault.dbg.json",
+            r#"{"buildInfo": "../../build-info/abc.json"}"#,
+        );
+        write("artifacts/build-info/abc.json", r#"{"solcVersion": "0.8.20", "output": {}}"#);
+
+        let mut discovered = discover_evm(&project);
+        discovered.sort_by(|a, b| a.name.cmp(&b.name));
+        let summary: Vec<_> = discovered
+            .iter()
+            .map(|c| (c.name.as_str(), c.path.as_str(), c.compiler_version.as_deref(), c.is_interface, c.is_dependency))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("IERC20", "lib/openzeppelin-contracts/contracts/token/ERC20/IERC20.sol", None, true, true),
+                ("Token", "src/Token.sol", Some("0.8.24+commit.e11b9ed9"), false, false),
+                ("Vault", "contracts/Vault.sol", Some("0.8.20"), false, false),
+            ]
+        );
+        assert_eq!(discovered[1].bytecode.as_deref(), Some("0x6080"));
+        assert_eq!(discovered[1].abi.as_deref(), Some("[]"));
+
+        std::fs::remove_dir_all(&project).unwrap();
+    }
+}
@@ -0,0 +1,17 @@
+use tauri::State;
+
+use crate::types::{SecretAccessEntry, SecretAccessFilter};
+use crate::AppState;
+
+/// Lists private-key and env-var reveals, newest first
+#[tauri::command]
+pub async fn list_secret_access_log(
+    filter: Option<SecretAccessFilter>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SecretAccessEntry>, String> {
+    state
+        .audit_service
+        .list(&filter.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
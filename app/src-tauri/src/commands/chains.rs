@@ -43,6 +43,7 @@ pub async fn create_chain(
         network_type: string_to_network_type(&request.network_type),
         is_custom: request.is_custom.unwrap_or(true),
         icon_id: request.icon_id,
+        network_id: request.network_id,
     };
 
     state
@@ -69,6 +70,7 @@ pub struct CreateChainRequest {
     pub network_type: String,
     pub is_custom: Option<bool>,
     pub icon_id: Option<String>,
+    pub network_id: Option<String>,
 }
 
 fn string_to_network_type(s: &str) -> NetworkType {
@@ -116,6 +118,7 @@ pub async fn update_chain(
         network_type: existing.network_type,
         is_custom: existing.is_custom,
         icon_id: existing.icon_id,
+        network_id: existing.network_id,
     };
 
     state
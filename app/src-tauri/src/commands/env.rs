@@ -20,11 +20,21 @@ pub async fn list_env_vars_with_values(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<std::collections::HashMap<String, String>, String> {
-    state
+    let values = state
         .env_service
         .get_all_env_values(&workspace_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let mut keys: Vec<String> = values.keys().cloned().collect();
+    keys.sort();
+    state
+        .audit_service
+        .record_env_reveal(&workspace_id, &keys, "list_env_vars_with_values")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(values)
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -42,11 +52,19 @@ pub async fn get_env_value(
     key: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    state
+    let value = state
         .env_service
         .get_env_value(&workspace_id, &key)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    state
+        .audit_service
+        .record_env_reveal(&workspace_id, &[key], "get_env_value")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(value)
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -10,6 +10,8 @@ pub mod preferences;
 pub mod contract_docs;
 pub mod workflows;
 pub mod adapters;
+pub mod vault;
+pub mod audit;
 
 pub use chains::*;
 pub use wallets::*;
@@ -22,4 +24,6 @@ pub use conversations::*;
 pub use preferences::*;
 pub use contract_docs::*;
 pub use workflows::*;
+pub use vault::*;
+pub use audit::*;
 
@@ -0,0 +1,52 @@
+use tauri::State;
+
+use crate::types::{KeyRotationReport, VaultStatus};
+use crate::AppState;
+
+#[tauri::command]
+pub async fn get_vault_status(state: State<'_, AppState>) -> Result<VaultStatus, String> {
+    state
+        .vault_service
+        .status()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets the vault passphrase and re-encrypts existing secrets under it
+#[tauri::command]
+pub async fn setup_vault(passphrase: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .vault_service
+        .setup(&passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlock_vault(passphrase: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .vault_service
+        .unlock(&passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn lock_vault(state: State<'_, AppState>) -> Result<(), String> {
+    state.vault_service.lock();
+    Ok(())
+}
+
+/// Changes the vault passphrase and re-encrypts every stored secret in one transaction
+#[tauri::command(rename_all = "camelCase")]
+pub async fn rotate_encryption_key(
+    old_passphrase: String,
+    new_passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<KeyRotationReport, String> {
+    state
+        .vault_service
+        .rotate_encryption_key(&old_passphrase, &new_passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,6 +1,10 @@
+use std::path::Path;
 use tauri::State;
 
-use crate::types::{Ecosystem, Wallet, WalletType, WalletWithChain};
+use crate::adapters::derivation;
+use crate::types::{
+    Ecosystem, HdWallet, Wallet, WalletBalanceSnapshot, WalletBundleImport, WalletType, WalletWithChain,
+};
 use crate::AppState;
 
 #[tauri::command(rename_all = "camelCase")]
@@ -77,7 +81,7 @@ pub async fn import_wallet(
             &address,
             private_key.as_deref(),
             wallet_type,
-            ecosystem.unwrap_or(Ecosystem::Evm),
+            ecosystem,
         )
         .await
         .map_err(|e| e.to_string())
@@ -88,10 +92,65 @@ pub async fn get_wallet_private_key(
     wallet_id: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    state
+    let private_key = state
         .wallet_service
         .get_wallet_private_key(&wallet_id)
         .await
+        .map_err(|e| e.to_string())?;
+
+    // Only hand the key out once the reveal is on record
+    state
+        .audit_service
+        .record_wallet_key_reveal(&wallet_id, "get_wallet_private_key")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(private_key)
+}
+
+/// Signs a UTF-8 message with the wallet's key or connected signer, returning hex
+#[tauri::command(rename_all = "camelCase")]
+pub async fn sign_message(
+    wallet_id: String,
+    message: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let signature = state
+        .wallet_service
+        .sign_message(&wallet_id, message.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(format!("0x{}", hex::encode(signature)))
+}
+
+/// Writes a chain's wallets, or the selected ones, to a passphrase-encrypted keystore file
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_wallets(
+    path: String,
+    passphrase: String,
+    chain_id: Option<String>,
+    wallet_ids: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    state
+        .wallet_service
+        .export_wallets(Path::new(&path), &passphrase, chain_id.as_deref(), wallet_ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restores wallets from a file written by `export_wallets`, skipping known addresses
+#[tauri::command(rename_all = "camelCase")]
+pub async fn import_wallet_bundle(
+    path: String,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<WalletBundleImport, String> {
+    state
+        .wallet_service
+        .import_wallet_bundle(Path::new(&path), &passphrase)
+        .await
         .map_err(|e| e.to_string())
 }
 
@@ -120,3 +179,60 @@ pub async fn refresh_balance(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Balance snapshots recorded by refresh_balance, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_balance_history(
+    wallet_id: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WalletBalanceSnapshot>, String> {
+    state
+        .wallet_service
+        .get_balance_history(&wallet_id, limit)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Generates a fresh BIP-39 phrase (12 words unless told otherwise)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn generate_mnemonic(word_count: Option<usize>) -> Result<String, String> {
+    derivation::generate_mnemonic(word_count.unwrap_or(12)).map_err(|e| e.to_string())
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn import_hd_wallet(
+    name: String,
+    mnemonic: String,
+    state: State<'_, AppState>,
+) -> Result<HdWallet, String> {
+    state
+        .wallet_service
+        .import_hd_wallet(&name, &mnemonic)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_hd_wallets(state: State<'_, AppState>) -> Result<Vec<HdWallet>, String> {
+    state
+        .wallet_service
+        .list_hd_wallets()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Derives the HD wallet's next account on a chain and saves it as a wallet
+#[tauri::command(rename_all = "camelCase")]
+pub async fn derive_next_wallet(
+    hd_wallet_id: String,
+    chain_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Wallet, String> {
+    state
+        .wallet_service
+        .derive_next_wallet(&hd_wallet_id, &chain_id, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
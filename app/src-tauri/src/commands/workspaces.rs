@@ -1,6 +1,7 @@
-use tauri::State;
+use tauri::{Emitter, State, Window};
+use tokio::sync::mpsc;
 
-use crate::types::{AIExplanation, Contract, ContractWithChain, Transaction, TransactionRun, Workspace};
+use crate::types::{AIExplanation, Contract, ContractDeployment, ContractSync, ContractWithChain, DeploymentResult, Transaction, TransactionRun, Workspace};
 use crate::AppState;
 
 #[tauri::command]
@@ -155,6 +156,96 @@ pub async fn update_contract(
         .map_err(|e| e.to_string())
 }
 
+/// Deploys a contract from its stored bytecode or build artifact with a workspace wallet
+#[tauri::command]
+pub async fn deploy_contract(
+    contract_id: String,
+    wallet_id: String,
+    constructor_args: serde_json::Value,
+    state: State<'_, AppState>,
+) -> Result<DeploymentResult, String> {
+    state
+        .workspace_service
+        .deploy_contract(&contract_id, &wallet_id, constructor_args)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_contract_deployments(
+    contract_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ContractDeployment>, String> {
+    state
+        .workspace_service
+        .list_contract_deployments(&contract_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Makes a deployment the one transactions on its network target
+#[tauri::command]
+pub async fn activate_deployment(
+    deployment_id: String,
+    state: State<'_, AppState>,
+) -> Result<ContractDeployment, String> {
+    state
+        .workspace_service
+        .activate_deployment(&deployment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn sync_contracts(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<ContractSync, String> {
+    state
+        .workspace_service
+        .sync_contracts(&workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-syncs the workspace's contracts after every build, emitting `contracts-synced`
+#[tauri::command]
+pub async fn watch_workspace(
+    workspace_id: String,
+    window: Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (tx, mut rx) = mpsc::channel::<ContractSync>(16);
+
+    tokio::spawn(async move {
+        while let Some(sync) = rx.recv().await {
+            let _ = window.emit("contracts-synced", sync);
+        }
+    });
+
+    state
+        .watcher_service
+        .watch(&workspace_id, tx)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unwatch_workspace(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.watcher_service.unwatch(&workspace_id).await)
+}
+
+#[tauri::command]
+pub async fn is_watching_workspace(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    Ok(state.watcher_service.is_watching(&workspace_id).await)
+}
+
 #[tauri::command]
 pub async fn list_transactions(
     workspace_id: String,
@@ -201,6 +292,7 @@ pub async fn update_transaction(
     contract_id: Option<String>,
     function_name: Option<String>,
     args: Option<String>,
+    overrides: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Transaction, String> {
     state
@@ -211,6 +303,7 @@ pub async fn update_transaction(
             contract_id.as_deref(),
             function_name.as_deref(),
             args.as_deref(),
+            overrides.as_deref(),
         )
         .await
         .map_err(|e| e.to_string())
@@ -230,6 +323,63 @@ pub async fn execute_transaction(
         .map_err(|e| e.to_string())
 }
 
+/// Sends native currency between two wallets on the workspace's chain
+#[tauri::command]
+pub async fn transfer_native(
+    workspace_id: String,
+    from_wallet_id: String,
+    to_wallet_id: String,
+    amount: String,
+    state: State<'_, AppState>,
+) -> Result<TransactionRun, String> {
+    state
+        .workspace_service
+        .transfer_native(&workspace_id, &from_wallet_id, &to_wallet_id, &amount)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-sends a pending run with the same nonce and a higher fee
+#[tauri::command]
+pub async fn speed_up_transaction(
+    run_id: String,
+    state: State<'_, AppState>,
+) -> Result<TransactionRun, String> {
+    state
+        .workspace_service
+        .speed_up_transaction(&run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces a pending run with a zero-value self-transfer at the same nonce
+#[tauri::command]
+pub async fn cancel_transaction(
+    run_id: String,
+    state: State<'_, AppState>,
+) -> Result<TransactionRun, String> {
+    state
+        .workspace_service
+        .cancel_transaction(&run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tops every wallet on the workspace's chain up to a target balance from the funder
+#[tauri::command]
+pub async fn fund_wallets(
+    workspace_id: String,
+    funder_wallet_id: String,
+    target_balance: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TransactionRun>, String> {
+    state
+        .workspace_service
+        .fund_wallets(&workspace_id, &funder_wallet_id, &target_balance)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn save_transaction_run(
     run: TransactionRun,
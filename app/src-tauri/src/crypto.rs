@@ -0,0 +1,173 @@
+//! Authenticated encryption for secrets stored in the database.
+//!
+//! Every ciphertext is wrapped in a versioned envelope:
+//!
+//! ```text
+//! | version (1) | algorithm (1) | key id (4) | nonce (12) | ciphertext + tag |
+//! ```
+//!
+//! Blobs written before the envelope existed are a bare `nonce || ciphertext`
+//! under AES-256-GCM; they are still accepted by [`decrypt`].
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::error::{CocoError, Result};
+
+/// Envelope format version written by [`encrypt`]
+pub const ENVELOPE_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 6;
+const NONCE_LEN: usize = 12;
+
+/// Cipher used for an envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Algorithm {
+    Aes256Gcm = 1,
+}
+
+impl Algorithm {
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Algorithm::Aes256Gcm),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed envelope header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeHeader {
+    pub version: u8,
+    pub algorithm: Algorithm,
+    pub key_id: u32,
+}
+
+/// Short fingerprint of a key, recorded in each envelope so a record
+/// encrypted under a different key is reported as such
+pub fn key_id(key: &[u8; 32]) -> u32 {
+    let digest = Sha256::new()
+        .chain_update(b"coco-key-id")
+        .chain_update(key)
+        .finalize();
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Reads the envelope header, or `None` for a legacy un-versioned blob
+pub fn header(blob: &[u8]) -> Option<EnvelopeHeader> {
+    if blob.len() < HEADER_LEN + NONCE_LEN || blob[0] != ENVELOPE_VERSION {
+        return None;
+    }
+
+    Some(EnvelopeHeader {
+        version: blob[0],
+        algorithm: Algorithm::from_id(blob[1])?,
+        key_id: u32::from_be_bytes([blob[2], blob[3], blob[4], blob[5]]),
+    })
+}
+
+/// Encrypts `plaintext` into a current-version envelope
+pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let nonce: [u8; NONCE_LEN] = rand::thread_rng().gen();
+    let ciphertext = aes_gcm_encrypt(key, &nonce, plaintext)?;
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + NONCE_LEN + ciphertext.len());
+    blob.push(ENVELOPE_VERSION);
+    blob.push(Algorithm::Aes256Gcm as u8);
+    blob.extend_from_slice(&key_id(key).to_be_bytes());
+    blob.extend_from_slice(&nonce);
+    blob.extend(ciphertext);
+    Ok(blob)
+}
+
+/// Decrypts an envelope, or a legacy `nonce || ciphertext` blob
+pub fn decrypt(key: &[u8; 32], blob: &[u8]) -> Result<Vec<u8>> {
+    if let Some(header) = header(blob) {
+        let body = &blob[HEADER_LEN..];
+        let result = match header.algorithm {
+            Algorithm::Aes256Gcm => aes_gcm_decrypt(key, &body[..NONCE_LEN], &body[NONCE_LEN..]),
+        };
+
+        match result {
+            Ok(plaintext) => return Ok(plaintext),
+            // A legacy nonce can start with the version byte by chance, so only
+            // report a key mismatch once the legacy layout has been ruled out
+            Err(_) if header.key_id != key_id(key) => {
+                return decrypt_legacy(key, blob).map_err(|_| {
+                    CocoError::Crypto(format!(
+                        "Secret was encrypted with a different key (key id {:08x})",
+                        header.key_id
+                    ))
+                });
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    decrypt_legacy(key, blob)
+}
+
+fn decrypt_legacy(key: &[u8; 32], blob: &[u8]) -> Result<Vec<u8>> {
+    if blob.len() < NONCE_LEN {
+        return Err(CocoError::Crypto("Invalid encrypted data".to_string()));
+    }
+    let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+    aes_gcm_decrypt(key, nonce, ciphertext)
+}
+
+fn aes_gcm_encrypt(key: &[u8; 32], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    Aes256Gcm::new_from_slice(key)
+        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?
+        .encrypt(Nonce::from_slice(nonce), plaintext)
+        .map_err(|e| CocoError::Crypto(format!("Encryption failed: {}", e)))
+}
+
+fn aes_gcm_decrypt(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    Aes256Gcm::new_from_slice(key)
+        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|e| CocoError::Crypto(format!("Decryption failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8; 32] = &[42u8; 32];
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let blob = encrypt(KEY, b"secret").unwrap();
+        let header = header(&blob).unwrap();
+
+        assert_eq!(header.version, ENVELOPE_VERSION);
+        assert_eq!(header.algorithm, Algorithm::Aes256Gcm);
+        assert_eq!(header.key_id, key_id(KEY));
+        assert_eq!(decrypt(KEY, &blob).unwrap(), b"secret");
+    }
+
+    #[test]
+    fn test_decrypts_legacy_blob() {
+        // Legacy layout: nonce || ciphertext, with a nonce that can't look like a header
+        let nonce = [0u8; NONCE_LEN];
+        let mut blob = nonce.to_vec();
+        blob.extend(aes_gcm_encrypt(KEY, &nonce, b"0xabc").unwrap());
+
+        assert!(header(&blob).is_none());
+        assert_eq!(decrypt(KEY, &blob).unwrap(), b"0xabc");
+    }
+
+    #[test]
+    fn test_reports_key_mismatch() {
+        let blob = encrypt(KEY, b"secret").unwrap();
+        let other = [7u8; 32];
+
+        let err = decrypt(&other, &blob).unwrap_err().to_string();
+        assert!(err.contains("different key"), "{}", err);
+    }
+}
@@ -25,6 +25,13 @@ pub async fn init_db(app_data_dir: PathBuf) -> Result<DbPool, sqlx::Error> {
     Ok(pool)
 }
 
+/// Fresh migrated database in a temporary directory, for service tests
+#[cfg(test)]
+pub async fn test_db() -> DbPool {
+    let dir = std::env::temp_dir().join(format!("coco-db-{}", uuid::Uuid::new_v4()));
+    init_db(dir).await.expect("test database")
+}
+
 /// Run database migrations
 async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
     // IMPORTANT: Create tables FIRST before running migrations
@@ -427,9 +434,259 @@ async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
         }
     }
 
+    // Migration: Record each run's signer, nonce and request so it can be sped up or cancelled
+    let replacement_columns = [
+        ("wallet_id", "TEXT"),
+        ("nonce", "INTEGER"),
+        ("request", "TEXT"),
+        ("replaces_run_id", "TEXT"),
+        ("replacement_kind", "TEXT"),
+    ];
+
+    for (col_name, col_type) in replacement_columns {
+        let columns: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT name FROM pragma_table_info('transaction_runs') WHERE name = '{}'",
+            col_name
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        if columns.is_empty() {
+            sqlx::query(&format!(
+                "ALTER TABLE transaction_runs ADD COLUMN {} {}",
+                col_name, col_type
+            ))
+            .execute(pool)
+            .await
+            .ok();
+        }
+    }
+
+    // Migration: Record the block each run landed in and its status history, for reorg tracking
+    let confirmation_columns = [("block_hash", "TEXT"), ("history", "TEXT")];
+
+    for (col_name, col_type) in confirmation_columns {
+        let columns: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT name FROM pragma_table_info('transaction_runs') WHERE name = '{}'",
+            col_name
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        if columns.is_empty() {
+            sqlx::query(&format!(
+                "ALTER TABLE transaction_runs ADD COLUMN {} {}",
+                col_name, col_type
+            ))
+            .execute(pool)
+            .await
+            .ok();
+        }
+    }
+
+    // Migration: Blocks a run must be buried under before it is final
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info('networks') WHERE name = 'confirmation_depth'"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if columns.is_empty() {
+        sqlx::query("ALTER TABLE networks ADD COLUMN confirmation_depth INTEGER NOT NULL DEFAULT 1")
+            .execute(pool)
+            .await
+            .ok();
+    }
+
+    // Migration: Link chains to the network whose settings (e.g. confirmation depth) they use
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info('chains') WHERE name = 'network_id'"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if columns.is_empty() {
+        sqlx::query("ALTER TABLE chains ADD COLUMN network_id TEXT")
+            .execute(pool)
+            .await
+            .ok();
+    }
+    link_chains_to_networks(pool).await?;
+
+    // The transaction tracker polls every pending run
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_transaction_runs_status ON transaction_runs(status)")
+        .execute(pool)
+        .await?;
+
+    // Migration: Link wallets to the HD wallet and path they were derived from
+    let wallet_columns = [
+        ("hd_wallet_id", "TEXT"),
+        ("derivation_path", "TEXT"),
+        ("derivation_index", "INTEGER"),
+    ];
+
+    for (col_name, col_type) in wallet_columns {
+        let columns: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT name FROM pragma_table_info('wallets') WHERE name = '{}'",
+            col_name
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        if columns.is_empty() {
+            sqlx::query(&format!(
+                "ALTER TABLE wallets ADD COLUMN {} {}",
+                col_name, col_type
+            ))
+            .execute(pool)
+            .await
+            .ok();
+        }
+    }
+
+    // Each HD index can only be derived once per chain
+    sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_wallets_hd_index ON wallets(hd_wallet_id, chain_id, derivation_index) WHERE hd_wallet_id IS NOT NULL",
+    )
+    .execute(pool)
+    .await?;
+
+    // Migration: Gas, fee and nonce settings pinned on saved transactions
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info('transactions') WHERE name = 'overrides'"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if columns.is_empty() {
+        sqlx::query("ALTER TABLE transactions ADD COLUMN overrides TEXT")
+            .execute(pool)
+            .await
+            .ok();
+    }
+
+    // Migration: Decoded revert reason of failed runs
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info('transaction_runs') WHERE name = 'revert'"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if columns.is_empty() {
+        sqlx::query("ALTER TABLE transaction_runs ADD COLUMN revert TEXT")
+            .execute(pool)
+            .await
+            .ok();
+    }
+
+    // Migration: Compiler version and interface/dependency flags from build artifacts
+    let artifact_columns = [
+        ("compiler_version", "TEXT"),
+        ("is_interface", "INTEGER NOT NULL DEFAULT 0"),
+        ("is_dependency", "INTEGER NOT NULL DEFAULT 0"),
+    ];
+
+    for (col_name, col_type) in artifact_columns {
+        let columns: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT name FROM pragma_table_info('contracts') WHERE name = '{}'",
+            col_name
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        if columns.is_empty() {
+            sqlx::query(&format!(
+                "ALTER TABLE contracts ADD COLUMN {} {}",
+                col_name, col_type
+            ))
+            .execute(pool)
+            .await
+            .ok();
+        }
+    }
+
+    // Migration: Transactions whose function disappeared from a re-synced ABI
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info('transactions') WHERE name = 'function_missing'"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if columns.is_empty() {
+        sqlx::query("ALTER TABLE transactions ADD COLUMN function_missing INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .ok();
+    }
+
+    // Migration: Register addresses entered before the deployment registry as active deployments
+    backfill_contract_deployments(pool).await?;
+
+    Ok(())
+}
+
+/// Adds an active deployment for every contract address entered before the
+/// deployment registry, with the same id and timestamp formats new rows use
+async fn backfill_contract_deployments(pool: &DbPool) -> Result<(), sqlx::Error> {
+    let contracts: Vec<(String, String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT c.id, w.chain_id, c.deployed_address, c.created_at
+        FROM contracts c
+        JOIN workspaces w ON w.id = c.workspace_id
+        WHERE c.deployed_address IS NOT NULL AND c.deployed_address != ''
+          AND NOT EXISTS (SELECT 1 FROM contract_deployments d WHERE d.contract_id = c.id)
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (contract_id, chain_id, address, created_at) in contracts {
+        sqlx::query(
+            r#"
+            INSERT INTO contract_deployments (id, contract_id, chain_id, address, is_active, created_at)
+            VALUES (?, ?, ?, ?, 1, ?)
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&contract_id)
+        .bind(&chain_id)
+        .bind(&address)
+        .bind(rfc3339_timestamp(&created_at))
+        .execute(pool)
+        .await?;
+    }
+
+    // Rows from an earlier backfill got random hex ids and SQLite timestamps
+    let legacy: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, created_at FROM contract_deployments WHERE id NOT LIKE '%-%' OR created_at NOT LIKE '%T%'",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (id, created_at) in legacy {
+        let new_id = if id.contains('-') { id.clone() } else { uuid::Uuid::new_v4().to_string() };
+        sqlx::query("UPDATE contract_deployments SET id = ?, created_at = ? WHERE id = ?")
+            .bind(new_id)
+            .bind(rfc3339_timestamp(&created_at))
+            .bind(&id)
+            .execute(pool)
+            .await?;
+    }
+
     Ok(())
 }
 
+/// Converts a stored timestamp to rfc3339. Rows written by SQLite's
+/// `datetime('now')` default are UTC without an offset.
+fn rfc3339_timestamp(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339())
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").map(|t| t.and_utc().to_rfc3339())
+        })
+        .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339())
+}
+
 /// Migrate old chains table to new blockchains + networks structure
 async fn migrate_chains_to_blockchains(pool: &DbPool) -> Result<(), sqlx::Error> {
     // Check if migration is already done (blockchains table has data)
@@ -545,6 +802,18 @@ async fn migrate_chains_to_blockchains(pool: &DbPool) -> Result<(), sqlx::Error>
     Ok(())
 }
 
+/// Points chains that were copied into networks at their network row. Chains
+/// created since then carry their own network_id.
+async fn link_chains_to_networks(pool: &DbPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE chains SET network_id = id WHERE network_id IS NULL AND id IN (SELECT id FROM networks)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 /// Seed default blockchains and networks for fresh installs
 async fn seed_blockchains_and_networks(pool: &DbPool) -> Result<(), sqlx::Error> {
     // Seed blockchains
@@ -651,10 +920,21 @@ async fn create_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
             public_key TEXT NOT NULL,
             encrypted_private_key BLOB,
             wallet_type TEXT NOT NULL DEFAULT 'local',
+            hd_wallet_id TEXT,
+            derivation_path TEXT,
+            derivation_index INTEGER,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             FOREIGN KEY (chain_id) REFERENCES chains(id) ON DELETE CASCADE
         );
 
+        -- HD wallets (encrypted BIP-39 mnemonic that wallets are derived from)
+        CREATE TABLE IF NOT EXISTS hd_wallets (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            encrypted_mnemonic BLOB NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
         -- Workspaces table
         CREATE TABLE IF NOT EXISTS workspaces (
             id TEXT PRIMARY KEY,
@@ -689,6 +969,7 @@ async fn create_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
             function_name TEXT,
             args TEXT,
             value TEXT,
+            overrides TEXT,
             status TEXT NOT NULL DEFAULT 'draft',
             tx_hash TEXT,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
@@ -735,6 +1016,19 @@ async fn create_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
             FOREIGN KEY (run_id) REFERENCES runs(id) ON DELETE CASCADE
         );
 
+        -- Wallet balance snapshots (one row per refresh)
+        CREATE TABLE IF NOT EXISTS wallet_balances (
+            id TEXT PRIMARY KEY,
+            wallet_id TEXT NOT NULL,
+            chain_id TEXT NOT NULL,
+            native TEXT NOT NULL,
+            native_decimals INTEGER NOT NULL,
+            native_symbol TEXT NOT NULL,
+            tokens TEXT,
+            recorded_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE CASCADE
+        );
+
         -- Create indexes for legacy tables
         CREATE INDEX IF NOT EXISTS idx_wallets_chain_id ON wallets(chain_id);
         CREATE INDEX IF NOT EXISTS idx_workspaces_chain_id ON workspaces(chain_id);
@@ -743,6 +1037,7 @@ async fn create_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
         CREATE INDEX IF NOT EXISTS idx_transaction_runs_transaction_id ON transaction_runs(transaction_id);
         CREATE INDEX IF NOT EXISTS idx_runs_workspace_id ON runs(workspace_id);
         CREATE INDEX IF NOT EXISTS idx_run_logs_run_id ON run_logs(run_id);
+        CREATE INDEX IF NOT EXISTS idx_wallet_balances_wallet_id ON wallet_balances(wallet_id, recorded_at);
         "#,
     )
     .execute(pool)
@@ -782,6 +1077,7 @@ async fn create_v003_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
             currency_symbol TEXT NOT NULL,
             currency_decimals INTEGER NOT NULL DEFAULT 18,
             is_default INTEGER NOT NULL DEFAULT 0,
+            confirmation_depth INTEGER NOT NULL DEFAULT 1,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             FOREIGN KEY (blockchain_id) REFERENCES blockchains(id) ON DELETE CASCADE
         );
@@ -949,6 +1245,83 @@ async fn create_v003_tables(pool: &DbPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Secret access log (append-only: updates and deletes are rejected)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS secret_access_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            secret_kind TEXT NOT NULL,
+            wallet_id TEXT,
+            wallet_address TEXT,
+            env_key TEXT,
+            workspace_id TEXT,
+            command TEXT NOT NULL,
+            accessed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_secret_access_log_wallet_id ON secret_access_log(wallet_id);
+        CREATE INDEX IF NOT EXISTS idx_secret_access_log_workspace_id ON secret_access_log(workspace_id);
+
+        CREATE TRIGGER IF NOT EXISTS secret_access_log_no_update
+        BEFORE UPDATE ON secret_access_log
+        BEGIN
+            SELECT RAISE(ABORT, 'secret_access_log is append-only');
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS secret_access_log_no_delete
+        BEFORE DELETE ON secret_access_log
+        BEGIN
+            SELECT RAISE(ABORT, 'secret_access_log is append-only');
+        END;
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Vault table (single row: passphrase salt, KDF costs and a verification blob)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS vault (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            salt BLOB NOT NULL,
+            verifier BLOB NOT NULL,
+            m_cost INTEGER NOT NULL,
+            t_cost INTEGER NOT NULL,
+            p_cost INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Contract deployments table (a contract's addresses per network, one active on each)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS contract_deployments (
+            id TEXT PRIMARY KEY,
+            contract_id TEXT NOT NULL,
+            chain_id TEXT NOT NULL,
+            address TEXT NOT NULL,
+            deployer_wallet_id TEXT,
+            tx_hash TEXT,
+            block_number INTEGER,
+            bytecode_hash TEXT,
+            is_active INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_contract_deployments_contract ON contract_deployments(contract_id, chain_id);
+        -- At most one active deployment per contract and network, and one per deploy transaction
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_contract_deployments_active
+            ON contract_deployments(contract_id, chain_id) WHERE is_active = 1;
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_contract_deployments_tx ON contract_deployments(contract_id, tx_hash);
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -998,6 +1371,7 @@ async fn seed_default_data(pool: &DbPool) -> Result<(), sqlx::Error> {
         .execute(pool)
         .await?;
     }
+    link_chains_to_networks(pool).await?;
 
     // Seed some dummy wallets for the Sepolia testnet
     let dummy_wallets = vec![
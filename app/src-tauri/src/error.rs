@@ -26,6 +26,11 @@ pub enum CocoError {
 
     #[error("Crypto error: {0}")]
     Crypto(String),
+
+    /// No master key is in memory, either because the vault was never set up or
+    /// because it is locked. The UI checks `get_vault_status` to offer the right step.
+    #[error("Vault locked: {0}")]
+    VaultLocked(String),
 }
 
 impl From<std::io::Error> for CocoError {
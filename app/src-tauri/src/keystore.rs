@@ -0,0 +1,282 @@
+//! Passphrase-encrypted wallet bundles for moving wallets between machines.
+//!
+//! Each key is sealed in an [Ethereum V3 keystore](https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/):
+//! scrypt derives a 32-byte key from the passphrase, AES-128-CTR encrypts the
+//! secret under its first half, and `keccak256(second half || ciphertext)` is the MAC.
+//!
+//! EVM keystores are standard and can be read by other Ethereum tools. Solana
+//! and Aptos keys use the same `crypto` section over their 32-byte ed25519
+//! secret seed, with `address` holding the chain's native address format.
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use uuid::Uuid;
+
+use crate::error::{CocoError, Result};
+use crate::types::{Ecosystem, WalletType};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Bundle format version written by [`WalletBundle::new`]
+pub const BUNDLE_VERSION: u32 = 1;
+
+const KEYSTORE_VERSION: u32 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const KDF: &str = "scrypt";
+const DKLEN: usize = 32;
+
+/// scrypt cost used by geth and ethers for new keystores
+#[cfg(not(test))]
+const SCRYPT_LOG_N: u8 = 18;
+/// Unoptimized test builds would spend minutes per key at the real cost
+#[cfg(test)]
+const SCRYPT_LOG_N: u8 = 4;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Largest scrypt costs accepted from an imported keystore, so a crafted file
+/// can't make the KDF allocate gigabytes. The combined cost is capped at
+/// n = 2^20, r = 8, p = 1, four times what geth writes.
+const MAX_SCRYPT_N: u64 = 1 << 20;
+const MAX_SCRYPT_R: u32 = 8;
+const MAX_SCRYPT_P: u32 = 4;
+const MAX_SCRYPT_COST: u64 = MAX_SCRYPT_N * MAX_SCRYPT_R as u64;
+
+/// File written by `export_wallets`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub wallets: Vec<BundledWallet>,
+}
+
+impl WalletBundle {
+    pub fn new(wallets: Vec<BundledWallet>) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            wallets,
+        }
+    }
+}
+
+/// One exported wallet. Watch-only and Ledger wallets carry no keystore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledWallet {
+    pub name: String,
+    pub chain_id: String,
+    pub ecosystem: Ecosystem,
+    pub address: String,
+    pub wallet_type: WalletType,
+    pub keystore: Option<Keystore>,
+}
+
+/// Web3 Secret Storage (V3) keystore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub id: String,
+    pub address: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: ScryptParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub dklen: usize,
+    pub n: u64,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+/// Seals a 32-byte secret under a passphrase. EVM addresses are written the
+/// way V3 expects: lowercase hex without `0x`.
+pub fn encrypt_key(
+    secret: &[u8],
+    address: &str,
+    ecosystem: &Ecosystem,
+    passphrase: &str,
+) -> Result<Keystore> {
+    encrypt_key_with_cost(secret, address, ecosystem, passphrase, SCRYPT_LOG_N)
+}
+
+fn encrypt_key_with_cost(
+    secret: &[u8],
+    address: &str,
+    ecosystem: &Ecosystem,
+    passphrase: &str,
+    log_n: u8,
+) -> Result<Keystore> {
+    let mut rng = rand::thread_rng();
+    let salt: [u8; 32] = rng.gen();
+    let iv: [u8; 16] = rng.gen();
+
+    let kdfparams = ScryptParams {
+        dklen: DKLEN,
+        n: 1 << log_n,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        salt: hex::encode(salt),
+    };
+    let derived = derive_key(passphrase, &kdfparams)?;
+
+    let mut ciphertext = secret.to_vec();
+    Aes128Ctr::new(derived[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+
+    let address = match ecosystem {
+        Ecosystem::Evm => address.trim_start_matches("0x").to_lowercase(),
+        Ecosystem::Solana | Ecosystem::Aptos => address.to_string(),
+    };
+
+    Ok(Keystore {
+        version: KEYSTORE_VERSION,
+        id: Uuid::new_v4().to_string(),
+        address,
+        crypto: KeystoreCrypto {
+            cipher: CIPHER.to_string(),
+            cipherparams: CipherParams { iv: hex::encode(iv) },
+            mac: hex::encode(mac(&derived, &ciphertext)),
+            ciphertext: hex::encode(ciphertext),
+            kdf: KDF.to_string(),
+            kdfparams,
+        },
+    })
+}
+
+/// Opens a keystore, returning the secret it holds
+pub fn decrypt_key(keystore: &Keystore, passphrase: &str) -> Result<Vec<u8>> {
+    let crypto = &keystore.crypto;
+    if keystore.version != KEYSTORE_VERSION {
+        return Err(CocoError::Validation(format!(
+            "Unsupported keystore version: {}",
+            keystore.version
+        )));
+    }
+    if crypto.kdf != KDF || crypto.cipher != CIPHER {
+        return Err(CocoError::Validation(format!(
+            "Unsupported keystore encryption: {} with {}",
+            crypto.cipher, crypto.kdf
+        )));
+    }
+
+    let derived = derive_key(passphrase, &crypto.kdfparams)?;
+    let mut ciphertext = decode_hex(&crypto.ciphertext)?;
+    if hex::encode(mac(&derived, &ciphertext)) != crypto.mac.trim_start_matches("0x").to_lowercase() {
+        return Err(CocoError::Validation(format!(
+            "Incorrect passphrase for keystore {}",
+            keystore.address
+        )));
+    }
+
+    let iv: [u8; 16] = decode_hex(&crypto.cipherparams.iv)?
+        .try_into()
+        .map_err(|_| CocoError::Validation("Keystore IV must be 16 bytes".to_string()))?;
+    Aes128Ctr::new(derived[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+    Ok(ciphertext)
+}
+
+fn derive_key(passphrase: &str, params: &ScryptParams) -> Result<[u8; DKLEN]> {
+    if params.dklen != DKLEN || !params.n.is_power_of_two() {
+        return Err(CocoError::Validation("Invalid keystore scrypt parameters".to_string()));
+    }
+    if params.n > MAX_SCRYPT_N
+        || params.r > MAX_SCRYPT_R
+        || params.p > MAX_SCRYPT_P
+        || params.n * params.r as u64 * params.p as u64 > MAX_SCRYPT_COST
+    {
+        return Err(CocoError::Validation(format!(
+            "Keystore scrypt cost is too high (n = {}, r = {}, p = {})",
+            params.n, params.r, params.p
+        )));
+    }
+
+    let scrypt_params = scrypt::Params::new(params.n.trailing_zeros() as u8, params.r, params.p)
+        .map_err(|e| CocoError::Crypto(format!("Invalid scrypt parameters: {}", e)))?;
+
+    let mut key = [0u8; DKLEN];
+    scrypt::scrypt(passphrase.as_bytes(), &decode_hex(&params.salt)?, &scrypt_params, &mut key)
+        .map_err(|e| CocoError::Crypto(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+fn mac(derived: &[u8; DKLEN], ciphertext: &[u8]) -> [u8; 32] {
+    Keccak256::new()
+        .chain_update(&derived[16..])
+        .chain_update(ciphertext)
+        .finalize()
+        .into()
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| CocoError::Validation(format!("Invalid keystore hex: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let secret = [5u8; 32];
+        let keystore = encrypt_key_with_cost(
+            &secret,
+            "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+            &Ecosystem::Evm,
+            "correct horse",
+            4,
+        )
+        .unwrap();
+
+        assert_eq!(keystore.address, "742d35cc6634c0532925a3b844bc454e4438f44e");
+        assert_eq!(decrypt_key(&keystore, "correct horse").unwrap(), secret);
+        assert!(matches!(
+            decrypt_key(&keystore, "wrong horse"),
+            Err(CocoError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_excessive_scrypt_cost() {
+        let mut keystore = encrypt_key_with_cost(&[5u8; 32], "0x01", &Ecosystem::Evm, "correct horse", 4).unwrap();
+
+        for (n, r, p) in [(1u64 << 30, 8, 1), (1 << 10, 1024, 1), (1 << 10, 8, 64), (1 << 20, 8, 4)] {
+            keystore.crypto.kdfparams.n = n;
+            keystore.crypto.kdfparams.r = r;
+            keystore.crypto.kdfparams.p = p;
+            let err = decrypt_key(&keystore, "correct horse").unwrap_err();
+            assert!(matches!(&err, CocoError::Validation(m) if m.contains("too high")), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_evm_keystore_opens_in_ethers() {
+        let local = ethers::signers::LocalWallet::from_bytes(&[7u8; 32]).unwrap();
+        let address = ethers::utils::to_checksum(&ethers::signers::Signer::address(&local), None);
+        let keystore = encrypt_key_with_cost(&[7u8; 32], &address, &Ecosystem::Evm, "correct horse", 4).unwrap();
+
+        let path = std::env::temp_dir().join(format!("coco-keystore-{}.json", keystore.id));
+        std::fs::write(&path, serde_json::to_vec(&keystore).unwrap()).unwrap();
+        let opened = ethers::signers::LocalWallet::decrypt_keystore(&path, "correct horse");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(opened.unwrap().signer().to_bytes().as_slice(), &[7u8; 32]);
+    }
+}
@@ -1,15 +1,20 @@
+pub mod abi;
 pub mod adapters;
+pub mod artifacts;
 pub mod commands;
+pub mod crypto;
 pub mod db;
 pub mod error;
+pub mod keystore;
 pub mod services;
 pub mod types;
 
 use adapters::AdapterRegistry;
 use db::DbPool;
 use services::{
-    BlockchainService, ChainService, ContractDocService, ConversationService, EnvService,
-    PreferenceService, RunService, ScriptService, WalletService, WorkspaceService, WorkflowService,
+    AuditService, BlockchainService, ChainService, ContractDocService, ConversationService, EnvService,
+    PreferenceService, RunService, ScriptService, TrackerService, VaultService, WalletService,
+    WatcherService, WorkspaceService, WorkflowService,
 };
 use std::sync::Arc;
 use tauri::Manager;
@@ -25,6 +30,8 @@ pub struct AppState {
     pub wallet_service: Arc<WalletService>,
     pub workspace_service: Arc<WorkspaceService>,
     pub run_service: Arc<RunService>,
+    pub tracker_service: Arc<TrackerService>,
+    pub watcher_service: Arc<WatcherService>,
     pub adapter_registry: Arc<RwLock<AdapterRegistry>>,
     // v0.0.3 services
     pub blockchain_service: Arc<BlockchainService>,
@@ -34,6 +41,8 @@ pub struct AppState {
     pub preference_service: Arc<PreferenceService>,
     pub contract_doc_service: Arc<ContractDocService>,
     pub workflow_service: Arc<WorkflowService>,
+    pub vault_service: Arc<VaultService>,
+    pub audit_service: Arc<AuditService>,
 }
 
 impl AppState {
@@ -41,16 +50,34 @@ impl AppState {
         // Create adapter registry
         let adapter_registry = Arc::new(RwLock::new(AdapterRegistry::new()));
 
+        // Vault holds the master key every other service encrypts secrets with
+        let vault_service = Arc::new(VaultService::new(db_pool.clone()));
+        let audit_service = Arc::new(AuditService::new(db_pool.clone()));
+
         // Create legacy services with database pool
         let chain_service = Arc::new(ChainService::new(db_pool.clone(), adapter_registry.clone()));
-        let wallet_service = Arc::new(WalletService::new(db_pool.clone(), adapter_registry.clone()));
-        let workspace_service = Arc::new(WorkspaceService::new(db_pool.clone()));
+        let wallet_service = Arc::new(WalletService::new(
+            db_pool.clone(),
+            chain_service.clone(),
+            vault_service.clone(),
+            audit_service.clone(),
+        ));
+        let workspace_service = Arc::new(WorkspaceService::new(
+            db_pool.clone(),
+            chain_service.clone(),
+            wallet_service.clone(),
+        ));
         let run_service = Arc::new(RunService::new(db_pool.clone()));
+        let tracker_service = Arc::new(TrackerService::new(
+            chain_service.clone(),
+            workspace_service.clone(),
+        ));
+        let watcher_service = Arc::new(WatcherService::new(workspace_service.clone()));
 
         // Create v0.0.3 services
         let blockchain_service = Arc::new(BlockchainService::new(db_pool.clone()));
         let script_service = Arc::new(ScriptService::new(db_pool.clone()));
-        let env_service = Arc::new(EnvService::new(db_pool.clone()));
+        let env_service = Arc::new(EnvService::new(db_pool.clone(), vault_service.clone()));
         let conversation_service = Arc::new(ConversationService::new(db_pool.clone()));
         let preference_service = Arc::new(PreferenceService::new(db_pool.clone()));
         let contract_doc_service = Arc::new(ContractDocService::new(db_pool.clone()));
@@ -62,6 +89,8 @@ impl AppState {
             wallet_service,
             workspace_service,
             run_service,
+            tracker_service,
+            watcher_service,
             adapter_registry,
             blockchain_service,
             script_service,
@@ -70,6 +99,8 @@ impl AppState {
             preference_service,
             contract_doc_service,
             workflow_service,
+            vault_service,
+            audit_service,
         }
     }
 }
@@ -101,6 +132,9 @@ pub fn run() {
                 AppState::new(db_pool).await
             });
 
+            // Follow transactions that were still pending when their command returned
+            tauri::async_runtime::spawn(app_state.tracker_service.clone().run());
+
             app.manage(app_state);
 
             // Configure macOS window for transparent title bar
@@ -128,7 +162,15 @@ pub fn run() {
             commands::wallets::import_wallet,
             commands::wallets::delete_wallet,
             commands::wallets::refresh_balance,
+            commands::wallets::get_balance_history,
             commands::wallets::get_wallet_private_key,
+            commands::wallets::sign_message,
+            commands::wallets::export_wallets,
+            commands::wallets::import_wallet_bundle,
+            commands::wallets::generate_mnemonic,
+            commands::wallets::import_hd_wallet,
+            commands::wallets::list_hd_wallets,
+            commands::wallets::derive_next_wallet,
             // Workspace commands
             commands::workspaces::list_workspaces,
             commands::workspaces::get_workspace,
@@ -140,11 +182,22 @@ pub fn run() {
             commands::workspaces::update_contract,
             commands::workspaces::delete_contract,
             commands::workspaces::discover_contracts,
+            commands::workspaces::deploy_contract,
+            commands::workspaces::list_contract_deployments,
+            commands::workspaces::activate_deployment,
+            commands::workspaces::sync_contracts,
+            commands::workspaces::watch_workspace,
+            commands::workspaces::unwatch_workspace,
+            commands::workspaces::is_watching_workspace,
             commands::workspaces::list_transactions,
             commands::workspaces::create_transaction,
             commands::workspaces::update_transaction,
             commands::workspaces::delete_transaction,
             commands::workspaces::execute_transaction,
+            commands::workspaces::transfer_native,
+            commands::workspaces::fund_wallets,
+            commands::workspaces::speed_up_transaction,
+            commands::workspaces::cancel_transaction,
             commands::workspaces::save_transaction_run,
             commands::workspaces::list_transaction_runs,
             commands::workspaces::update_transaction_run_explanation,
@@ -237,6 +290,14 @@ pub fn run() {
             commands::workflows::update_workflow_run_step_logs,
             // v0.0.5: Adapter commands
             commands::adapters::execute_adapter,
+            // Vault commands
+            commands::vault::get_vault_status,
+            commands::vault::setup_vault,
+            commands::vault::unlock_vault,
+            commands::vault::lock_vault,
+            commands::vault::rotate_encryption_key,
+            // Audit commands
+            commands::audit::list_secret_access_log,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,246 @@
+use crate::db::DbPool;
+use crate::error::{CocoError, Result};
+use crate::types::{SecretAccessEntry, SecretAccessFilter, SecretKind};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Append-only record of every plaintext secret handed to the frontend
+pub struct AuditService {
+    db: DbPool,
+}
+
+impl AuditService {
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+
+    /// Records that a wallet's private key was revealed by `command`
+    pub async fn record_wallet_key_reveal(&self, wallet_id: &str, command: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO secret_access_log (secret_kind, wallet_id, wallet_address, command, accessed_at)
+            VALUES (?, ?, (SELECT address FROM wallets WHERE id = ?), ?, ?)
+            "#,
+        )
+        .bind(secret_kind_to_string(&SecretKind::WalletKey))
+        .bind(wallet_id)
+        .bind(wallet_id)
+        .bind(command)
+        .bind(timestamp(&Utc::now()))
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Records that environment variable values were revealed by `command`, one entry per key
+    pub async fn record_env_reveal(
+        &self,
+        workspace_id: &str,
+        keys: &[String],
+        command: &str,
+    ) -> Result<()> {
+        let accessed_at = timestamp(&Utc::now());
+
+        for key in keys {
+            sqlx::query(
+                r#"
+                INSERT INTO secret_access_log (secret_kind, env_key, workspace_id, command, accessed_at)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(secret_kind_to_string(&SecretKind::EnvVar))
+            .bind(key)
+            .bind(workspace_id)
+            .bind(command)
+            .bind(&accessed_at)
+            .execute(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists log entries matching the filter, newest first
+    pub async fn list(&self, filter: &SecretAccessFilter) -> Result<Vec<SecretAccessEntry>> {
+        let mut conditions = Vec::new();
+        let mut bindings: Vec<String> = Vec::new();
+
+        if let Some(kind) = &filter.kind {
+            conditions.push("secret_kind = ?");
+            bindings.push(secret_kind_to_string(kind).to_string());
+        }
+        if let Some(wallet_id) = &filter.wallet_id {
+            conditions.push("wallet_id = ?");
+            bindings.push(wallet_id.clone());
+        }
+        if let Some(workspace_id) = &filter.workspace_id {
+            conditions.push("workspace_id = ?");
+            bindings.push(workspace_id.clone());
+        }
+        if let Some(env_key) = &filter.env_key {
+            conditions.push("env_key = ?");
+            bindings.push(env_key.clone());
+        }
+        if let Some(command) = &filter.command {
+            conditions.push("command = ?");
+            bindings.push(command.clone());
+        }
+        if let Some(since) = &filter.since {
+            conditions.push("accessed_at >= ?");
+            bindings.push(timestamp(since));
+        }
+        if let Some(until) = &filter.until {
+            conditions.push("accessed_at <= ?");
+            bindings.push(timestamp(until));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let query = format!(
+            "SELECT id, secret_kind, wallet_id, wallet_address, env_key, workspace_id, command, accessed_at FROM secret_access_log {} ORDER BY accessed_at DESC, id DESC LIMIT ?",
+            where_clause
+        );
+
+        let mut query_builder = sqlx::query_as::<_, SecretAccessRow>(&query);
+        for binding in &bindings {
+            query_builder = query_builder.bind(binding);
+        }
+
+        let rows = query_builder
+            .bind(filter.limit.map(|l| l as i64).unwrap_or(-1))
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(SecretAccessEntry::from).collect())
+    }
+}
+
+/// `accessed_at` is filtered and sorted as text, so every value written or
+/// compared uses this one fixed-width format, matching the column default
+fn timestamp(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn secret_kind_to_string(kind: &SecretKind) -> &'static str {
+    match kind {
+        SecretKind::WalletKey => "wallet_key",
+        SecretKind::EnvVar => "env_var",
+    }
+}
+
+fn string_to_secret_kind(s: &str) -> SecretKind {
+    match s {
+        "env_var" => SecretKind::EnvVar,
+        _ => SecretKind::WalletKey,
+    }
+}
+
+// Helper struct for SQLx
+#[derive(sqlx::FromRow)]
+struct SecretAccessRow {
+    id: i64,
+    secret_kind: String,
+    wallet_id: Option<String>,
+    wallet_address: Option<String>,
+    env_key: Option<String>,
+    workspace_id: Option<String>,
+    command: String,
+    accessed_at: String,
+}
+
+impl From<SecretAccessRow> for SecretAccessEntry {
+    fn from(row: SecretAccessRow) -> Self {
+        SecretAccessEntry {
+            id: row.id,
+            kind: string_to_secret_kind(&row.secret_kind),
+            wallet_id: row.wallet_id,
+            wallet_address: row.wallet_address,
+            env_key: row.env_key,
+            workspace_id: row.workspace_id,
+            command: row.command,
+            accessed_at: row
+                .accessed_at
+                .parse::<DateTime<Utc>>()
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    async fn setup() -> AuditService {
+        let db = crate::db::test_db().await;
+        sqlx::query(
+            "INSERT INTO wallets (id, chain_id, name, address, public_key) VALUES ('deployer', 'ethereum-sepolia', 'Deployer', '0xabc', '0xpub')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        AuditService::new(db)
+    }
+
+    #[tokio::test]
+    async fn test_log_is_append_only() {
+        let audit = setup().await;
+        audit.record_wallet_key_reveal("deployer", "get_private_key").await.unwrap();
+
+        let update = sqlx::query("UPDATE secret_access_log SET command = 'nothing'")
+            .execute(&audit.db)
+            .await;
+        assert!(update.unwrap_err().to_string().contains("append-only"));
+
+        let delete = sqlx::query("DELETE FROM secret_access_log").execute(&audit.db).await;
+        assert!(delete.unwrap_err().to_string().contains("append-only"));
+        assert_eq!(audit.list(&SecretAccessFilter::default()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_reveal_records_address() {
+        let audit = setup().await;
+        audit.record_wallet_key_reveal("deployer", "export_wallets").await.unwrap();
+
+        let entries = audit.list(&SecretAccessFilter::default()).await.unwrap();
+        assert_eq!(entries[0].kind, SecretKind::WalletKey);
+        assert_eq!(entries[0].wallet_address.as_deref(), Some("0xabc"));
+        assert_eq!(entries[0].command, "export_wallets");
+    }
+
+    #[tokio::test]
+    async fn test_list_filters() {
+        let audit = setup().await;
+        let before = Utc::now() - Duration::seconds(1);
+        audit.record_wallet_key_reveal("deployer", "get_private_key").await.unwrap();
+        audit
+            .record_env_reveal("ws-1", &["RPC_URL".to_string(), "API_KEY".to_string()], "export_env")
+            .await
+            .unwrap();
+        audit.record_env_reveal("ws-2", &["API_KEY".to_string()], "export_env").await.unwrap();
+
+        let count = |filter: SecretAccessFilter| {
+            let audit = &audit;
+            async move { audit.list(&filter).await.unwrap().len() }
+        };
+
+        assert_eq!(count(SecretAccessFilter::default()).await, 4);
+        assert_eq!(count(SecretAccessFilter { kind: Some(SecretKind::EnvVar), ..Default::default() }).await, 3);
+        assert_eq!(count(SecretAccessFilter { wallet_id: Some("deployer".into()), ..Default::default() }).await, 1);
+        assert_eq!(count(SecretAccessFilter { workspace_id: Some("ws-1".into()), ..Default::default() }).await, 2);
+        assert_eq!(count(SecretAccessFilter { env_key: Some("API_KEY".into()), ..Default::default() }).await, 2);
+        assert_eq!(count(SecretAccessFilter { limit: Some(3), ..Default::default() }).await, 3);
+
+        assert_eq!(count(SecretAccessFilter { since: Some(before), ..Default::default() }).await, 4);
+        assert_eq!(count(SecretAccessFilter { until: Some(before), ..Default::default() }).await, 0);
+        let later = Utc::now() + Duration::hours(1);
+        assert_eq!(count(SecretAccessFilter { since: Some(later), ..Default::default() }).await, 0);
+    }
+}
@@ -2,6 +2,7 @@ use crate::db::DbPool;
 use crate::error::{CocoError, Result};
 use crate::types::{
     Blockchain, CreateNetworkInput, Ecosystem, Network, NetworkType, UpdateNetworkInput,
+    DEFAULT_CONFIRMATION_DEPTH,
 };
 use chrono::{DateTime, Utc};
 
@@ -112,7 +113,7 @@ impl BlockchainService {
             r#"
             SELECT id, blockchain_id, name, network_type, rpc_url, chain_id_numeric,
                    explorer_url, explorer_api_url, explorer_api_key, faucet_url,
-                   currency_symbol, currency_decimals, is_default, created_at
+                   currency_symbol, currency_decimals, is_default, confirmation_depth, created_at
             FROM networks
             WHERE blockchain_id = ?
             ORDER BY is_default DESC, name
@@ -132,7 +133,7 @@ impl BlockchainService {
             r#"
             SELECT id, blockchain_id, name, network_type, rpc_url, chain_id_numeric,
                    explorer_url, explorer_api_url, explorer_api_key, faucet_url,
-                   currency_symbol, currency_decimals, is_default, created_at
+                   currency_symbol, currency_decimals, is_default, confirmation_depth, created_at
             FROM networks
             WHERE id = ?
             "#,
@@ -151,6 +152,8 @@ impl BlockchainService {
         let id = format!("{}-{}", input.blockchain_id, input.name.to_lowercase().replace(' ', "-"));
         let created_at = Utc::now();
         let currency_decimals = input.currency_decimals.unwrap_or(18);
+        let confirmation_depth = input.confirmation_depth.unwrap_or(DEFAULT_CONFIRMATION_DEPTH);
+        validate_confirmation_depth(confirmation_depth)?;
 
         // If this is set as default, unset other defaults for this blockchain
         if input.is_default {
@@ -166,8 +169,8 @@ impl BlockchainService {
             INSERT INTO networks (
                 id, blockchain_id, name, network_type, rpc_url, chain_id_numeric,
                 explorer_url, explorer_api_url, explorer_api_key, faucet_url,
-                currency_symbol, currency_decimals, is_default, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                currency_symbol, currency_decimals, is_default, confirmation_depth, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id)
@@ -183,6 +186,7 @@ impl BlockchainService {
         .bind(&input.currency_symbol)
         .bind(currency_decimals as i32)
         .bind(input.is_default)
+        .bind(confirmation_depth as i64)
         .bind(created_at.to_rfc3339())
         .execute(&self.db)
         .await
@@ -202,6 +206,7 @@ impl BlockchainService {
             currency_symbol: input.currency_symbol,
             currency_decimals,
             is_default: input.is_default,
+            confirmation_depth,
             created_at,
         })
     }
@@ -236,6 +241,11 @@ impl BlockchainService {
             updates.push("faucet_url = ?");
             values.push(Box::new(faucet_url.clone()));
         }
+        if let Some(confirmation_depth) = input.confirmation_depth {
+            validate_confirmation_depth(confirmation_depth)?;
+            updates.push("confirmation_depth = ?");
+            values.push(Box::new(confirmation_depth));
+        }
 
         if updates.is_empty() && input.is_default.is_none() {
             return self.get_network(id).await;
@@ -281,6 +291,9 @@ impl BlockchainService {
         if let Some(faucet_url) = input.faucet_url {
             q = q.bind(faucet_url);
         }
+        if let Some(confirmation_depth) = input.confirmation_depth {
+            q = q.bind(confirmation_depth as i64);
+        }
         if let Some(is_default) = input.is_default {
             q = q.bind(is_default);
         }
@@ -364,6 +377,8 @@ struct NetworkRow {
     currency_symbol: String,
     currency_decimals: i32,
     is_default: bool,
+    #[sqlx(default)]
+    confirmation_depth: i64,
     created_at: String,
 }
 
@@ -383,6 +398,7 @@ impl From<NetworkRow> for Network {
             currency_symbol: row.currency_symbol,
             currency_decimals: row.currency_decimals as u8,
             is_default: row.is_default,
+            confirmation_depth: row.confirmation_depth.max(1) as u32,
             created_at: row
                 .created_at
                 .parse::<DateTime<Utc>>()
@@ -391,6 +407,15 @@ impl From<NetworkRow> for Network {
     }
 }
 
+fn validate_confirmation_depth(depth: u32) -> Result<()> {
+    if depth == 0 {
+        return Err(CocoError::Validation(
+            "Confirmation depth must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 fn string_to_ecosystem(s: &str) -> Ecosystem {
     match s.to_lowercase().as_str() {
         "solana" => Ecosystem::Solana,
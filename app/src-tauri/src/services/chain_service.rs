@@ -1,7 +1,7 @@
-use crate::adapters::AdapterRegistry;
+use crate::adapters::{AdapterRegistry, FullAdapter, NonceManager};
 use crate::db::DbPool;
 use crate::error::{CocoError, Result};
-use crate::types::{Chain, Ecosystem, NetworkType};
+use crate::types::{Chain, ChainConfig, Ecosystem, NetworkType, DEFAULT_CONFIRMATION_DEPTH};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -20,7 +20,7 @@ impl ChainService {
 
     pub async fn list_chains(&self) -> Result<Vec<Chain>> {
         let rows = sqlx::query_as::<_, ChainRow>(
-            "SELECT id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id FROM chains ORDER BY name"
+            "SELECT id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id, network_id FROM chains ORDER BY name"
         )
         .fetch_all(&self.db)
         .await
@@ -31,7 +31,7 @@ impl ChainService {
 
     pub async fn get_chain(&self, id: &str) -> Result<Chain> {
         let row = sqlx::query_as::<_, ChainRow>(
-            "SELECT id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id FROM chains WHERE id = ?"
+            "SELECT id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id, network_id FROM chains WHERE id = ?"
         )
         .bind(id)
         .fetch_optional(&self.db)
@@ -45,8 +45,8 @@ impl ChainService {
     pub async fn create_chain(&self, chain: Chain) -> Result<Chain> {
         sqlx::query(
             r#"
-            INSERT INTO chains (id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO chains (id, name, ecosystem, rpc_url, chain_id_numeric, explorer_url, explorer_api_url, explorer_api_key, faucet_url, is_testnet, currency_symbol, currency_decimals, blockchain, network_type, is_custom, icon_id, network_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&chain.id)
@@ -65,6 +65,7 @@ impl ChainService {
         .bind(network_type_to_string(&chain.network_type))
         .bind(chain.is_custom as i32)
         .bind(&chain.icon_id)
+        .bind(&chain.network_id)
         .execute(&self.db)
         .await
         .map_err(|e| CocoError::Database(e.to_string()))?;
@@ -76,7 +77,7 @@ impl ChainService {
         let result = sqlx::query(
             r#"
             UPDATE chains
-            SET name = ?, ecosystem = ?, rpc_url = ?, chain_id_numeric = ?, explorer_url = ?, explorer_api_url = ?, explorer_api_key = ?, faucet_url = ?, is_testnet = ?, currency_symbol = ?, currency_decimals = ?, blockchain = ?, network_type = ?, is_custom = ?, icon_id = ?
+            SET name = ?, ecosystem = ?, rpc_url = ?, chain_id_numeric = ?, explorer_url = ?, explorer_api_url = ?, explorer_api_key = ?, faucet_url = ?, is_testnet = ?, currency_symbol = ?, currency_decimals = ?, blockchain = ?, network_type = ?, is_custom = ?, icon_id = ?, network_id = ?
             WHERE id = ?
             "#,
         )
@@ -95,6 +96,7 @@ impl ChainService {
         .bind(network_type_to_string(&updated.network_type))
         .bind(updated.is_custom as i32)
         .bind(&updated.icon_id)
+        .bind(&updated.network_id)
         .bind(id)
         .execute(&self.db)
         .await
@@ -104,6 +106,9 @@ impl ChainService {
             return Err(CocoError::NotFound(format!("Chain not found: {}", id)));
         }
 
+        // Drop the cached adapter so the next use picks up the new RPC settings
+        self.adapter_registry.read().await.remove(id).await;
+
         Ok(updated)
     }
 
@@ -118,20 +123,44 @@ impl ChainService {
             return Err(CocoError::NotFound(format!("Chain not found: {}", id)));
         }
 
+        self.adapter_registry.read().await.remove(id).await;
+
         Ok(())
     }
 
-    pub async fn get_adapter_for_chain(&self, chain_id: &str) -> Result<()> {
+    /// Nonce manager that coordinates sends from the same account
+    pub async fn nonce_manager(&self) -> Arc<NonceManager> {
+        self.adapter_registry.read().await.nonce_manager()
+    }
+
+    /// Blocks a transaction on the chain must be buried under before it is final,
+    /// from the settings of the network the chain is linked to
+    pub async fn confirmation_depth(&self, chain_id: &str) -> Result<u64> {
+        let depth: Option<(i64,)> = sqlx::query_as(
+            "SELECT n.confirmation_depth FROM chains c JOIN networks n ON n.id = c.network_id WHERE c.id = ?",
+        )
+            .bind(chain_id)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(depth.map_or(DEFAULT_CONFIRMATION_DEPTH as u64, |(d,)| d.max(1) as u64))
+    }
+
+    /// Gets the adapter for a chain, creating it from the chain's RPC settings on first use
+    pub async fn get_adapter_for_chain(&self, chain_id: &str) -> Result<Arc<dyn FullAdapter>> {
         let chain = self.get_chain(chain_id).await?;
         let registry = self.adapter_registry.read().await;
 
         registry
-            .get_adapter(&chain.ecosystem)
-            .ok_or_else(|| {
-                CocoError::Adapter(format!("No adapter found for ecosystem: {:?}", chain.ecosystem))
-            })?;
-
-        Ok(())
+            .get_or_register(ChainConfig {
+                id: chain.id,
+                name: chain.name,
+                ecosystem: chain.ecosystem,
+                rpc_url: chain.rpc_url,
+                native_currency: chain.currency_symbol,
+            })
+            .await
     }
 }
 
@@ -154,6 +183,7 @@ struct ChainRow {
     network_type: Option<String>,
     is_custom: Option<i32>,
     icon_id: Option<String>,
+    network_id: Option<String>,
 }
 
 impl From<ChainRow> for Chain {
@@ -175,6 +205,7 @@ impl From<ChainRow> for Chain {
             network_type: row.network_type.map(|s| string_to_network_type(&s)).unwrap_or(NetworkType::Custom),
             is_custom: row.is_custom.map(|v| v != 0).unwrap_or(false),
             icon_id: row.icon_id,
+            network_id: row.network_id,
         }
     }
 }
@@ -236,3 +267,60 @@ fn infer_blockchain_from_id(id: &str) -> String {
         "custom".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_chain(id: &str, network_id: Option<&str>) -> Chain {
+        Chain {
+            id: id.to_string(),
+            name: "Local Anvil".to_string(),
+            ecosystem: Ecosystem::Evm,
+            rpc_url: "http://localhost:8545".to_string(),
+            chain_id_numeric: Some(31337),
+            explorer_url: None,
+            explorer_api_url: None,
+            explorer_api_key: None,
+            faucet_url: None,
+            is_testnet: true,
+            currency_symbol: "ETH".to_string(),
+            currency_decimals: 18,
+            blockchain: "ethereum".to_string(),
+            network_type: NetworkType::Custom,
+            is_custom: true,
+            icon_id: None,
+            network_id: network_id.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_depth_follows_linked_network() {
+        let db = crate::db::test_db().await;
+        let service = ChainService::new(db.clone(), Arc::new(RwLock::new(AdapterRegistry::new())));
+
+        sqlx::query(
+            "INSERT INTO networks (id, blockchain_id, name, rpc_url, currency_symbol, confirmation_depth) VALUES ('ethereum-local', 'ethereum', 'Local', 'http://localhost:8545', 'ETH', 4)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE networks SET confirmation_depth = 6 WHERE id = 'ethereum-sepolia'")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        // The chain id differs from its network's, so only the link finds the depth
+        service.create_chain(custom_chain("anvil", Some("ethereum-local"))).await.unwrap();
+        service.create_chain(custom_chain("anvil-unlinked", None)).await.unwrap();
+
+        assert_eq!(service.confirmation_depth("anvil").await.unwrap(), 4);
+        assert_eq!(
+            service.confirmation_depth("anvil-unlinked").await.unwrap(),
+            DEFAULT_CONFIRMATION_DEPTH as u64
+        );
+        // Seeded chains are linked to the network they were copied into
+        assert_eq!(service.get_chain("ethereum-sepolia").await.unwrap().network_id.as_deref(), Some("ethereum-sepolia"));
+        assert_eq!(service.confirmation_depth("ethereum-sepolia").await.unwrap(), 6);
+    }
+}
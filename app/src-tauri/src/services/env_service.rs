@@ -1,69 +1,20 @@
 use crate::db::DbPool;
 use crate::error::{CocoError, Result};
+use crate::services::VaultService;
 use crate::types::EnvironmentVariable;
-use aes_gcm::{
-    aead::{Aead, KeyInit},
-    Aes256Gcm, Nonce,
-};
 use chrono::{DateTime, Utc};
-use rand::Rng;
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
-// Reuse the same encryption key derivation as wallet_service
-// In production, this would use proper key management
-fn get_encryption_key() -> [u8; 32] {
-    let mut key = [0u8; 32];
-    key.copy_from_slice(b"coco_dev_key_32bytes_placeholder");
-    key
-}
-
-fn encrypt_value(value: &str) -> Result<Vec<u8>> {
-    let key = get_encryption_key();
-    let cipher = Aes256Gcm::new_from_slice(&key)
-        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?;
-
-    let mut rng = rand::thread_rng();
-    let mut nonce_bytes = [0u8; 12];
-    rng.fill(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes);
-
-    let ciphertext = cipher
-        .encrypt(nonce, value.as_bytes())
-        .map_err(|e| CocoError::Crypto(format!("Encryption failed: {}", e)))?;
-
-    // Prepend nonce to ciphertext
-    let mut result = nonce_bytes.to_vec();
-    result.extend(ciphertext);
-    Ok(result)
-}
-
-fn decrypt_value(encrypted: &[u8]) -> Result<String> {
-    if encrypted.len() < 12 {
-        return Err(CocoError::Crypto("Invalid encrypted data".to_string()));
-    }
-
-    let key = get_encryption_key();
-    let cipher = Aes256Gcm::new_from_slice(&key)
-        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?;
-
-    let nonce = Nonce::from_slice(&encrypted[..12]);
-    let ciphertext = &encrypted[12..];
-
-    let plaintext = cipher
-        .decrypt(nonce, ciphertext)
-        .map_err(|e| CocoError::Crypto(format!("Decryption failed: {}", e)))?;
-
-    String::from_utf8(plaintext).map_err(|e| CocoError::Crypto(format!("Invalid UTF-8: {}", e)))
-}
-
 pub struct EnvService {
     db: DbPool,
+    vault: Arc<VaultService>,
 }
 
 impl EnvService {
-    pub fn new(db: DbPool) -> Self {
-        Self { db }
+    pub fn new(db: DbPool, vault: Arc<VaultService>) -> Self {
+        Self { db, vault }
     }
 
     /// List all environment variables for a workspace (without values)
@@ -128,7 +79,7 @@ impl EnvService {
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
         match row {
-            Some((encrypted,)) => decrypt_value(&encrypted),
+            Some((encrypted,)) => self.vault.decrypt(&encrypted),
             None => Err(CocoError::NotFound(format!(
                 "Environment variable not found: {} in workspace {}",
                 key, workspace_id
@@ -172,7 +123,7 @@ impl EnvService {
 
         let mut result = HashMap::new();
         for (key, encrypted) in rows {
-            let value = decrypt_value(&encrypted)?;
+            let value = self.vault.decrypt(&encrypted)?;
             result.insert(key, value);
         }
 
@@ -197,7 +148,7 @@ impl EnvService {
 
         let id = Uuid::new_v4().to_string();
         let created_at = Utc::now();
-        let encrypted_value = encrypt_value(value)?;
+        let encrypted_value = self.vault.encrypt(value)?;
 
         sqlx::query(
             "INSERT INTO environment_variables (id, workspace_id, key, value, description, created_at) VALUES (?, ?, ?, ?, ?, ?)",
@@ -281,7 +232,7 @@ impl EnvService {
             q = q.bind(key.unwrap());
         }
         if has_value {
-            let encrypted = encrypt_value(value.unwrap())?;
+            let encrypted = self.vault.encrypt(value.unwrap())?;
             q = q.bind(encrypted);
         }
         if has_desc {
@@ -9,6 +9,10 @@ mod conversation_service;
 mod preference_service;
 mod contract_doc_service;
 mod workflow_service;
+mod vault_service;
+mod audit_service;
+mod tracker_service;
+mod watcher_service;
 
 pub use chain_service::ChainService;
 pub use wallet_service::WalletService;
@@ -20,5 +24,9 @@ pub use env_service::EnvService;
 pub use conversation_service::ConversationService;
 pub use preference_service::PreferenceService;
 pub use contract_doc_service::ContractDocService;
+pub use vault_service::VaultService;
+pub use audit_service::AuditService;
+pub use tracker_service::TrackerService;
+pub use watcher_service::WatcherService;
 pub use workflow_service::{WorkflowService, Workflow, WorkflowRun, WorkflowStepExecution, ExecutionMode};
 
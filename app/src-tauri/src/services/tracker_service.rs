@@ -0,0 +1,86 @@
+use crate::error::{CocoError, Result};
+use crate::services::{ChainService, WorkspaceService};
+use crate::types::{TransactionRun, TxStatus};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often pending runs are checked for receipts
+const TRACKER_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Background task that follows runs still pending after `execute_transaction`
+/// stopped waiting, recording their receipts once they are mined and watching
+/// confirming runs for reorgs until they reach the network's confirmation depth
+pub struct TrackerService {
+    chain_service: Arc<ChainService>,
+    workspace_service: Arc<WorkspaceService>,
+}
+
+impl TrackerService {
+    pub fn new(chain_service: Arc<ChainService>, workspace_service: Arc<WorkspaceService>) -> Self {
+        Self {
+            chain_service,
+            workspace_service,
+        }
+    }
+
+    /// Polls until the app exits; spawned once at startup
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(TRACKER_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            // A database error now is retried on the next tick like any other
+            if let Err(e) = self.poll().await {
+                eprintln!("Transaction tracker poll failed: {}", e);
+            }
+        }
+    }
+
+    /// Checks every pending and confirming run once and returns the runs it updated
+    pub async fn poll(&self) -> Result<Vec<TransactionRun>> {
+        let mut updated = Vec::new();
+
+        for (chain_id, run) in self.workspace_service.list_pending_runs().await? {
+            let Some(hash) = run.tx_hash.clone() else {
+                continue;
+            };
+            let Ok(adapter) = self.chain_service.get_adapter_for_chain(&chain_id).await else {
+                continue;
+            };
+
+            let run_id = run.id.clone();
+            let confirming = run.status == TxStatus::Confirming;
+            let result = match adapter.get_transaction_receipt(&hash).await {
+                Ok(receipt) if confirming && run.block_hash.is_some() && receipt.block_hash != run.block_hash => {
+                    let note = format!(
+                        "Block {} was reorganized away; the transaction is now in block {}",
+                        run.block_hash.as_deref().unwrap_or_default(),
+                        receipt.block_number
+                    );
+                    self.workspace_service.reopen_run(run, note).await
+                }
+                Ok(receipt) => {
+                    self.workspace_service
+                        .complete_run(run, adapter.as_ref(), &receipt)
+                        .await
+                }
+                Err(CocoError::NotFound(_)) if confirming => {
+                    let note = format!(
+                        "Receipt from block {} disappeared in a reorg",
+                        run.block_number.unwrap_or_default()
+                    );
+                    self.workspace_service.reopen_run(run, note).await
+                }
+                // Not mined yet, dropped in favour of a replacement, or the node is
+                // unreachable: either way the run keeps its status until the next poll
+                Err(_) => continue,
+            };
+            // One run failing to save must not hold up the rest
+            match result {
+                Ok(run) => updated.push(run),
+                Err(e) => eprintln!("Failed to update transaction run {}: {}", run_id, e),
+            }
+        }
+
+        Ok(updated)
+    }
+}
@@ -0,0 +1,565 @@
+use crate::crypto;
+use crate::db::DbPool;
+use crate::error::{CocoError, Result};
+use crate::types::{KeyRotationReport, VaultStatus};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::Rng;
+use sqlx::SqliteConnection;
+use std::sync::RwLock;
+
+/// Key every secret was encrypted with before the vault existed
+const LEGACY_KEY: &[u8; 32] = b"coco_dev_key_32bytes_placeholder";
+
+/// Plaintext sealed under the master key so unlock can check the passphrase
+const VERIFIER_PLAINTEXT: &[u8] = b"coco-vault-verifier";
+
+const SALT_LEN: usize = 16;
+
+/// Holds the passphrase-derived master key used to encrypt wallet keys and
+/// environment variables. The key only ever lives in memory while unlocked.
+pub struct VaultService {
+    db: DbPool,
+    key: RwLock<KeySlot>,
+}
+
+/// The in-memory master key, and whether a rotation is re-encrypting the
+/// stored secrets right now
+#[derive(Default)]
+struct KeySlot {
+    key: Option<[u8; 32]>,
+    rotating: bool,
+}
+
+impl VaultService {
+    pub fn new(db: DbPool) -> Self {
+        Self {
+            db,
+            key: RwLock::new(KeySlot::default()),
+        }
+    }
+
+    pub async fn status(&self) -> Result<VaultStatus> {
+        Ok(VaultStatus {
+            initialized: self.load_config().await?.is_some(),
+            unlocked: self.is_unlocked(),
+        })
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.key.read().unwrap_or_else(|e| e.into_inner()).key.is_some()
+    }
+
+    /// Creates the vault from a passphrase and re-encrypts every secret that
+    /// was stored under the legacy built-in key. Leaves the vault unlocked.
+    pub async fn setup(&self, passphrase: &str) -> Result<()> {
+        validate_passphrase(passphrase)?;
+
+        if self.load_config().await?.is_some() {
+            return Err(CocoError::Validation("Vault is already set up".to_string()));
+        }
+
+        let salt: [u8; SALT_LEN] = rand::thread_rng().gen();
+        let params = KdfParams::default();
+        let key = derive_key_blocking(passphrase, salt.to_vec(), params).await?;
+        let verifier = crypto::encrypt(&key, VERIFIER_PLAINTEXT)?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO vault (id, salt, verifier, m_cost, t_cost, p_cost) VALUES (1, ?, ?, ?, ?, ?)",
+        )
+        .bind(salt.to_vec())
+        .bind(&verifier)
+        .bind(params.m_cost as i64)
+        .bind(params.t_cost as i64)
+        .bind(params.p_cost as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        // One-time migration of secrets written before the vault existed
+        reencrypt_secrets(&mut tx, LEGACY_KEY, &key).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        self.set_key(Some(key));
+        Ok(())
+    }
+
+    /// Derives the master key from the passphrase and keeps it in memory
+    pub async fn unlock(&self, passphrase: &str) -> Result<()> {
+        let key = self.verify_passphrase(passphrase).await?;
+        self.set_key(Some(key));
+        Ok(())
+    }
+
+    /// Replaces the passphrase, re-encrypting every wallet key, HD wallet phrase
+    /// and environment variable under the new master key. Nothing changes unless all succeed.
+    pub async fn rotate_encryption_key(
+        &self,
+        old_passphrase: &str,
+        new_passphrase: &str,
+    ) -> Result<KeyRotationReport> {
+        validate_passphrase(new_passphrase)?;
+        let old_key = self.verify_passphrase(old_passphrase).await?;
+
+        let salt: [u8; SALT_LEN] = rand::thread_rng().gen();
+        let params = KdfParams::default();
+        let new_key = derive_key_blocking(new_passphrase, salt.to_vec(), params).await?;
+        let verifier = crypto::encrypt(&new_key, VERIFIER_PLAINTEXT)?;
+
+        // Nothing may seal or open a secret under the old key until the rotation ends
+        let _rotation = self.begin_rotation()?;
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE vault SET salt = ?, verifier = ?, m_cost = ?, t_cost = ?, p_cost = ? WHERE id = 1",
+        )
+        .bind(salt.to_vec())
+        .bind(&verifier)
+        .bind(params.m_cost as i64)
+        .bind(params.t_cost as i64)
+        .bind(params.p_cost as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        // Dropping the transaction on an error rolls every update back
+        let report = reencrypt_secrets(&mut tx, &old_key, &new_key).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        self.set_key(Some(new_key));
+        Ok(report)
+    }
+
+    /// Derives the key for a passphrase and checks it against the stored verifier
+    async fn verify_passphrase(&self, passphrase: &str) -> Result<[u8; 32]> {
+        let config = self
+            .load_config()
+            .await?
+            .ok_or_else(|| CocoError::Validation("Vault is not set up".to_string()))?;
+
+        let key = derive_key_blocking(passphrase, config.salt, config.params).await?;
+        match crypto::decrypt(&key, &config.verifier) {
+            Ok(plaintext) if plaintext == VERIFIER_PLAINTEXT => Ok(key),
+            _ => Err(CocoError::Validation("Incorrect passphrase".to_string())),
+        }
+    }
+
+    /// Forgets the master key
+    pub fn lock(&self) {
+        self.set_key(None);
+    }
+
+    /// Encrypts a secret under the master key
+    pub fn encrypt(&self, plaintext: &str) -> Result<Vec<u8>> {
+        crypto::encrypt(&self.key()?, plaintext.as_bytes())
+    }
+
+    /// Decrypts a secret stored under the master key
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<String> {
+        let plaintext = crypto::decrypt(&self.key()?, encrypted)?;
+        String::from_utf8(plaintext).map_err(|e| CocoError::Crypto(format!("Invalid UTF-8: {}", e)))
+    }
+
+    fn key(&self) -> Result<[u8; 32]> {
+        let slot = self.key.read().unwrap_or_else(|e| e.into_inner());
+        if slot.rotating {
+            return Err(CocoError::VaultLocked(
+                "the encryption key is being rotated, try again once it finishes".to_string(),
+            ));
+        }
+        slot.key.ok_or_else(|| {
+            CocoError::VaultLocked(
+                "set a passphrase with setup_vault on first run, or unlock the vault".to_string(),
+            )
+        })
+    }
+
+    fn set_key(&self, key: Option<[u8; 32]>) {
+        let mut slot = self.key.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = slot.key.as_mut() {
+            old.fill(0);
+        }
+        slot.key = key;
+    }
+
+    /// Blocks encrypt and decrypt until the returned guard is dropped
+    fn begin_rotation(&self) -> Result<RotationGuard<'_>> {
+        let mut slot = self.key.write().unwrap_or_else(|e| e.into_inner());
+        if slot.rotating {
+            return Err(CocoError::Validation("A key rotation is already in progress".to_string()));
+        }
+        slot.rotating = true;
+        Ok(RotationGuard { slot: &self.key })
+    }
+
+    async fn load_config(&self) -> Result<Option<VaultConfig>> {
+        let row = sqlx::query_as::<_, VaultRow>(
+            "SELECT salt, verifier, m_cost, t_cost, p_cost FROM vault WHERE id = 1",
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(row.map(VaultConfig::from))
+    }
+}
+
+/// Ends a rotation however it finishes, including when the future is dropped
+struct RotationGuard<'a> {
+    slot: &'a RwLock<KeySlot>,
+}
+
+impl Drop for RotationGuard<'_> {
+    fn drop(&mut self) {
+        self.slot.write().unwrap_or_else(|e| e.into_inner()).rotating = false;
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct VaultRow {
+    salt: Vec<u8>,
+    verifier: Vec<u8>,
+    m_cost: i64,
+    t_cost: i64,
+    p_cost: i64,
+}
+
+struct VaultConfig {
+    salt: Vec<u8>,
+    verifier: Vec<u8>,
+    params: KdfParams,
+}
+
+impl From<VaultRow> for VaultConfig {
+    fn from(row: VaultRow) -> Self {
+        VaultConfig {
+            salt: row.salt,
+            verifier: row.verifier,
+            params: KdfParams {
+                m_cost: row.m_cost as u32,
+                t_cost: row.t_cost as u32,
+                p_cost: row.p_cost as u32,
+            },
+        }
+    }
+}
+
+/// Argon2id cost parameters, stored with the vault so defaults can change later
+#[derive(Clone, Copy)]
+struct KdfParams {
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Re-encrypts every stored wallet key, HD wallet phrase and environment variable
+/// from one key to another
+async fn reencrypt_secrets(
+    conn: &mut SqliteConnection,
+    from: &[u8; 32],
+    to: &[u8; 32],
+) -> Result<KeyRotationReport> {
+    let mut report = KeyRotationReport::default();
+
+    let wallets: Vec<(String, Vec<u8>)> = sqlx::query_as(
+        "SELECT id, encrypted_private_key FROM wallets WHERE encrypted_private_key IS NOT NULL",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| CocoError::Database(e.to_string()))?;
+
+    for (id, encrypted) in wallets {
+        let plaintext = crypto::decrypt(from, &encrypted).map_err(|e| {
+            CocoError::Crypto(format!("Failed to re-encrypt key for wallet {}: {}", id, e))
+        })?;
+
+        sqlx::query("UPDATE wallets SET encrypted_private_key = ? WHERE id = ?")
+            .bind(crypto::encrypt(to, &plaintext)?)
+            .bind(&id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        report.wallets += 1;
+    }
+
+    let hd_wallets: Vec<(String, Vec<u8>)> =
+        sqlx::query_as("SELECT id, encrypted_mnemonic FROM hd_wallets")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+    for (id, encrypted) in hd_wallets {
+        let plaintext = crypto::decrypt(from, &encrypted).map_err(|e| {
+            CocoError::Crypto(format!("Failed to re-encrypt phrase for HD wallet {}: {}", id, e))
+        })?;
+
+        sqlx::query("UPDATE hd_wallets SET encrypted_mnemonic = ? WHERE id = ?")
+            .bind(crypto::encrypt(to, &plaintext)?)
+            .bind(&id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        report.hd_wallets += 1;
+    }
+
+    let env_vars: Vec<(String, Vec<u8>)> =
+        sqlx::query_as("SELECT id, value FROM environment_variables")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+    for (id, encrypted) in env_vars {
+        let plaintext = crypto::decrypt(from, &encrypted).map_err(|e| {
+            CocoError::Crypto(format!("Failed to re-encrypt environment variable {}: {}", id, e))
+        })?;
+
+        sqlx::query("UPDATE environment_variables SET value = ? WHERE id = ?")
+            .bind(crypto::encrypt(to, &plaintext)?)
+            .bind(&id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        report.env_vars += 1;
+    }
+
+    Ok(report)
+}
+
+fn validate_passphrase(passphrase: &str) -> Result<()> {
+    if passphrase.chars().count() < 8 {
+        return Err(CocoError::Validation(
+            "Passphrase must be at least 8 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Runs [`derive_key`] on the blocking pool, since Argon2id is slow by design
+/// and would otherwise stall every other task on the runtime
+async fn derive_key_blocking(passphrase: &str, salt: Vec<u8>, params: KdfParams) -> Result<[u8; 32]> {
+    let passphrase = passphrase.to_string();
+    tokio::task::spawn_blocking(move || derive_key(&passphrase, &salt, &params))
+        .await
+        .map_err(|e| CocoError::Crypto(format!("Key derivation failed: {}", e)))?
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<[u8; 32]> {
+    let params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+        .map_err(|e| CocoError::Crypto(format!("Invalid KDF parameters: {}", e)))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CocoError::Crypto(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cheap_params() -> KdfParams {
+        KdfParams {
+            m_cost: 64,
+            t_cost: 1,
+            p_cost: 1,
+        }
+    }
+
+    #[test]
+    fn test_derived_key_opens_verifier() {
+        let salt = [7u8; SALT_LEN];
+        let key = derive_key("correct horse", &salt, &cheap_params()).unwrap();
+        let verifier = crypto::encrypt(&key, VERIFIER_PLAINTEXT).unwrap();
+
+        let again = derive_key("correct horse", &salt, &cheap_params()).unwrap();
+        assert_eq!(crypto::decrypt(&again, &verifier).unwrap(), VERIFIER_PLAINTEXT);
+
+        let wrong = derive_key("wrong horse", &salt, &cheap_params()).unwrap();
+        assert!(crypto::decrypt(&wrong, &verifier).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_secrets_need_set_up_and_unlocked_vault() {
+        let vault = VaultService::new(crate::db::test_db().await);
+        assert!(matches!(vault.encrypt("0xabc"), Err(CocoError::VaultLocked(_))));
+
+        vault.setup("correct horse").await.unwrap();
+        let sealed = vault.encrypt("0xabc").unwrap();
+
+        vault.lock();
+        assert!(matches!(vault.decrypt(&sealed), Err(CocoError::VaultLocked(_))));
+        assert!(vault.unlock("wrong horse").await.is_err());
+        vault.unlock("correct horse").await.unwrap();
+        assert_eq!(vault.decrypt(&sealed).unwrap(), "0xabc");
+    }
+
+    /// Stores two wallet keys, an HD wallet phrase and an environment variable
+    /// sealed under the vault's current key
+    async fn seed_secrets(vault: &VaultService) {
+        for (id, key) in [("alice", "0xaa"), ("bob", "0xbb")] {
+            sqlx::query(
+                "INSERT INTO wallets (id, chain_id, name, address, public_key, wallet_type, encrypted_private_key) VALUES (?, 'ethereum-sepolia', ?, ?, '', 'imported', ?)",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("0x{}", id))
+            .bind(vault.encrypt(key).unwrap())
+            .execute(&vault.db)
+            .await
+            .unwrap();
+        }
+        sqlx::query("INSERT INTO hd_wallets (id, name, encrypted_mnemonic) VALUES ('seed', 'Seed', ?)")
+            .bind(vault.encrypt("test test test").unwrap())
+            .execute(&vault.db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO workspaces (id, chain_id, name, path) VALUES ('ws', 'ethereum-sepolia', 'Vault', '/tmp')")
+            .execute(&vault.db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO environment_variables (id, workspace_id, key, value) VALUES ('rpc', 'ws', 'RPC_URL', ?)")
+            .bind(vault.encrypt("http://localhost:8545").unwrap())
+            .execute(&vault.db)
+            .await
+            .unwrap();
+    }
+
+    async fn wallet_key(vault: &VaultService, id: &str) -> String {
+        let (encrypted,): (Vec<u8>,) = sqlx::query_as("SELECT encrypted_private_key FROM wallets WHERE id = ?")
+            .bind(id)
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+        vault.decrypt(&encrypted).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rotation_reports_reencrypted_secrets() {
+        let vault = VaultService::new(crate::db::test_db().await);
+        vault.setup("correct horse").await.unwrap();
+        seed_secrets(&vault).await;
+
+        let report = vault.rotate_encryption_key("correct horse", "battery staple").await.unwrap();
+        assert_eq!((report.wallets, report.hd_wallets, report.env_vars), (2, 1, 1));
+
+        vault.lock();
+        assert!(vault.unlock("correct horse").await.is_err());
+        vault.unlock("battery staple").await.unwrap();
+        assert_eq!(wallet_key(&vault, "alice").await, "0xaa");
+        assert_eq!(wallet_key(&vault, "bob").await, "0xbb");
+        let (phrase,): (Vec<u8>,) = sqlx::query_as("SELECT encrypted_mnemonic FROM hd_wallets WHERE id = 'seed'")
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+        assert_eq!(vault.decrypt(&phrase).unwrap(), "test test test");
+    }
+
+    #[tokio::test]
+    async fn test_failed_rotation_rolls_back_everything() {
+        let vault = VaultService::new(crate::db::test_db().await);
+        vault.setup("correct horse").await.unwrap();
+        seed_secrets(&vault).await;
+        let (salt_before,): (Vec<u8>,) = sqlx::query_as("SELECT salt FROM vault WHERE id = 1")
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+        let (alice_before,): (Vec<u8>,) = sqlx::query_as("SELECT encrypted_private_key FROM wallets WHERE id = 'alice'")
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+
+        // The environment variable is re-encrypted last, after both wallet keys
+        let stranger = derive_key("someone else", &[9u8; SALT_LEN], &cheap_params()).unwrap();
+        sqlx::query("UPDATE environment_variables SET value = ? WHERE id = 'rpc'")
+            .bind(crypto::encrypt(&stranger, b"http://localhost:8545").unwrap())
+            .execute(&vault.db)
+            .await
+            .unwrap();
+
+        let error = vault.rotate_encryption_key("correct horse", "battery staple").await.unwrap_err();
+        assert!(matches!(error, CocoError::Crypto(_)));
+
+        let (salt_after,): (Vec<u8>,) = sqlx::query_as("SELECT salt FROM vault WHERE id = 1")
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+        assert_eq!(salt_after, salt_before);
+        let (alice_after,): (Vec<u8>,) = sqlx::query_as("SELECT encrypted_private_key FROM wallets WHERE id = 'alice'")
+            .fetch_one(&vault.db)
+            .await
+            .unwrap();
+        assert_eq!(alice_after, alice_before);
+
+        // The old key still opens everything and the vault is usable again
+        assert_eq!(wallet_key(&vault, "alice").await, "0xaa");
+        assert_eq!(wallet_key(&vault, "bob").await, "0xbb");
+        vault.lock();
+        assert!(vault.unlock("battery staple").await.is_err());
+        vault.unlock("correct horse").await.unwrap();
+        assert!(vault.encrypt("0xcc").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_blocks_encrypt_and_decrypt() {
+        let vault = VaultService::new(crate::db::test_db().await);
+        vault.setup("correct horse").await.unwrap();
+        let sealed = vault.encrypt("0xabc").unwrap();
+
+        let rotation = vault.begin_rotation().unwrap();
+        assert!(matches!(vault.encrypt("0xabc"), Err(CocoError::VaultLocked(_))));
+        assert!(matches!(vault.decrypt(&sealed), Err(CocoError::VaultLocked(_))));
+        assert!(vault.begin_rotation().is_err());
+
+        drop(rotation);
+        assert_eq!(vault.decrypt(&sealed).unwrap(), "0xabc");
+    }
+
+    #[test]
+    fn test_legacy_ciphertext_reseals() {
+        use aes_gcm::{aead::{Aead, KeyInit}, Aes256Gcm, Nonce};
+
+        // Pre-envelope layout: a bare nonce || ciphertext under the built-in key
+        let nonce = [3u8; 12];
+        let mut legacy = nonce.to_vec();
+        legacy.extend(
+            Aes256Gcm::new_from_slice(LEGACY_KEY)
+                .unwrap()
+                .encrypt(Nonce::from_slice(&nonce), b"0xdeadbeef".as_slice())
+                .unwrap(),
+        );
+        assert!(crypto::header(&legacy).is_none());
+        assert_eq!(crypto::decrypt(LEGACY_KEY, &legacy).unwrap(), b"0xdeadbeef");
+
+        let key = derive_key("correct horse", &[1u8; SALT_LEN], &cheap_params()).unwrap();
+
+        let resealed = crypto::encrypt(&key, &crypto::decrypt(LEGACY_KEY, &legacy).unwrap()).unwrap();
+        assert_eq!(crypto::decrypt(&key, &resealed).unwrap(), b"0xdeadbeef");
+        assert!(crypto::decrypt(LEGACY_KEY, &resealed).is_err());
+    }
+}
@@ -1,64 +1,44 @@
-use crate::adapters::AdapterRegistry;
+use crate::adapters::{aptos, derivation, evm, solana, ExternalSigner, SoftwareSigner};
 use crate::db::DbPool;
 use crate::error::{CocoError, Result};
-use crate::types::{Ecosystem, Wallet, WalletType, WalletWithChain};
-use aes_gcm::{
-    aead::{Aead, KeyInit},
-    Aes256Gcm, Nonce,
+use crate::keystore::{self, BundledWallet, WalletBundle};
+use crate::services::{AuditService, ChainService, VaultService};
+use crate::types::{
+    Ecosystem, HdWallet, SkippedWallet, TokenBalance, Wallet, WalletBalanceSnapshot,
+    WalletBundleImport, WalletType, WalletWithChain,
 };
 use chrono::{DateTime, Utc};
-use rand::Rng;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
-// Simple encryption key derivation (in production, use proper key management)
-// This is a placeholder - in a real app, derive from user password or secure enclave
-fn get_encryption_key() -> [u8; 32] {
-    // For now, use a static key - in production, derive from user credentials
-    let mut key = [0u8; 32];
-    key.copy_from_slice(b"coco_dev_key_32bytes_placeholder");
-    key
-}
-
-fn encrypt_private_key(private_key: &str) -> Result<Vec<u8>> {
-    let key = get_encryption_key();
-    let cipher = Aes256Gcm::new_from_slice(&key)
-        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?;
-
-    let mut rng = rand::thread_rng();
-    let mut nonce_bytes = [0u8; 12];
-    rng.fill(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes);
-
-    let ciphertext = cipher
-        .encrypt(nonce, private_key.as_bytes())
-        .map_err(|e| CocoError::Crypto(format!("Encryption failed: {}", e)))?;
-
-    // Prepend nonce to ciphertext
-    let mut result = nonce_bytes.to_vec();
-    result.extend(ciphertext);
-    Ok(result)
+/// Compares two addresses in the ecosystem's canonical form
+fn addresses_match(ecosystem: &Ecosystem, a: &str, b: &str) -> bool {
+    match ecosystem {
+        // Checksummed and lowercase forms are the same address
+        Ecosystem::Evm => a.trim().eq_ignore_ascii_case(b.trim()),
+        // Leading zeros may be dropped in the short form
+        Ecosystem::Aptos => {
+            let normalize = |a: &str| {
+                format!("{:0>64}", a.trim().trim_start_matches("0x").to_lowercase())
+            };
+            normalize(a) == normalize(b)
+        }
+        Ecosystem::Solana => a.trim() == b.trim(),
+    }
 }
 
-fn decrypt_private_key(encrypted: &[u8]) -> Result<String> {
-    if encrypted.len() < 12 {
-        return Err(CocoError::Crypto("Invalid encrypted data".to_string()));
+/// Rejects a supplied address that isn't the one derived from its private key
+fn ensure_address_matches(ecosystem: &Ecosystem, supplied: &str, derived: &str) -> Result<()> {
+    if addresses_match(ecosystem, supplied, derived) {
+        Ok(())
+    } else {
+        Err(CocoError::Validation(format!(
+            "Address {} does not match the private key (derived {})",
+            supplied, derived
+        )))
     }
-
-    let key = get_encryption_key();
-    let cipher = Aes256Gcm::new_from_slice(&key)
-        .map_err(|e| CocoError::Crypto(format!("Failed to create cipher: {}", e)))?;
-
-    let nonce = Nonce::from_slice(&encrypted[..12]);
-    let ciphertext = &encrypted[12..];
-
-    let plaintext = cipher
-        .decrypt(nonce, ciphertext)
-        .map_err(|e| CocoError::Crypto(format!("Decryption failed: {}", e)))?;
-
-    String::from_utf8(plaintext)
-        .map_err(|e| CocoError::Crypto(format!("Invalid UTF-8: {}", e)))
 }
 
 /// Validate wallet address format based on ecosystem
@@ -113,23 +93,39 @@ fn validate_address(address: &str, ecosystem: &Ecosystem) -> Result<()> {
     Ok(())
 }
 
+/// Latest native balance snapshot, selected alongside wallet rows
+const LATEST_BALANCE: &str = "(SELECT native FROM wallet_balances b WHERE b.wallet_id = wallets.id ORDER BY julianday(b.recorded_at) DESC, b.rowid DESC LIMIT 1) AS balance";
+
 pub struct WalletService {
     db: DbPool,
-    adapter_registry: Arc<RwLock<AdapterRegistry>>,
+    chain_service: Arc<ChainService>,
+    vault: Arc<VaultService>,
+    audit: Arc<AuditService>,
+    /// External signers connected for Ledger wallets, by wallet ID
+    signers: RwLock<HashMap<String, Arc<dyn ExternalSigner>>>,
 }
 
 impl WalletService {
-    pub fn new(db: DbPool, adapter_registry: Arc<RwLock<AdapterRegistry>>) -> Self {
+    pub fn new(
+        db: DbPool,
+        chain_service: Arc<ChainService>,
+        vault: Arc<VaultService>,
+        audit: Arc<AuditService>,
+    ) -> Self {
         Self {
             db,
-            adapter_registry,
+            chain_service,
+            vault,
+            audit,
+            signers: RwLock::new(HashMap::new()),
         }
     }
 
     pub async fn list_wallets(&self, chain_id: &str) -> Result<Vec<Wallet>> {
-        let rows = sqlx::query_as::<_, WalletRow>(
-            "SELECT id, chain_id, name, address, public_key, wallet_type, created_at FROM wallets WHERE chain_id = ? ORDER BY created_at DESC"
-        )
+        let rows = sqlx::query_as::<_, WalletRow>(&format!(
+            "SELECT id, chain_id, name, address, public_key, wallet_type, created_at, hd_wallet_id, derivation_path, {} FROM wallets WHERE chain_id = ? ORDER BY created_at DESC",
+            LATEST_BALANCE
+        ))
         .bind(chain_id)
         .fetch_all(&self.db)
         .await
@@ -160,9 +156,10 @@ impl WalletService {
     }
 
     pub async fn get_wallet(&self, _chain_id: &str, wallet_id: &str) -> Result<Wallet> {
-        let row = sqlx::query_as::<_, WalletRow>(
-            "SELECT id, chain_id, name, address, public_key, wallet_type, created_at FROM wallets WHERE id = ?"
-        )
+        let row = sqlx::query_as::<_, WalletRow>(&format!(
+            "SELECT id, chain_id, name, address, public_key, wallet_type, created_at, hd_wallet_id, derivation_path, {} FROM wallets WHERE id = ?",
+            LATEST_BALANCE
+        ))
         .bind(wallet_id)
         .fetch_optional(&self.db)
         .await
@@ -176,41 +173,71 @@ impl WalletService {
         &self,
         chain_id: &str,
         name: &str,
-        _wallet_type: WalletType,
+        wallet_type: WalletType,
         address: Option<&str>,
         private_key: Option<&str>,
+        // Only kept for keyless wallets; otherwise re-derived from the private key
         public_key: Option<&str>,
     ) -> Result<Wallet> {
-        // Use provided address/keys from frontend SDK, or generate random (fallback)
-        let (wallet_address, wallet_public_key) = if let (Some(addr), Some(pubkey)) = (address, public_key) {
-            (addr.to_string(), pubkey.to_string())
-        } else {
-            // Fallback: Generate random bytes (not recommended - frontend should use SDK)
-            let mut rng = rand::thread_rng();
-            let mut address_bytes = [0u8; 20];
-            let mut pubkey_bytes = [0u8; 32];
-            rng.fill(&mut address_bytes);
-            rng.fill(&mut pubkey_bytes);
-            (
-                format!("0x{}", hex::encode(address_bytes)),
-                format!("04{}", hex::encode(pubkey_bytes)),
-            )
-        };
+        let adapter = self.chain_service.get_adapter_for_chain(chain_id).await?;
+
+        match wallet_type {
+            WalletType::Watch | WalletType::Ledger => {
+                if private_key.is_some() {
+                    return Err(CocoError::Validation(format!(
+                        "A {} wallet cannot store a private key",
+                        wallet_type_to_string(&wallet_type)
+                    )));
+                }
+                let address = address.ok_or_else(|| {
+                    CocoError::Validation(format!(
+                        "An address is required for a {} wallet",
+                        wallet_type_to_string(&wallet_type)
+                    ))
+                })?;
+                return self
+                    .insert_keyless_wallet(chain_id, name, address, public_key, wallet_type, &adapter.ecosystem())
+                    .await;
+            }
+            WalletType::Hd => {
+                return Err(CocoError::Validation(
+                    "HD wallets are created with derive_next_wallet".to_string(),
+                ));
+            }
+            WalletType::Local | WalletType::Imported => {}
+        }
 
-        // Encrypt private key if provided
-        let encrypted_private_key = if let Some(pk) = private_key {
-            Some(encrypt_private_key(pk)?)
-        } else {
-            None
+        // Use the keypair from the frontend SDK when given, otherwise generate one
+        let keys = match private_key {
+            Some(pk) => {
+                let keys = adapter.import_wallet(pk).await?;
+                if let Some(addr) = address {
+                    ensure_address_matches(&adapter.ecosystem(), addr, &keys.address)?;
+                }
+                keys
+            }
+            None if address.is_some() => {
+                return Err(CocoError::Validation(
+                    "A private key is required to create a wallet from an address".to_string(),
+                ));
+            }
+            None => adapter.generate_wallet().await?,
         };
 
+        let private_key = keys.private_key.as_deref().ok_or_else(|| {
+            CocoError::Crypto("Adapter did not return a private key".to_string())
+        })?;
+        let encrypted_private_key = self.vault.encrypt(private_key)?;
+
         let wallet = Wallet {
             id: Uuid::new_v4().to_string(),
             chain_id: chain_id.to_string(),
             name: name.to_string(),
-            address: wallet_address.clone(),
-            wallet_type: WalletType::Local,
+            address: keys.address.clone(),
+            wallet_type,
             balance: None,
+            hd_wallet_id: None,
+            derivation_path: None,
             created_at: Utc::now(),
         };
 
@@ -224,8 +251,8 @@ impl WalletService {
         .bind(&wallet.chain_id)
         .bind(&wallet.name)
         .bind(&wallet.address)
-        .bind(&wallet_public_key)
-        .bind("local")
+        .bind(&keys.public_key)
+        .bind(wallet_type_to_string(&wallet.wallet_type))
         .bind(&encrypted_private_key)
         .execute(&self.db)
         .await
@@ -240,35 +267,56 @@ impl WalletService {
         name: &str,
         address: &str,
         private_key: Option<&str>,
-        _wallet_type: WalletType,
-        ecosystem: Ecosystem,
+        wallet_type: WalletType,
+        ecosystem: Option<Ecosystem>,
     ) -> Result<Wallet> {
-        // Validate address format based on ecosystem
-        validate_address(address, &ecosystem)?;
-
-        // Generate random bytes first (before any awaits to avoid Send issues)
-        let public_key = {
-            let mut rng = rand::thread_rng();
-            let mut pubkey_bytes = [0u8; 32];
-            rng.fill(&mut pubkey_bytes);
-            format!("04{}", hex::encode(pubkey_bytes))
+        let adapter = self.chain_service.get_adapter_for_chain(chain_id).await?;
+        let chain_ecosystem = adapter.ecosystem();
+        if ecosystem.is_some_and(|e| e != chain_ecosystem) {
+            return Err(CocoError::Validation(format!(
+                "Wallet ecosystem does not match chain {}",
+                chain_id
+            )));
+        }
+
+        // Without a key the wallet is watch-only, or signs through a connected Ledger
+        let Some(private_key) = private_key else {
+            let wallet_type = match wallet_type {
+                WalletType::Ledger => WalletType::Ledger,
+                _ => WalletType::Watch,
+            };
+            return self
+                .insert_keyless_wallet(chain_id, name, address, None, wallet_type, &chain_ecosystem)
+                .await;
         };
+        if matches!(wallet_type, WalletType::Watch | WalletType::Ledger) {
+            return Err(CocoError::Validation(format!(
+                "A {} wallet cannot store a private key",
+                wallet_type_to_string(&wallet_type)
+            )));
+        }
+
+        // Validate address format based on ecosystem
+        validate_address(address, &chain_ecosystem)?;
 
-        // Encrypt private key if provided
+        // Derive the real keys and make sure they belong to the supplied address.
         // For Solana, the key is base58 encoded; for EVM/Aptos, it's hex
-        let encrypted_private_key = if let Some(pk) = private_key {
-            Some(encrypt_private_key(pk)?)
-        } else {
-            None
-        };
+        let keys = adapter.import_wallet(private_key).await?;
+        ensure_address_matches(&chain_ecosystem, address, &keys.address)?;
+        let private_key = keys.private_key.as_deref().ok_or_else(|| {
+            CocoError::Crypto("Adapter did not return a private key".to_string())
+        })?;
+        let encrypted_private_key = self.vault.encrypt(private_key)?;
 
         let wallet = Wallet {
             id: Uuid::new_v4().to_string(),
             chain_id: chain_id.to_string(),
             name: name.to_string(),
-            address: address.to_string(),
+            address: keys.address.clone(),
             wallet_type: WalletType::Imported,
             balance: None,
+            hd_wallet_id: None,
+            derivation_path: None,
             created_at: Utc::now(),
         };
 
@@ -282,7 +330,7 @@ impl WalletService {
         .bind(&wallet.chain_id)
         .bind(&wallet.name)
         .bind(&wallet.address)
-        .bind(&public_key)
+        .bind(&keys.public_key)
         .bind("imported")
         .bind(&encrypted_private_key)
         .execute(&self.db)
@@ -292,6 +340,49 @@ impl WalletService {
         Ok(wallet)
     }
 
+    /// Stores a watch-only or Ledger wallet, which has an address but no stored key
+    async fn insert_keyless_wallet(
+        &self,
+        chain_id: &str,
+        name: &str,
+        address: &str,
+        public_key: Option<&str>,
+        wallet_type: WalletType,
+        ecosystem: &Ecosystem,
+    ) -> Result<Wallet> {
+        validate_address(address, ecosystem)?;
+
+        let wallet = Wallet {
+            id: Uuid::new_v4().to_string(),
+            chain_id: chain_id.to_string(),
+            name: name.to_string(),
+            address: address.trim().to_string(),
+            wallet_type,
+            balance: None,
+            hd_wallet_id: None,
+            derivation_path: None,
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallets (id, chain_id, name, address, public_key, wallet_type)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&wallet.id)
+        .bind(&wallet.chain_id)
+        .bind(&wallet.name)
+        .bind(&wallet.address)
+        .bind(public_key.unwrap_or_default())
+        .bind(wallet_type_to_string(&wallet.wallet_type))
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(wallet)
+    }
+
     /// Get the decrypted private key for a wallet
     pub async fn get_wallet_private_key(&self, wallet_id: &str) -> Result<String> {
         let row: Option<(Option<Vec<u8>>,)> = sqlx::query_as(
@@ -303,7 +394,7 @@ impl WalletService {
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
         match row {
-            Some((Some(encrypted),)) => decrypt_private_key(&encrypted),
+            Some((Some(encrypted),)) => self.vault.decrypt(&encrypted),
             Some((None,)) => Err(CocoError::NotFound(
                 "Wallet does not have a stored private key".to_string(),
             )),
@@ -314,6 +405,250 @@ impl WalletService {
         }
     }
 
+    /// Resolves what signs for a wallet: its stored key, or the external signer
+    /// connected for a Ledger wallet. Watch-only wallets can't sign.
+    pub async fn signer(&self, wallet_id: &str) -> Result<Arc<dyn ExternalSigner>> {
+        let row = sqlx::query_as::<_, SigningRow>(
+            "SELECT chain_id, address, wallet_type, encrypted_private_key FROM wallets WHERE id = ?",
+        )
+        .bind(wallet_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?
+        .ok_or_else(|| CocoError::NotFound(format!("Wallet not found: {}", wallet_id)))?;
+
+        if let Some(encrypted) = &row.encrypted_private_key {
+            let adapter = self.chain_service.get_adapter_for_chain(&row.chain_id).await?;
+            let private_key = self.vault.decrypt(encrypted)?;
+            return Ok(Arc::new(SoftwareSigner::new(adapter.ecosystem(), &private_key)?));
+        }
+
+        if let Some(signer) = self.signers.read().unwrap_or_else(|e| e.into_inner()).get(wallet_id) {
+            return Ok(signer.clone());
+        }
+
+        match string_to_wallet_type(&row.wallet_type) {
+            WalletType::Ledger => Err(CocoError::Validation(format!(
+                "No signer is connected for Ledger wallet {}",
+                row.address
+            ))),
+            _ => Err(CocoError::Validation(format!(
+                "Wallet {} is watch-only and cannot sign",
+                row.address
+            ))),
+        }
+    }
+
+    /// Connects an external signer to a Ledger wallet; its address must match the wallet's
+    pub async fn attach_signer(&self, wallet_id: &str, signer: Arc<dyn ExternalSigner>) -> Result<()> {
+        let wallet = self.get_wallet("", wallet_id).await?;
+        if wallet.wallet_type != WalletType::Ledger {
+            return Err(CocoError::Validation(format!(
+                "Wallet {} does not use an external signer",
+                wallet.address
+            )));
+        }
+
+        let adapter = self.chain_service.get_adapter_for_chain(&wallet.chain_id).await?;
+        ensure_address_matches(&adapter.ecosystem(), &wallet.address, &signer.address())?;
+
+        // Ledger wallets added by address alone learn their public key here
+        sqlx::query("UPDATE wallets SET public_key = ? WHERE id = ? AND public_key = ''")
+            .bind(signer.public_key())
+            .bind(wallet_id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        self.signers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(wallet_id.to_string(), signer);
+        Ok(())
+    }
+
+    /// Disconnects a wallet's external signer, if any
+    pub fn detach_signer(&self, wallet_id: &str) {
+        self.signers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(wallet_id);
+    }
+
+    /// Signs a message with the wallet's key or connected signer
+    pub async fn sign_message(&self, wallet_id: &str, message: &[u8]) -> Result<Vec<u8>> {
+        self.signer(wallet_id).await?.sign_message(message).await
+    }
+
+    /// Writes the chain's wallets, or the selected ones, to a passphrase-encrypted
+    /// keystore bundle. Returns how many wallets were exported.
+    pub async fn export_wallets(
+        &self,
+        path: &Path,
+        passphrase: &str,
+        chain_id: Option<&str>,
+        wallet_ids: Option<&[String]>,
+    ) -> Result<usize> {
+        if passphrase.chars().count() < 8 {
+            return Err(CocoError::Validation(
+                "Passphrase must be at least 8 characters".to_string(),
+            ));
+        }
+
+        let mut conditions = Vec::new();
+        if chain_id.is_some() {
+            conditions.push("chain_id = ?".to_string());
+        }
+        if let Some(ids) = wallet_ids {
+            conditions.push(format!("id IN ({})", vec!["?"; ids.len()].join(", ")));
+        }
+        if conditions.is_empty() {
+            return Err(CocoError::Validation(
+                "Select a chain or wallets to export".to_string(),
+            ));
+        }
+
+        let query = format!(
+            "SELECT id, chain_id, name, address, wallet_type, encrypted_private_key FROM wallets WHERE {} ORDER BY created_at",
+            conditions.join(" AND ")
+        );
+        let mut query_builder = sqlx::query_as::<_, ExportRow>(&query);
+        if let Some(chain_id) = chain_id {
+            query_builder = query_builder.bind(chain_id);
+        }
+        for id in wallet_ids.unwrap_or_default() {
+            query_builder = query_builder.bind(id);
+        }
+        let rows = query_builder
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        let mut ecosystems: HashMap<String, Ecosystem> = HashMap::new();
+        let mut wallets = Vec::with_capacity(rows.len());
+        let mut revealed = Vec::new();
+        for row in rows {
+            let ecosystem = match ecosystems.get(&row.chain_id) {
+                Some(ecosystem) => *ecosystem,
+                None => {
+                    let ecosystem = self.chain_service.get_chain(&row.chain_id).await?.ecosystem;
+                    ecosystems.insert(row.chain_id.clone(), ecosystem);
+                    ecosystem
+                }
+            };
+
+            let keystore = match &row.encrypted_private_key {
+                Some(encrypted) => {
+                    let seed = secret_seed(&ecosystem, &self.vault.decrypt(encrypted)?)?;
+                    let (address, passphrase) = (row.address.clone(), passphrase.to_string());
+                    revealed.push(row.id.clone());
+                    Some(
+                        run_kdf(move || keystore::encrypt_key(&seed, &address, &ecosystem, &passphrase))
+                            .await?,
+                    )
+                }
+                None => None,
+            };
+
+            wallets.push(BundledWallet {
+                name: row.name,
+                chain_id: row.chain_id,
+                ecosystem,
+                address: row.address,
+                // Derived wallets lose their HD link once exported
+                wallet_type: match string_to_wallet_type(&row.wallet_type) {
+                    WalletType::Hd => WalletType::Imported,
+                    other => other,
+                },
+                keystore,
+            });
+        }
+
+        // The keys leave the vault with the file, so the export goes on record first
+        for wallet_id in &revealed {
+            self.audit.record_wallet_key_reveal(wallet_id, "export_wallets").await?;
+        }
+
+        let count = wallets.len();
+        std::fs::write(path, serde_json::to_vec_pretty(&WalletBundle::new(wallets))?)?;
+        Ok(count)
+    }
+
+    /// Restores wallets from a bundle written by `export_wallets`, keeping their
+    /// names and chains. Wallets whose address already exists on the chain, or
+    /// whose chain doesn't exist here, are skipped.
+    pub async fn import_wallet_bundle(&self, path: &Path, passphrase: &str) -> Result<WalletBundleImport> {
+        let bundle: WalletBundle = serde_json::from_slice(&std::fs::read(path)?)?;
+        if bundle.version > keystore::BUNDLE_VERSION {
+            return Err(CocoError::Validation(format!(
+                "Unsupported wallet bundle version: {}",
+                bundle.version
+            )));
+        }
+
+        // Open every keystore first so a wrong passphrase imports nothing
+        let mut entries = Vec::with_capacity(bundle.wallets.len());
+        for wallet in bundle.wallets {
+            let private_key = match &wallet.keystore {
+                Some(keystore) => {
+                    let (keystore, passphrase) = (keystore.clone(), passphrase.to_string());
+                    let secret = run_kdf(move || keystore::decrypt_key(&keystore, &passphrase)).await?;
+                    Some(format!("0x{}", hex::encode(secret)))
+                }
+                None => None,
+            };
+            entries.push((wallet, private_key));
+        }
+
+        let mut report = WalletBundleImport::default();
+        for (wallet, private_key) in entries {
+            let skip = |reason: String| SkippedWallet {
+                name: wallet.name.clone(),
+                chain_id: wallet.chain_id.clone(),
+                address: wallet.address.clone(),
+                reason,
+            };
+
+            let chain = match self.chain_service.get_chain(&wallet.chain_id).await {
+                Ok(chain) => chain,
+                Err(CocoError::NotFound(_)) => {
+                    report.skipped.push(skip(format!("Chain {} does not exist", wallet.chain_id)));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let existing: Vec<(String,)> = sqlx::query_as("SELECT address FROM wallets WHERE chain_id = ?")
+                .bind(&chain.id)
+                .fetch_all(&self.db)
+                .await
+                .map_err(|e| CocoError::Database(e.to_string()))?;
+            if existing.iter().any(|(a,)| addresses_match(&chain.ecosystem, a, &wallet.address)) {
+                report.skipped.push(skip("A wallet with this address already exists".to_string()));
+                continue;
+            }
+
+            let wallet_type = if private_key.is_some() { WalletType::Imported } else { wallet.wallet_type };
+            match self
+                .import_wallet(
+                    &chain.id,
+                    &wallet.name,
+                    &wallet.address,
+                    private_key.as_deref(),
+                    wallet_type,
+                    Some(wallet.ecosystem),
+                )
+                .await
+            {
+                Ok(imported) => report.imported.push(imported),
+                Err(CocoError::Validation(reason)) => report.skipped.push(skip(reason)),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(report)
+    }
+
     pub async fn update_wallet(
         &self,
         _chain_id: &str,
@@ -351,15 +686,184 @@ impl WalletService {
             )));
         }
 
+        self.detach_signer(wallet_id);
         Ok(())
     }
 
+    /// Stores a BIP-39 mnemonic, encrypted under the vault key, to derive wallets from
+    pub async fn import_hd_wallet(&self, name: &str, mnemonic: &str) -> Result<HdWallet> {
+        // Validates the phrase and its checksum
+        derivation::mnemonic_to_seed(mnemonic)?;
+        let phrase = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let hd_wallet = HdWallet {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            "INSERT INTO hd_wallets (id, name, encrypted_mnemonic, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&hd_wallet.id)
+        .bind(&hd_wallet.name)
+        .bind(self.vault.encrypt(&phrase)?)
+        .bind(hd_wallet.created_at.to_rfc3339())
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(hd_wallet)
+    }
+
+    pub async fn list_hd_wallets(&self) -> Result<Vec<HdWallet>> {
+        let rows = sqlx::query_as::<_, HdWalletRow>(
+            "SELECT id, name, created_at FROM hd_wallets ORDER BY created_at"
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(HdWallet::from).collect())
+    }
+
+    /// Derives the next unused account of an HD wallet on a chain and stores it
+    /// as a linked wallet. Indexes count up from 0 per chain, so the same
+    /// sequence of calls recreates the same accounts from the same phrase.
+    pub async fn derive_next_wallet(
+        &self,
+        hd_wallet_id: &str,
+        chain_id: &str,
+        name: &str,
+    ) -> Result<Wallet> {
+        let row: Option<(Vec<u8>,)> = sqlx::query_as(
+            "SELECT encrypted_mnemonic FROM hd_wallets WHERE id = ?"
+        )
+        .bind(hd_wallet_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        let (encrypted_mnemonic,) = row
+            .ok_or_else(|| CocoError::NotFound(format!("HD wallet not found: {}", hd_wallet_id)))?;
+        let mnemonic = self.vault.decrypt(&encrypted_mnemonic)?;
+
+        let (next_index,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(derivation_index) + 1, 0) FROM wallets WHERE hd_wallet_id = ? AND chain_id = ?"
+        )
+        .bind(hd_wallet_id)
+        .bind(chain_id)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        let chain = self.chain_service.get_chain(chain_id).await?;
+        let path = derivation::hd_path(&chain.ecosystem, next_index as u32);
+        let adapter = self.chain_service.get_adapter_for_chain(chain_id).await?;
+        let derived = adapter.import_from_mnemonic(&mnemonic, Some(&path)).await?;
+
+        let private_key = derived.private_key.as_deref().ok_or_else(|| {
+            CocoError::Crypto("Adapter did not return a derived private key".to_string())
+        })?;
+
+        let wallet = Wallet {
+            id: Uuid::new_v4().to_string(),
+            chain_id: chain_id.to_string(),
+            name: name.to_string(),
+            address: derived.address.clone(),
+            wallet_type: WalletType::Hd,
+            balance: None,
+            hd_wallet_id: Some(hd_wallet_id.to_string()),
+            derivation_path: Some(path),
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallets (id, chain_id, name, address, public_key, wallet_type, encrypted_private_key, hd_wallet_id, derivation_path, derivation_index)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&wallet.id)
+        .bind(&wallet.chain_id)
+        .bind(&wallet.name)
+        .bind(&wallet.address)
+        .bind(&derived.public_key)
+        .bind("hd")
+        .bind(self.vault.encrypt(private_key)?)
+        .bind(hd_wallet_id)
+        .bind(&wallet.derivation_path)
+        .bind(next_index)
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(wallet)
+    }
+
+    /// Queries the wallet's chain for its current balances and records a snapshot
     pub async fn refresh_balance(&self, chain_id: &str, wallet_id: &str) -> Result<Wallet> {
-        let _registry = self.adapter_registry.read().await;
+        let wallet = self.get_wallet(chain_id, wallet_id).await?;
+        let adapter = self.chain_service.get_adapter_for_chain(&wallet.chain_id).await?;
+
+        let balance = adapter.get_balance(&wallet.address).await?;
+        // Token lookups aren't supported everywhere; a native-only snapshot is still useful
+        let tokens = adapter
+            .get_token_balances(&wallet.address)
+            .await
+            .unwrap_or_default();
 
-        // In production, this would query the chain adapter for actual balance
-        // For now, return the wallet as-is
-        self.get_wallet(chain_id, wallet_id).await
+        let tokens_json = serde_json::to_string(&tokens)
+            .map_err(|e| CocoError::Serialization(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_balances (id, wallet_id, chain_id, native, native_decimals, native_symbol, tokens, recorded_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&wallet.id)
+        .bind(&wallet.chain_id)
+        .bind(&balance.native)
+        .bind(balance.native_decimals as i32)
+        .bind(&balance.native_symbol)
+        .bind(&tokens_json)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(Wallet {
+            balance: Some(balance.native),
+            ..wallet
+        })
+    }
+
+    /// Lists recorded balance snapshots for a wallet, newest first
+    pub async fn get_balance_history(
+        &self,
+        wallet_id: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<WalletBalanceSnapshot>> {
+        let rows = sqlx::query_as::<_, WalletBalanceRow>(
+            r#"
+            SELECT id, wallet_id, chain_id, native, native_decimals, native_symbol, tokens, recorded_at
+            FROM wallet_balances
+            WHERE wallet_id = ?
+            -- Snapshots are rfc3339 but the column default is SQLite's format, which
+            -- doesn't sort with it as text; same-instant snapshots keep insertion order
+            ORDER BY julianday(recorded_at) DESC, rowid DESC
+            LIMIT ?
+            "#
+        )
+        .bind(wallet_id)
+        .bind(limit.map(|l| l as i64).unwrap_or(-1))
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(WalletBalanceSnapshot::from).collect())
     }
 }
 
@@ -374,6 +878,12 @@ struct WalletRow {
     public_key: String,
     wallet_type: String,
     created_at: String,
+    #[sqlx(default)]
+    hd_wallet_id: Option<String>,
+    #[sqlx(default)]
+    derivation_path: Option<String>,
+    #[sqlx(default)]
+    balance: Option<String>,
 }
 
 impl From<WalletRow> for Wallet {
@@ -384,7 +894,9 @@ impl From<WalletRow> for Wallet {
             name: row.name,
             address: row.address,
             wallet_type: string_to_wallet_type(&row.wallet_type),
-            balance: None,
+            balance: row.balance,
+            hd_wallet_id: row.hd_wallet_id,
+            derivation_path: row.derivation_path,
             created_at: row
                 .created_at
                 .parse::<DateTime<Utc>>()
@@ -393,14 +905,119 @@ impl From<WalletRow> for Wallet {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct HdWalletRow {
+    id: String,
+    name: String,
+    created_at: String,
+}
+
+impl From<HdWalletRow> for HdWallet {
+    fn from(row: HdWalletRow) -> Self {
+        HdWallet {
+            id: row.id,
+            name: row.name,
+            created_at: row
+                .created_at
+                .parse::<DateTime<Utc>>()
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WalletBalanceRow {
+    id: String,
+    wallet_id: String,
+    chain_id: String,
+    native: String,
+    native_decimals: i32,
+    native_symbol: String,
+    tokens: Option<String>,
+    recorded_at: String,
+}
+
+impl From<WalletBalanceRow> for WalletBalanceSnapshot {
+    fn from(row: WalletBalanceRow) -> Self {
+        let tokens: Vec<TokenBalance> = row
+            .tokens
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+        WalletBalanceSnapshot {
+            id: row.id,
+            wallet_id: row.wallet_id,
+            chain_id: row.chain_id,
+            native: row.native,
+            native_decimals: row.native_decimals as u8,
+            native_symbol: row.native_symbol,
+            tokens,
+            recorded_at: row
+                .recorded_at
+                .parse::<DateTime<Utc>>()
+                .or_else(|_| {
+                    chrono::NaiveDateTime::parse_from_str(&row.recorded_at, "%Y-%m-%d %H:%M:%S").map(|t| t.and_utc())
+                })
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+fn wallet_type_to_string(wallet_type: &WalletType) -> &'static str {
+    match wallet_type {
+        WalletType::Local => "local",
+        WalletType::Imported => "imported",
+        WalletType::Hd => "hd",
+        WalletType::Ledger => "ledger",
+        WalletType::Watch => "watch",
+    }
+}
+
 fn string_to_wallet_type(s: &str) -> WalletType {
     match s {
         "imported" => WalletType::Imported,
+        "hd" => WalletType::Hd,
         "ledger" => WalletType::Ledger,
+        "watch" => WalletType::Watch,
         _ => WalletType::Local,
     }
 }
 
+/// The 32-byte secret a stored private key encodes, as sealed in keystores
+fn secret_seed(ecosystem: &Ecosystem, private_key: &str) -> Result<[u8; 32]> {
+    Ok(match ecosystem {
+        Ecosystem::Evm => evm::parse_private_key(private_key)?.signer().to_bytes().into(),
+        Ecosystem::Solana => solana::parse_secret_key(private_key)?.to_bytes(),
+        Ecosystem::Aptos => aptos::parse_private_key(private_key)?.to_bytes(),
+    })
+}
+
+/// Runs a keystore operation on the blocking pool: scrypt at geth's cost takes
+/// around a second per wallet and would stall the async runtime
+async fn run_kdf<T: Send + 'static>(operation: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| CocoError::Crypto(format!("Key derivation failed: {}", e)))?
+}
+
+#[derive(sqlx::FromRow)]
+struct ExportRow {
+    id: String,
+    chain_id: String,
+    name: String,
+    address: String,
+    wallet_type: String,
+    encrypted_private_key: Option<Vec<u8>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SigningRow {
+    chain_id: String,
+    address: String,
+    wallet_type: String,
+    encrypted_private_key: Option<Vec<u8>>,
+}
+
 // Helper struct for wallet with chain info
 #[derive(sqlx::FromRow)]
 struct WalletWithChainRow {
@@ -432,3 +1049,259 @@ impl From<WalletWithChainRow> for WalletWithChain {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mock::mock_adapter;
+    use crate::types::SecretAccessFilter;
+
+    #[test]
+    fn test_address_match_normalization() {
+        let evm = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert!(ensure_address_matches(&Ecosystem::Evm, &evm.to_lowercase(), evm).is_ok());
+        assert!(ensure_address_matches(&Ecosystem::Evm, "0x0000000000000000000000000000000000000001", evm).is_err());
+
+        let aptos = format!("0x{}1", "0".repeat(63));
+        assert!(ensure_address_matches(&Ecosystem::Aptos, "0x1", &aptos).is_ok());
+
+        let solana = "11111111111111111111111111111111";
+        assert!(ensure_address_matches(&Ecosystem::Solana, solana, solana).is_ok());
+        assert!(ensure_address_matches(&Ecosystem::Solana, &solana.to_lowercase(), "1111111111111111111111111111111A").is_err());
+    }
+
+    /// Hardhat's first dev account
+    const DEV_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const DEV_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    /// Wallet service over the real chain adapters, with an unlocked vault
+    async fn wallet_service(db: &DbPool) -> WalletService {
+        let registry = Arc::new(tokio::sync::RwLock::new(crate::adapters::AdapterRegistry::new()));
+        let chains = Arc::new(ChainService::new(db.clone(), registry));
+        let vault = Arc::new(VaultService::new(db.clone()));
+        vault.setup("correct horse").await.unwrap();
+        let audit = Arc::new(AuditService::new(db.clone()));
+        WalletService::new(db.clone(), chains, vault, audit)
+    }
+
+    async fn stored_keys(db: &DbPool, wallet_id: &str) -> (String, String, bool) {
+        let (address, public_key, key): (String, String, Option<Vec<u8>>) = sqlx::query_as(
+            "SELECT address, public_key, encrypted_private_key FROM wallets WHERE id = ?",
+        )
+        .bind(wallet_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+        (address, public_key, key.is_some())
+    }
+
+    #[tokio::test]
+    async fn test_imported_key_stores_derived_address_and_public_key() {
+        let db = crate::db::test_db().await;
+        let wallets = wallet_service(&db).await;
+        let derived = wallets
+            .chain_service
+            .get_adapter_for_chain("ethereum-sepolia")
+            .await
+            .unwrap()
+            .import_wallet(DEV_KEY)
+            .await
+            .unwrap();
+        assert!(!derived.public_key.is_empty());
+
+        // A lowercase address is accepted, and the checksummed one is stored
+        let wallet = wallets
+            .import_wallet("ethereum-sepolia", "Dev", &DEV_ADDRESS.to_lowercase(), Some(DEV_KEY), WalletType::Imported, None)
+            .await
+            .unwrap();
+        assert_eq!(wallet.address, DEV_ADDRESS);
+        assert_eq!(wallet.wallet_type, WalletType::Imported);
+        assert_eq!(stored_keys(&db, &wallet.id).await, (DEV_ADDRESS.to_string(), derived.public_key, true));
+        assert_eq!(wallets.get_wallet_private_key(&wallet.id).await.unwrap(), DEV_KEY);
+    }
+
+    #[tokio::test]
+    async fn test_key_for_another_address_is_rejected() {
+        let db = crate::db::test_db().await;
+        let wallets = wallet_service(&db).await;
+        let before = wallets.list_wallets("ethereum-sepolia").await.unwrap().len();
+        let other = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+        let imported = wallets
+            .import_wallet("ethereum-sepolia", "Dev", other, Some(DEV_KEY), WalletType::Imported, None)
+            .await;
+        assert!(matches!(imported, Err(CocoError::Validation(ref m)) if m.contains("does not match")));
+        let created = wallets
+            .create_wallet("ethereum-sepolia", "Dev", WalletType::Imported, Some(other), Some(DEV_KEY), None)
+            .await;
+        assert!(matches!(created, Err(CocoError::Validation(ref m)) if m.contains("does not match")));
+
+        assert_eq!(wallets.list_wallets("ethereum-sepolia").await.unwrap().len(), before);
+    }
+
+    #[tokio::test]
+    async fn test_create_wallet_never_stores_an_address_without_its_key() {
+        let db = crate::db::test_db().await;
+        let wallets = wallet_service(&db).await;
+        let before = wallets.list_wallets("ethereum-sepolia").await.unwrap().len();
+
+        for wallet_type in [WalletType::Local, WalletType::Imported] {
+            let created = wallets
+                .create_wallet("ethereum-sepolia", "Dev", wallet_type, Some(DEV_ADDRESS), None, None)
+                .await;
+            assert!(matches!(created, Err(CocoError::Validation(ref m)) if m.contains("private key is required")));
+        }
+        assert_eq!(wallets.list_wallets("ethereum-sepolia").await.unwrap().len(), before);
+
+        // With the key, the stored address is the derived one
+        let wallet = wallets
+            .create_wallet("ethereum-sepolia", "Dev", WalletType::Imported, None, Some(DEV_KEY), None)
+            .await
+            .unwrap();
+        assert_eq!(stored_keys(&db, &wallet.id).await.0, DEV_ADDRESS);
+        assert!(stored_keys(&db, &wallet.id).await.2);
+
+        // Without either, a fresh keypair is generated and its key kept
+        let generated = wallets
+            .create_wallet("ethereum-sepolia", "Fresh", WalletType::Local, None, None, None)
+            .await
+            .unwrap();
+        assert!(stored_keys(&db, &generated.id).await.2);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_bundle_round_trips_into_a_fresh_database() {
+        let db = crate::db::test_db().await;
+        let wallets = wallet_service(&db).await;
+        let second_key = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let first = wallets
+            .import_wallet("ethereum-sepolia", "Dev 0", DEV_ADDRESS, Some(DEV_KEY), WalletType::Imported, None)
+            .await
+            .unwrap();
+        let second = wallets
+            .create_wallet("ethereum-sepolia", "Dev 1", WalletType::Local, None, Some(second_key), None)
+            .await
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("coco-bundle-{}.json", Uuid::new_v4()));
+        let ids = vec![first.id.clone(), second.id.clone(), "wallet-1".to_string()];
+        assert_eq!(wallets.export_wallets(&path, "bundle passphrase", None, Some(&ids)).await.unwrap(), 3);
+
+        // One reveal per exported key; the watch-only seed wallet has none
+        let reveals = wallets
+            .audit
+            .list(&SecretAccessFilter { command: Some("export_wallets".to_string()), ..Default::default() })
+            .await
+            .unwrap();
+        let mut revealed: Vec<_> = reveals.iter().filter_map(|e| e.wallet_id.clone()).collect();
+        revealed.sort();
+        let mut expected = vec![first.id.clone(), second.id.clone()];
+        expected.sort();
+        assert_eq!(revealed, expected);
+
+        // A wrong passphrase imports nothing
+        let fresh_db = crate::db::test_db().await;
+        let fresh = wallet_service(&fresh_db).await;
+        let before = fresh.list_wallets("ethereum-sepolia").await.unwrap().len();
+        assert!(fresh.import_wallet_bundle(&path, "not the passphrase").await.is_err());
+        assert_eq!(fresh.list_wallets("ethereum-sepolia").await.unwrap().len(), before);
+
+        // The fresh database already has the seeded watch wallet, so only the keys are imported
+        let report = fresh.import_wallet_bundle(&path, "bundle passphrase").await.unwrap();
+        let mut imported: Vec<_> = report.imported.iter().map(|w| (w.name.as_str(), w.address.as_str())).collect();
+        imported.sort();
+        assert_eq!(imported, vec![("Dev 0", DEV_ADDRESS), ("Dev 1", second.address.as_str())]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].address, "0x742d35Cc6634C0532925a3b844Bc454e4438f44E");
+        for wallet in &report.imported {
+            assert_eq!(wallet.wallet_type, WalletType::Imported);
+            assert!(stored_keys(&fresh_db, &wallet.id).await.2);
+        }
+        let restored = report.imported.iter().find(|w| w.name == "Dev 1").unwrap();
+        assert_eq!(fresh.get_wallet_private_key(&restored.id).await.unwrap(), second_key);
+
+        // Importing again skips every wallet as a duplicate
+        let again = fresh.import_wallet_bundle(&path, "bundle passphrase").await.unwrap();
+        assert!(again.imported.is_empty());
+        assert_eq!(again.skipped.len(), 3);
+        assert!(again.skipped.iter().all(|s| s.reason.contains("already exists")));
+        assert_eq!(fresh.list_wallets("ethereum-sepolia").await.unwrap().len(), before + 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refreshed_balances_become_history_newest_first() {
+        let db = crate::db::test_db().await;
+        let adapter = Arc::new(mock_adapter());
+        let registry = crate::adapters::AdapterRegistry::new();
+        registry.insert("ethereum-sepolia", adapter.clone()).await;
+        let chains = Arc::new(ChainService::new(db.clone(), Arc::new(tokio::sync::RwLock::new(registry))));
+        let vault = Arc::new(VaultService::new(db.clone()));
+        let audit = Arc::new(AuditService::new(db.clone()));
+        let wallets = WalletService::new(db.clone(), chains, vault, audit);
+        let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        let wallet = wallets
+            .import_wallet("ethereum-sepolia", "Watched", address, None, WalletType::Watch, None)
+            .await
+            .unwrap();
+
+        // Old snapshots in both formats: the later one uses the column default's
+        // format, which sorts before the earlier one as text
+        for (native, recorded_at) in [("1", "2020-01-01T09:00:00+00:00"), ("2", "2020-01-01 10:00:00")] {
+            sqlx::query(
+                "INSERT INTO wallet_balances (id, wallet_id, chain_id, native, native_decimals, native_symbol, recorded_at) VALUES (?, ?, 'ethereum-sepolia', ?, 18, 'ETH', ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&wallet.id)
+            .bind(native)
+            .bind(recorded_at)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let current = |wallet: Wallet| wallet.balance;
+        assert_eq!(current(wallets.get_wallet("ethereum-sepolia", &wallet.id).await.unwrap()).as_deref(), Some("2"));
+
+        adapter.seed_wallet(address, 100);
+        assert_eq!(wallets.refresh_balance("ethereum-sepolia", &wallet.id).await.unwrap().balance.as_deref(), Some("100"));
+        adapter.seed_wallet(address, 250);
+        wallets.refresh_balance("ethereum-sepolia", &wallet.id).await.unwrap();
+
+        let history = wallets.get_balance_history(&wallet.id, None).await.unwrap();
+        let natives: Vec<_> = history.iter().map(|s| s.native.as_str()).collect();
+        assert_eq!(natives, vec!["250", "100", "2", "1"]);
+        assert!(history.windows(2).all(|pair| pair[0].recorded_at >= pair[1].recorded_at));
+
+        let latest = wallets.get_balance_history(&wallet.id, Some(1)).await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].native, "250");
+
+        assert_eq!(current(wallets.get_wallet("ethereum-sepolia", &wallet.id).await.unwrap()).as_deref(), Some("250"));
+        let listed = wallets.list_wallets("ethereum-sepolia").await.unwrap();
+        let listed = listed.into_iter().find(|w| w.id == wallet.id).unwrap();
+        assert_eq!(listed.balance.as_deref(), Some("250"));
+    }
+
+    #[tokio::test]
+    async fn test_hd_wallet_derives_after_key_rotation() {
+        let db = crate::db::test_db().await;
+        let registry = Arc::new(tokio::sync::RwLock::new(crate::adapters::AdapterRegistry::new()));
+        let chains = Arc::new(ChainService::new(db.clone(), registry));
+        let vault = Arc::new(VaultService::new(db.clone()));
+        let audit = Arc::new(AuditService::new(db.clone()));
+        let wallets = WalletService::new(db, chains, vault.clone(), audit);
+
+        vault.setup("correct horse").await.unwrap();
+        let phrase = "test test test test test test test test test test test junk";
+        let hd = wallets.import_hd_wallet("Dev", phrase).await.unwrap();
+        let first = wallets.derive_next_wallet(&hd.id, "ethereum-sepolia", "Dev 0").await.unwrap();
+
+        let report = vault.rotate_encryption_key("correct horse", "battery staple").await.unwrap();
+        assert_eq!((report.wallets, report.hd_wallets), (1, 1));
+
+        let second = wallets.derive_next_wallet(&hd.id, "ethereum-sepolia", "Dev 1").await.unwrap();
+        assert_eq!(first.address, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        assert_eq!(second.address, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    }
+}
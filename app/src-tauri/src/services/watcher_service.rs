@@ -0,0 +1,195 @@
+use crate::error::{CocoError, Result};
+use crate::services::WorkspaceService;
+use crate::types::ContractSync;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+/// How long build outputs must stay unchanged before contracts are re-synced,
+/// so a build that writes hundreds of artifacts triggers one sync
+const SETTLE_DELAY: Duration = Duration::from_millis(750);
+
+/// Directories compilers write artifacts to, relative to the workspace root:
+/// Foundry, Hardhat, Move and Anchor
+const BUILD_OUTPUTS: [&str; 4] = ["out", "artifacts", "build", "target/idl"];
+
+/// Watches workspace folders for rebuilt artifacts and re-syncs their contracts.
+/// Watching is opt-in per workspace and lasts until unwatched or the app exits.
+pub struct WatcherService {
+    workspace_service: Arc<WorkspaceService>,
+    watches: Mutex<HashMap<String, Watch>>,
+}
+
+/// A running watch; dropping it stops the sync task, which owns the file watcher
+struct Watch {
+    task: JoinHandle<()>,
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl WatcherService {
+    pub fn new(workspace_service: Arc<WorkspaceService>) -> Self {
+        Self {
+            workspace_service,
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts watching a workspace, replacing any earlier watch on it. Each
+    /// re-sync after a build is sent to `syncs`; the watch ends if it's closed.
+    pub async fn watch(&self, workspace_id: &str, syncs: mpsc::Sender<ContractSync>) -> Result<()> {
+        let workspace = self.workspace_service.get_workspace(workspace_id).await?;
+        // Events carry canonical paths (e.g. /private/var on macOS)
+        let root = std::fs::canonicalize(&workspace.path)?;
+
+        let (changed_tx, mut changed_rx) = mpsc::unbounded_channel::<()>();
+        let watched_root = root.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            if event
+                .paths
+                .iter()
+                .any(|path| is_build_output(&watched_root, path) || may_hold_build_output(&watched_root, path))
+            {
+                let _ = changed_tx.send(());
+            }
+        })
+        .map_err(|e| CocoError::Io(format!("Failed to watch {}: {}", workspace.path, e)))?;
+        let mut watched = HashMap::new();
+        update_watches(&mut watcher, &root, &mut watched)
+            .map_err(|e| CocoError::Io(format!("Failed to watch {}: {}", workspace.path, e)))?;
+
+        let workspace_service = self.workspace_service.clone();
+        let id = workspace_id.to_string();
+        let task = tokio::spawn(async move {
+            while changed_rx.recv().await.is_some() {
+                while let Ok(Some(())) = tokio::time::timeout(SETTLE_DELAY, changed_rx.recv()).await {}
+                // Follow output directories the build created or a clean deleted; one
+                // that vanishes mid-update is picked up again by the next change
+                let _ = update_watches(&mut watcher, &root, &mut watched);
+
+                // A half-written artifact fails to parse; the write that finishes it syncs again
+                let Ok(sync) = workspace_service.sync_contracts(&id).await else {
+                    continue;
+                };
+                if syncs.send(sync).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.watches
+            .lock()
+            .await
+            .insert(workspace_id.to_string(), Watch { task });
+        Ok(())
+    }
+
+    /// Stops watching a workspace. Returns whether it was being watched.
+    pub async fn unwatch(&self, workspace_id: &str) -> bool {
+        self.watches.lock().await.remove(workspace_id).is_some()
+    }
+
+    pub async fn is_watching(&self, workspace_id: &str) -> bool {
+        self.watches.lock().await.contains_key(workspace_id)
+    }
+}
+
+/// Whether a changed path is inside one of the workspace's build output directories
+fn is_build_output(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root)
+        .is_ok_and(|relative| BUILD_OUTPUTS.iter().any(|dir| relative.starts_with(dir)))
+}
+
+/// Whether a changed path is a folder a build output directory would be created
+/// in, e.g. `target/` for `target/idl/`
+fn may_hold_build_output(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root).is_ok_and(|relative| {
+        !relative.as_os_str().is_empty() && BUILD_OUTPUTS.iter().any(|dir| Path::new(dir).starts_with(relative))
+    })
+}
+
+/// Directories to watch instead of the whole workspace: every build output that
+/// exists, recursively, and otherwise the folder it would appear in, on its own.
+/// The root is always watched on its own so a first build's `out/` is noticed.
+fn watch_targets(root: &Path) -> HashMap<PathBuf, RecursiveMode> {
+    let mut targets = HashMap::from([(root.to_path_buf(), RecursiveMode::NonRecursive)]);
+    for dir in BUILD_OUTPUTS {
+        let output = root.join(dir);
+        if output.is_dir() {
+            targets.insert(output, RecursiveMode::Recursive);
+        } else if let Some(parent) = output.parent().filter(|parent| parent.is_dir()) {
+            targets.entry(parent.to_path_buf()).or_insert(RecursiveMode::NonRecursive);
+        }
+    }
+    targets
+}
+
+/// Points the watcher at the current [`watch_targets`]. Existing targets are
+/// watched again since a directory deleted and rebuilt loses its watch.
+fn update_watches(
+    watcher: &mut RecommendedWatcher,
+    root: &Path,
+    watched: &mut HashMap<PathBuf, RecursiveMode>,
+) -> notify::Result<()> {
+    let targets = watch_targets(root);
+    for path in watched.keys().filter(|path| !targets.contains_key(*path)) {
+        // Fails for a directory that was deleted, which unwatched it already
+        let _ = watcher.unwatch(path);
+    }
+    watched.clear();
+    for (path, mode) in targets {
+        watcher.watch(&path, mode)?;
+        watched.insert(path, mode);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_build_outputs_trigger_sync() {
+        let root = Path::new("/work/vault");
+        assert!(is_build_output(root, &root.join("out/Vault.sol/Vault.json")));
+        assert!(is_build_output(root, &root.join("artifacts/contracts/Vault.sol/Vault.json")));
+        assert!(is_build_output(root, &root.join("target/idl/vault.json")));
+        assert!(!is_build_output(root, &root.join("src/Vault.sol")));
+        assert!(!is_build_output(root, &root.join("target/deploy/vault.so")));
+        assert!(!is_build_output(root, &root.join("output/notes.txt")));
+        assert!(!is_build_output(Path::new("/elsewhere"), &root.join("out/Vault.json")));
+
+        assert!(may_hold_build_output(root, &root.join("target")));
+        assert!(!may_hold_build_output(root, &root.join("target/deploy")));
+        assert!(!may_hold_build_output(root, root));
+    }
+
+    #[test]
+    fn test_watches_only_build_outputs() {
+        let root = std::env::temp_dir().join(format!("coco-watch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("out/Vault.sol")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target/deploy")).unwrap();
+
+        let targets = watch_targets(&root);
+        let mut paths: Vec<_> = targets.keys().map(|p| p.strip_prefix(&root).unwrap().to_path_buf()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![PathBuf::new(), PathBuf::from("out"), PathBuf::from("target")]);
+        assert!(matches!(targets[&root], RecursiveMode::NonRecursive));
+        assert!(matches!(targets[&root.join("out")], RecursiveMode::Recursive));
+        assert!(matches!(targets[&root.join("target")], RecursiveMode::NonRecursive));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,18 +1,40 @@
+use crate::abi::{self, ContractAbi};
+use crate::adapters::{ExternalSigner, FullAdapter};
+use crate::artifacts;
 use crate::db::DbPool;
 use crate::error::{CocoError, Result};
-use crate::types::{AIExplanation, Contract, ContractWithChain, DecodedEvent, InterfaceType, Transaction, TransactionRun, TransactionStatus, TxStatus, Workspace};
+use crate::services::{ChainService, WalletService};
+use crate::types::{AIExplanation, Contract, ContractArg, ContractChanges, ContractDeployment, ContractSync, ContractWithChain, DecodedEvent, DeploymentResult, DiscoveredContract, Ecosystem, InterfaceType, ReplacementKind, RunHistoryEntry, Transaction, TransactionOverrides, TransactionReceipt, TransactionRequest, TransactionResult, TransactionRun, TransactionStatus, TxStatus, Wallet, Workspace};
 use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use uuid::Uuid;
 use walkdir::WalkDir;
 
+/// How often to ask the node for a receipt after broadcasting
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait for a receipt before recording the run as pending
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
 pub struct WorkspaceService {
     db: DbPool,
+    chain_service: Arc<ChainService>,
+    wallet_service: Arc<WalletService>,
+    /// Transactions currently being executed, so a double click can't send twice
+    executing: Mutex<HashSet<String>>,
 }
 
 impl WorkspaceService {
-    pub fn new(db: DbPool) -> Self {
-        Self { db }
+    pub fn new(db: DbPool, chain_service: Arc<ChainService>, wallet_service: Arc<WalletService>) -> Self {
+        Self {
+            db,
+            chain_service,
+            wallet_service,
+            executing: Mutex::new(HashSet::new()),
+        }
     }
 
     // Workspace operations
@@ -153,7 +175,7 @@ impl WorkspaceService {
     // Contract operations
     pub async fn list_contracts(&self, workspace_id: &str) -> Result<Vec<Contract>> {
         let rows = sqlx::query_as::<_, ContractRow>(
-            "SELECT id, workspace_id, name, path, interface_type, abi, idl, move_definition, bytecode, deployed_address, created_at FROM contracts WHERE workspace_id = ? ORDER BY name"
+            "SELECT id, workspace_id, name, path, interface_type, abi, idl, move_definition, bytecode, deployed_address, compiler_version, is_interface, is_dependency, created_at FROM contracts WHERE workspace_id = ? ORDER BY name"
         )
         .bind(workspace_id)
         .fetch_all(&self.db)
@@ -185,59 +207,189 @@ impl WorkspaceService {
         Ok(rows.into_iter().map(ContractWithChain::from).collect())
     }
 
+    /// Finds the contracts in the workspace's build output with the chain's adapter
+    /// and saves them, updating the rows an earlier scan made. Before the project
+    /// is built, Solidity sources under `src/` are listed without interfaces.
     pub async fn discover_contracts(&self, workspace_id: &str) -> Result<Vec<Contract>> {
         let workspace = self.get_workspace(workspace_id).await?;
         let path = PathBuf::from(&workspace.path);
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+
+        let mut found = adapter.discover_contracts(&path).await?;
+        if found.is_empty() {
+            found = solidity_sources(&path);
+        }
 
+        let mut existing = self.list_contracts(workspace_id).await?;
         let mut discovered = Vec::new();
+        for contract in found {
+            discovered.push(
+                self.save_discovered(workspace_id, &workspace.chain_id, &mut existing, contract)
+                    .await?,
+            );
+        }
 
-        // Look for Solidity files in src/ directory (Foundry convention)
-        let src_path = path.join("src");
-        if src_path.exists() {
-            for entry in WalkDir::new(&src_path)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().map_or(false, |ext| ext == "sol"))
-            {
-                let file_name = entry
-                    .path()
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("Unknown")
-                    .to_string();
-
-                let contract = Contract {
-                    id: Uuid::new_v4().to_string(),
-                    workspace_id: workspace_id.to_string(),
-                    name: file_name,
-                    path: entry.path().to_string_lossy().to_string(),
-                    interface_type: InterfaceType::Abi, // Discovered Solidity files use ABI
-                    abi: None,
-                    idl: None,
-                    move_definition: None,
-                    bytecode: None,
-                    deployed_address: None,
-                    created_at: Utc::now(),
-                };
+        Ok(discovered)
+    }
+
+    /// Re-runs discovery and reports how each contract's ABI functions changed
+    /// from the stored version. Saved transactions whose function is gone are
+    /// flagged, and unflagged once it's back.
+    pub async fn sync_contracts(&self, workspace_id: &str) -> Result<ContractSync> {
+        let before: HashMap<String, ContractAbi> = self
+            .list_contracts(workspace_id)
+            .await?
+            .into_iter()
+            .filter_map(|c| Some((c.id, ContractAbi::parse(c.abi?.as_bytes()).ok()?)))
+            .collect();
+
+        // Newly discovered contracts diff against nothing, so all their functions are added
+        let unknown = ContractAbi::default();
+        let mut contracts = Vec::new();
+        for contract in self.discover_contracts(workspace_id).await? {
+            let Some(abi) = contract.abi.as_deref().and_then(|a| ContractAbi::parse(a.as_bytes()).ok()) else {
+                continue;
+            };
+            let functions = before.get(&contract.id).unwrap_or(&unknown).diff_functions(&abi);
+            if !functions.is_empty() {
+                contracts.push(ContractChanges {
+                    contract_id: contract.id,
+                    name: contract.name,
+                    functions,
+                });
+            }
+        }
+
+        // Contracts the scan didn't touch keep their ABI, so check against everything stored
+        let abis: HashMap<String, ContractAbi> = self
+            .list_contracts(workspace_id)
+            .await?
+            .into_iter()
+            .filter_map(|c| Some((c.id, ContractAbi::parse(c.abi?.as_bytes()).ok()?)))
+            .collect();
+
+        let mut stale_transactions = Vec::new();
+        for transaction in self.list_transactions(workspace_id).await? {
+            let (Some(contract_id), Some(function)) = (&transaction.contract_id, &transaction.function_name) else {
+                continue;
+            };
+            let Some(abi) = abis.get(contract_id) else {
+                continue;
+            };
+
+            let missing = !abi.has_function(function);
+            if missing != transaction.function_missing {
+                sqlx::query("UPDATE transactions SET function_missing = ? WHERE id = ?")
+                    .bind(missing)
+                    .bind(&transaction.id)
+                    .execute(&self.db)
+                    .await
+                    .map_err(|e| CocoError::Database(e.to_string()))?;
+            }
+            if missing {
+                stale_transactions.push(transaction.id);
+            }
+        }
+
+        Ok(ContractSync {
+            workspace_id: workspace_id.to_string(),
+            contracts,
+            stale_transactions,
+        })
+    }
 
-                // Insert into database
+    /// Saves a discovered contract over the row an earlier scan, or the user, made
+    /// for it: same name, and the same source, or else none recorded. Sources are
+    /// matched first so two same-named contracts don't both claim a pathless row.
+    /// An address the project config declares is recorded as a deployment the
+    /// first time it's seen.
+    async fn save_discovered(
+        &self,
+        workspace_id: &str,
+        chain_id: &str,
+        existing: &mut Vec<Contract>,
+        found: DiscoveredContract,
+    ) -> Result<Contract> {
+        let matched = existing
+            .iter()
+            .position(|c| c.name == found.name && !c.path.is_empty() && Path::new(&c.path).ends_with(&found.path))
+            .or_else(|| existing.iter().position(|c| c.name == found.name && c.path.is_empty()));
+
+        let id = match matched {
+            Some(index) => {
+                let contract = existing.remove(index);
                 sqlx::query(
-                    "INSERT OR IGNORE INTO contracts (id, workspace_id, name, path, interface_type) VALUES (?, ?, ?, ?, ?)"
+                    r#"
+                    UPDATE contracts
+                    SET path = ?, interface_type = ?, abi = COALESCE(?, abi), idl = COALESCE(?, idl),
+                        move_definition = COALESCE(?, move_definition), bytecode = COALESCE(?, bytecode),
+                        compiler_version = COALESCE(?, compiler_version), is_interface = ?, is_dependency = ?
+                    WHERE id = ?
+                    "#,
                 )
+                .bind(&found.path)
+                .bind(found.interface_type.to_string())
+                .bind(&found.abi)
+                .bind(&found.idl)
+                .bind(&found.move_definition)
+                .bind(&found.bytecode)
+                .bind(&found.compiler_version)
+                .bind(found.is_interface)
+                .bind(found.is_dependency)
                 .bind(&contract.id)
-                .bind(&contract.workspace_id)
-                .bind(&contract.name)
-                .bind(&contract.path)
-                .bind(contract.interface_type.to_string())
                 .execute(&self.db)
                 .await
                 .map_err(|e| CocoError::Database(e.to_string()))?;
+                contract.id
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                sqlx::query(
+                    r#"
+                    INSERT INTO contracts (
+                        id, workspace_id, name, path, interface_type, abi, idl, move_definition,
+                        bytecode, compiler_version, is_interface, is_dependency, created_at
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&id)
+                .bind(workspace_id)
+                .bind(&found.name)
+                .bind(&found.path)
+                .bind(found.interface_type.to_string())
+                .bind(&found.abi)
+                .bind(&found.idl)
+                .bind(&found.move_definition)
+                .bind(&found.bytecode)
+                .bind(&found.compiler_version)
+                .bind(found.is_interface)
+                .bind(found.is_dependency)
+                .bind(Utc::now().to_rfc3339())
+                .execute(&self.db)
+                .await
+                .map_err(|e| CocoError::Database(e.to_string()))?;
+                id
+            }
+        };
 
-                discovered.push(contract);
+        // Leave an already known address alone so rescans don't undo a switch the user made
+        if let Some(address) = found.deployed_address.as_deref() {
+            let known: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM contract_deployments WHERE contract_id = ? AND chain_id = ? AND address = ?",
+            )
+            .bind(&id)
+            .bind(chain_id)
+            .bind(address)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+            if known.is_none() {
+                self.record_deployment(&manual_deployment(&id, chain_id, address)).await?;
             }
         }
 
-        Ok(discovered)
+        self.get_contract(&id).await
     }
 
     pub async fn add_contract(
@@ -264,6 +416,9 @@ impl WorkspaceService {
             move_definition: move_definition.map(|s| s.to_string()),
             bytecode: None,
             deployed_address: address.map(|s| s.to_string()),
+            compiler_version: None,
+            is_interface: false,
+            is_dependency: false,
             created_at: Utc::now(),
         };
 
@@ -287,6 +442,12 @@ impl WorkspaceService {
         .await
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
+        if let Some(address) = contract.deployed_address.as_deref().filter(|a| !a.is_empty()) {
+            let workspace = self.get_workspace(workspace_id).await?;
+            self.record_deployment(&manual_deployment(&contract.id, &workspace.chain_id, address))
+                .await?;
+        }
+
         Ok(contract)
     }
 
@@ -319,7 +480,7 @@ impl WorkspaceService {
     ) -> Result<Contract> {
         // Get the existing contract to preserve workspace_id and other fields
         let rows = sqlx::query_as::<_, ContractRow>(
-            "SELECT id, workspace_id, name, path, interface_type, abi, idl, move_definition, bytecode, deployed_address, created_at FROM contracts WHERE id = ?"
+            "SELECT id, workspace_id, name, path, interface_type, abi, idl, move_definition, bytecode, deployed_address, compiler_version, is_interface, is_dependency, created_at FROM contracts WHERE id = ?"
         )
         .bind(contract_id)
         .fetch_optional(&self.db)
@@ -327,6 +488,7 @@ impl WorkspaceService {
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
         let existing = rows.ok_or_else(|| CocoError::NotFound(format!("Contract not found: {}", contract_id)))?;
+        let address = address.filter(|a| !a.is_empty());
 
         let result = sqlx::query(
             r#"
@@ -353,6 +515,18 @@ impl WorkspaceService {
             )));
         }
 
+        // An address entered by hand becomes the active deployment on the workspace's network
+        let workspace = self.get_workspace(&existing.workspace_id).await?;
+        if address != existing.deployed_address.as_deref() {
+            match address {
+                Some(address) => {
+                    self.record_deployment(&manual_deployment(contract_id, &workspace.chain_id, address))
+                        .await?
+                }
+                None => self.set_active_deployment(contract_id, &workspace.chain_id, None).await?,
+            }
+        }
+
         // Return the updated contract
         Ok(Contract {
             id: contract_id.to_string(),
@@ -365,6 +539,9 @@ impl WorkspaceService {
             move_definition: move_definition.map(|s| s.to_string()),
             bytecode: existing.bytecode,
             deployed_address: address.map(|s| s.to_string()),
+            compiler_version: existing.compiler_version,
+            is_interface: existing.is_interface,
+            is_dependency: existing.is_dependency,
             created_at: existing.created_at.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now()),
         })
     }
@@ -372,7 +549,7 @@ impl WorkspaceService {
     // Transaction operations
     pub async fn list_transactions(&self, workspace_id: &str) -> Result<Vec<Transaction>> {
         let rows = sqlx::query_as::<_, TransactionRow>(
-            "SELECT id, workspace_id, name, contract_id, function_name, args, value, status, tx_hash, created_at, executed_at FROM transactions WHERE workspace_id = ? ORDER BY created_at DESC"
+            "SELECT id, workspace_id, name, contract_id, function_name, args, value, overrides, status, tx_hash, created_at, executed_at, function_missing FROM transactions WHERE workspace_id = ? ORDER BY created_at DESC"
         )
         .bind(workspace_id)
         .fetch_all(&self.db)
@@ -400,10 +577,12 @@ impl WorkspaceService {
             function_name: function_name.map(|s| s.to_string()),
             args: vec![],
             value: None,
+            overrides: Default::default(),
             status: TransactionStatus::Draft,
             tx_hash: None,
             created_at: Utc::now(),
             executed_at: None,
+            function_missing: false,
         };
 
         sqlx::query(
@@ -427,7 +606,7 @@ impl WorkspaceService {
 
     pub async fn get_transaction(&self, transaction_id: &str) -> Result<Transaction> {
         let row = sqlx::query_as::<_, TransactionRow>(
-            "SELECT id, workspace_id, name, contract_id, function_name, args, value, status, tx_hash, created_at, executed_at FROM transactions WHERE id = ?"
+            "SELECT id, workspace_id, name, contract_id, function_name, args, value, overrides, status, tx_hash, created_at, executed_at, function_missing FROM transactions WHERE id = ?"
         )
         .bind(transaction_id)
         .fetch_optional(&self.db)
@@ -462,6 +641,7 @@ impl WorkspaceService {
         contract_id: Option<&str>,
         function_name: Option<&str>,
         args: Option<&str>,
+        overrides: Option<&str>,
     ) -> Result<Transaction> {
         // Verify transaction exists
         let existing = self.get_transaction(transaction_id).await?;
@@ -482,10 +662,20 @@ impl WorkspaceService {
             updates.push("function_name = ?");
             bindings.push(fname.to_string());
         }
+        // A newly picked target is checked again on the next re-sync
+        if contract_id.is_some() || function_name.is_some() {
+            updates.push("function_missing = 0");
+        }
         if let Some(a) = args {
             updates.push("args = ?");
             bindings.push(a.to_string());
         }
+        if let Some(o) = overrides {
+            serde_json::from_str::<TransactionOverrides>(o)
+                .map_err(|e| CocoError::Validation(format!("Invalid transaction overrides: {}", e)))?;
+            updates.push("overrides = ?");
+            bindings.push(o.to_string());
+        }
 
         if updates.is_empty() {
             return Ok(existing);
@@ -511,186 +701,1395 @@ impl WorkspaceService {
         self.get_transaction(transaction_id).await
     }
 
+    /// Signs and broadcasts a transaction with the given wallet, waits for its
+    /// receipt and records the run
     pub async fn execute_transaction(
         &self,
         transaction_id: &str,
         payload: serde_json::Value,
-        _wallet_id: &str,
+        wallet_id: &str,
     ) -> Result<TransactionRun> {
-        // Get the transaction to verify it exists
-        let _transaction = self.get_transaction(transaction_id).await?;
+        let _guard = ExecutionGuard::acquire(&self.executing, transaction_id)?;
+
+        let transaction = self.get_transaction(transaction_id).await?;
+        let function = transaction
+            .function_name
+            .clone()
+            .ok_or_else(|| CocoError::Validation("Transaction has no function selected".to_string()))?;
+        let contract_id = transaction
+            .contract_id
+            .as_deref()
+            .ok_or_else(|| CocoError::Validation("Transaction has no contract selected".to_string()))?;
+        let contract = self.get_contract(contract_id).await?;
+        let interface = contract_interface(&contract)?;
+
+        let workspace = self.get_workspace(&transaction.workspace_id).await?;
+        let address = self
+            .active_address(contract_id, &workspace.chain_id)
+            .await?
+            .ok_or_else(|| {
+                CocoError::Validation(format!(
+                    "Contract {} has no active deployment on {}",
+                    contract.name, workspace.chain_id
+                ))
+            })?;
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+        let signer = self.wallet_service.signer(wallet_id).await?;
+
+        let args = payload_args(&payload);
+        let data = adapter.encode_call(interface.as_bytes(), &function, args.clone())?;
+        let accounts = adapter.resolve_accounts(interface.as_bytes(), &function, &args, &signer.address(), &address)?;
+
+        // Overrides in the payload (e.g. from a replayed run) win over the saved ones
+        let overrides = payload_overrides(&payload)?.unwrap_or(transaction.overrides);
+        let mut request = TransactionRequest {
+            to: Some(address),
+            value: transaction.value.clone(),
+            data: Some(data),
+            accounts,
+            ..Default::default()
+        };
+        overrides.apply(&mut request);
+
+        let payload = with_overrides(payload, &overrides)?;
+        let mut run = pending_run(transaction_id, payload, wallet_id);
+        let nonces = self.chain_service.nonce_manager().await;
+        let sent = nonces.send(adapter.as_ref(), signer.as_ref(), request.clone()).await;
+        if sent.is_err() {
+            // Rejected before broadcast, most often by gas estimation; simulating tells us why
+            let call = TransactionRequest {
+                from: Some(signer.address()),
+                ..request.clone()
+            };
+            if let Ok(simulation) = adapter.simulate_transaction(&call).await {
+                run.revert = simulation.revert;
+            }
+        }
+        self.record_send(run, adapter.as_ref(), sent, &request, Some(&interface))
+            .await
+    }
 
-        let started_at = Utc::now();
+    /// Deploys a contract with the given wallet and records it as a transaction run.
+    /// Bytecode comes from the contract row or else the project's build artifact;
+    /// constructor args are given like a transaction payload. Once mined, the
+    /// deployment becomes the contract's active one on the workspace's network.
+    pub async fn deploy_contract(
+        &self,
+        contract_id: &str,
+        wallet_id: &str,
+        payload: serde_json::Value,
+    ) -> Result<DeploymentResult> {
+        let contract = self.get_contract(contract_id).await?;
+        let workspace = self.get_workspace(&contract.workspace_id).await?;
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+
+        // Move packages are published with their own CLI; discovery picks up
+        // the address once they are
+        if adapter.ecosystem() == Ecosystem::Aptos {
+            return Err(CocoError::Validation(format!(
+                "Aptos packages can't be deployed from here; publish {} with `aptos move publish` and sync the workspace",
+                contract.name
+            )));
+        }
 
-        // For now, create a mock successful result
-        // TODO: Implement actual blockchain transaction execution
-        // This would involve:
-        // 1. Getting the wallet's private key
-        // 2. Getting the contract ABI and address
-        // 3. Encoding the function call with payload
-        // 4. Sending the transaction to the blockchain
-        // 5. Waiting for confirmation
+        let artifact = match contract.bytecode.as_deref().filter(|b| !b.is_empty()) {
+            Some(bytecode) => artifacts::Artifact {
+                bytecode: artifacts::decode_bytecode(&contract.name, bytecode)?,
+                abi: None,
+                program_keypair: None,
+            },
+            None => {
+                let source = (!contract.path.is_empty()).then(|| Path::new(&contract.path));
+                artifacts::find(Path::new(&workspace.path), &contract.name, source)?
+            }
+        };
+        let bytecode = artifact.bytecode;
 
-        let finished_at = Utc::now();
-        let duration_ms = (finished_at - started_at).num_milliseconds() as u64;
+        let args = payload_args(&payload);
+        let deployment = match adapter.ecosystem() {
+            // Programs take no constructor arguments and deploy to their build's keypair
+            Ecosystem::Solana => {
+                if !args.is_empty() {
+                    return Err(CocoError::Validation(format!(
+                        "Program {} takes no constructor arguments",
+                        contract.name
+                    )));
+                }
+                let keypair = artifact.program_keypair.ok_or_else(|| {
+                    CocoError::Validation(format!(
+                        "No program keypair found for {}; build it with `anchor build`",
+                        contract.name
+                    ))
+                })?;
+                Deployment::Program { keypair }
+            }
+            _ => {
+                let abi_json = contract.abi.clone().or(artifact.abi);
+                let constructor_args = match &abi_json {
+                    Some(abi_json) => ContractAbi::parse(abi_json.as_bytes())?.encode_constructor(&args)?,
+                    None if args.is_empty() => vec![],
+                    None => {
+                        return Err(CocoError::Validation(format!(
+                            "Contract {} has no ABI to encode constructor arguments with",
+                            contract.name
+                        )))
+                    }
+                };
+                let mut request = adapter.deployment_request(&bytecode, &constructor_args)?;
+                let overrides = payload_overrides(&payload)?.unwrap_or_default();
+                overrides.apply(&mut request);
+                Deployment::Contract { request: Box::new(request), abi_json }
+            }
+        };
 
-        let run = TransactionRun {
-            id: Uuid::new_v4().to_string(),
-            transaction_id: transaction_id.to_string(),
-            payload: Some(payload),
-            result: None,
-            tx_hash: Some(format!("0x{}", hex::encode(Uuid::new_v4().as_bytes()))),
-            block_number: Some(12345678),
-            gas_used: Some(21000),
-            fee: Some("0.001".to_string()),
-            status: TxStatus::Success,
-            error_message: None,
-            events: Some(vec![]),
-            started_at,
-            finished_at: Some(finished_at),
-            duration_ms: Some(duration_ms),
-            ai_explanation: None,
+        let signer = self.wallet_service.signer(wallet_id).await?;
+
+        let transaction_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO transactions (id, workspace_id, name, contract_id, args, wallet_id, status)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&transaction_id)
+        .bind(&contract.workspace_id)
+        .bind(format!("Deploy {}", contract.name))
+        .bind(contract_id)
+        .bind(serde_json::to_string(&payload)?)
+        .bind(wallet_id)
+        .bind("draft")
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        let mut run = pending_run(&transaction_id, payload, wallet_id);
+        run.result = Some(serde_json::json!({
+            "bytecodeHash": format!("0x{}", hex::encode(ethers::utils::keccak256(&bytecode))),
+        }));
+        let run = match deployment {
+            Deployment::Contract { request, abi_json } => {
+                let nonces = self.chain_service.nonce_manager().await;
+                let sent = nonces.send(adapter.as_ref(), signer.as_ref(), (*request).clone()).await;
+                self.record_send(run, adapter.as_ref(), sent, &request, abi_json.as_deref())
+                    .await?
+            }
+            Deployment::Program { keypair } => {
+                let sent = adapter.deploy_program(signer.as_ref(), &bytecode, &keypair).await;
+                // The deploy transaction's receipt doesn't name the program it creates
+                let sent = sent.map(|(program_id, sent)| {
+                    if let Some(serde_json::Value::Object(result)) = run.result.as_mut() {
+                        result.insert("contractAddress".to_string(), program_id.into());
+                    }
+                    sent
+                });
+                let request = TransactionRequest::default();
+                self.record_send(run, adapter.as_ref(), sent, &request, contract.idl.as_deref())
+                    .await?
+            }
         };
 
-        Ok(run)
+        Ok(DeploymentResult {
+            contract: self.get_contract(contract_id).await?,
+            run,
+        })
     }
 
-    // Transaction run operations
-    pub async fn save_transaction_run(&self, run: &TransactionRun) -> Result<TransactionRun> {
-        // Verify transaction exists
-        self.get_transaction(&run.transaction_id).await?;
+    /// Sends `amount` of the chain's native currency (in its smallest unit) between
+    /// two wallets, recorded as a transaction in the workspace
+    pub async fn transfer_native(
+        &self,
+        workspace_id: &str,
+        from_wallet_id: &str,
+        to_wallet_id: &str,
+        amount: &str,
+    ) -> Result<TransactionRun> {
+        if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+            return Err(CocoError::Validation(format!("Invalid amount: {}", amount)));
+        }
 
-        // Serialize events to JSON
-        let events_json = run.events.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default());
+        let workspace = self.get_workspace(workspace_id).await?;
+        let from = self.wallet_service.get_wallet(&workspace.chain_id, from_wallet_id).await?;
+        let to = self.wallet_service.get_wallet(&workspace.chain_id, to_wallet_id).await?;
+        for wallet in [&from, &to] {
+            if wallet.chain_id != workspace.chain_id {
+                return Err(CocoError::Validation(format!(
+                    "Wallet {} is not on the workspace's chain",
+                    wallet.name
+                )));
+            }
+        }
 
-        // Serialize args/payload to JSON
-        let args_json = run.payload.as_ref().map(|p| serde_json::to_string(p).unwrap_or_default());
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+        let signer = self.wallet_service.signer(from_wallet_id).await?;
+        self.send_transfer(workspace_id, adapter.as_ref(), signer.as_ref(), from_wallet_id, &to, amount)
+            .await
+    }
 
-        // Serialize result to JSON
-        let result_json = run.result.as_ref().map(|r| serde_json::to_string(r).unwrap_or_default());
+    /// Tops every other wallet on the workspace's chain up to `target_balance`
+    /// (in the smallest unit) from the funder, one recorded transfer per wallet.
+    /// Transfers still unconfirmed after `RECEIPT_TIMEOUT` are left pending.
+    pub async fn fund_wallets(
+        &self,
+        workspace_id: &str,
+        funder_wallet_id: &str,
+        target_balance: &str,
+    ) -> Result<Vec<TransactionRun>> {
+        let target: u128 = target_balance
+            .parse()
+            .map_err(|_| CocoError::Validation(format!("Invalid target balance: {}", target_balance)))?;
 
-        // Serialize AI explanation to JSON
-        let ai_explanation_json = run.ai_explanation.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default());
+        let workspace = self.get_workspace(workspace_id).await?;
+        let funder = self.wallet_service.get_wallet(&workspace.chain_id, funder_wallet_id).await?;
+        if funder.chain_id != workspace.chain_id {
+            return Err(CocoError::Validation(format!(
+                "Wallet {} is not on the workspace's chain",
+                funder.name
+            )));
+        }
 
-        let status_str = match run.status {
-            TxStatus::Pending => "pending",
-            TxStatus::Success => "success",
-            TxStatus::Failed => "failed",
-        };
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+        let signer = self.wallet_service.signer(funder_wallet_id).await?;
+
+        // Every transfer goes out before any receipt is awaited, so the
+        // receipts share one timeout instead of each waiting its own
+        let mut sends = Vec::new();
+        let queued = self
+            .broadcast_fundings(workspace_id, adapter.as_ref(), signer.as_ref(), &funder, target, &mut sends)
+            .await;
+
+        // Whatever was broadcast is recorded even if a later wallet failed
+        let deadline = tokio::time::Instant::now() + RECEIPT_TIMEOUT;
+        let mut runs = Vec::with_capacity(sends.len());
+        for send in sends {
+            runs.push(
+                self.record_send_until(send.run, adapter.as_ref(), send.sent, &send.request, None, deadline)
+                    .await?,
+            );
+        }
+
+        queued?;
+        Ok(runs)
+    }
+
+    /// Broadcasts a top-up transfer to each wallet below `target`, stopping
+    /// at the first send that fails
+    async fn broadcast_fundings(
+        &self,
+        workspace_id: &str,
+        adapter: &dyn FullAdapter,
+        signer: &dyn ExternalSigner,
+        funder: &Wallet,
+        target: u128,
+        sends: &mut Vec<PendingSend>,
+    ) -> Result<()> {
+        for wallet in self.wallet_service.list_wallets(&funder.chain_id).await? {
+            if wallet.id == funder.id {
+                continue;
+            }
+
+            let balance = adapter.get_balance(&wallet.address).await?;
+            let current: u128 = balance.native.parse().map_err(|_| {
+                CocoError::Adapter(format!("Invalid balance for {}: {}", wallet.address, balance.native))
+            })?;
+            if current >= target {
+                continue;
+            }
+
+            let amount = (target - current).to_string();
+            let send = self
+                .broadcast_transfer(workspace_id, adapter, signer, &funder.id, &wallet, &amount)
+                .await?;
+
+            // Later transfers would fail the same way once the funder runs dry
+            let failed = send.sent.is_err();
+            sends.push(send);
+            if failed {
+                break;
+            }
+        }
+        Ok(())
+    }
 
+    /// Records a native transfer as a transaction and executes it
+    async fn send_transfer(
+        &self,
+        workspace_id: &str,
+        adapter: &dyn FullAdapter,
+        signer: &dyn ExternalSigner,
+        from_wallet_id: &str,
+        to: &Wallet,
+        amount: &str,
+    ) -> Result<TransactionRun> {
+        let send = self
+            .broadcast_transfer(workspace_id, adapter, signer, from_wallet_id, to, amount)
+            .await?;
+        self.record_send(send.run, adapter, send.sent, &send.request, None).await
+    }
+
+    /// Records a native transfer as a transaction and broadcasts it, without
+    /// waiting for a receipt. The run still has to be passed to `record_send`.
+    async fn broadcast_transfer(
+        &self,
+        workspace_id: &str,
+        adapter: &dyn FullAdapter,
+        signer: &dyn ExternalSigner,
+        from_wallet_id: &str,
+        to: &Wallet,
+        amount: &str,
+    ) -> Result<PendingSend> {
+        let transaction_id = Uuid::new_v4().to_string();
         sqlx::query(
             r#"
-            INSERT INTO transaction_runs (
-                id, transaction_id, status, tx_hash, block_number, gas_used,
-                error, args, events, executed_at,
-                result, fee, finished_at, duration_ms, ai_explanation
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO transactions (id, workspace_id, name, value, wallet_id, status)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&run.id)
-        .bind(&run.transaction_id)
-        .bind(status_str)
-        .bind(&run.tx_hash)
-        .bind(run.block_number.map(|n| n as i64))
-        .bind(run.gas_used.map(|g| g.to_string()))
-        .bind(&run.error_message)
-        .bind(&args_json)
-        .bind(&events_json)
-        .bind(run.started_at.to_rfc3339())
-        .bind(&result_json)
-        .bind(&run.fee)
-        .bind(run.finished_at.map(|t| t.to_rfc3339()))
-        .bind(run.duration_ms.map(|d| d as i64))
-        .bind(&ai_explanation_json)
+        .bind(&transaction_id)
+        .bind(workspace_id)
+        .bind(format!("Transfer to {}", to.name))
+        .bind(amount)
+        .bind(from_wallet_id)
+        .bind("draft")
         .execute(&self.db)
         .await
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
-        Ok(run.clone())
+        let request = TransactionRequest {
+            to: Some(to.address.clone()),
+            value: Some(amount.to_string()),
+            ..Default::default()
+        };
+        let payload = serde_json::json!({
+            "from": signer.address(),
+            "to": to.address,
+            "amount": amount,
+        });
+
+        let run = pending_run(&transaction_id, payload, from_wallet_id);
+        let nonces = self.chain_service.nonce_manager().await;
+        let sent = nonces.send(adapter, signer, request.clone()).await;
+        Ok(PendingSend { run, sent, request })
     }
 
-    pub async fn list_transaction_runs(&self, transaction_id: &str) -> Result<Vec<TransactionRun>> {
-        let rows = sqlx::query_as::<_, TransactionRunRow>(
+    /// Re-sends a pending run with the same nonce and a higher fee
+    pub async fn speed_up_transaction(&self, run_id: &str) -> Result<TransactionRun> {
+        self.replace_run(run_id, ReplacementKind::SpeedUp).await
+    }
+
+    /// Replaces a pending run with a zero-value transfer to the sender at the
+    /// same nonce and a higher fee, so the original can no longer be mined
+    pub async fn cancel_transaction(&self, run_id: &str) -> Result<TransactionRun> {
+        self.replace_run(run_id, ReplacementKind::Cancel).await
+    }
+
+    async fn replace_run(&self, run_id: &str, kind: ReplacementKind) -> Result<TransactionRun> {
+        let original = self.get_transaction_run(run_id).await?;
+        if !matches!(original.status, TxStatus::Pending) {
+            return Err(CocoError::Validation(format!(
+                "Run {} is no longer pending",
+                run_id
+            )));
+        }
+        let tx_hash = original
+            .tx_hash
+            .clone()
+            .ok_or_else(|| CocoError::Validation(format!("Run {} was never broadcast", run_id)))?;
+        let wallet_id = original
+            .wallet_id
+            .clone()
+            .ok_or_else(|| CocoError::Validation(format!("Run {} has no recorded signer", run_id)))?;
+
+        let _guard = ExecutionGuard::acquire(&self.executing, &original.transaction_id)?;
+
+        let transaction = self.get_transaction(&original.transaction_id).await?;
+        let workspace = self.get_workspace(&transaction.workspace_id).await?;
+        let adapter = self.chain_service.get_adapter_for_chain(&workspace.chain_id).await?;
+        let signer = self.wallet_service.signer(&wallet_id).await?;
+
+        let (request, payload, interface) = match kind {
+            ReplacementKind::SpeedUp => {
+                let request = self.get_run_request(run_id).await?.ok_or_else(|| {
+                    CocoError::Validation(format!("Run {} has no stored request to resend", run_id))
+                })?;
+                let payload = original.payload.clone().unwrap_or(serde_json::Value::Null);
+                (request, payload, self.transaction_interface(&transaction).await)
+            }
+            ReplacementKind::Cancel => {
+                let request = TransactionRequest {
+                    to: Some(signer.address()),
+                    value: Some("0".to_string()),
+                    ..Default::default()
+                };
+                (request, serde_json::json!({ "cancels": tx_hash }), None)
+            }
+        };
+
+        // Nothing was broadcast if the replacement is rejected, so there is no run to record
+        let sent = adapter
+            .replace_transaction(signer.as_ref(), &tx_hash, request.clone())
+            .await?;
+
+        let mut run = pending_run(&original.transaction_id, payload, &wallet_id);
+        run.replaces_run_id = Some(original.id);
+        run.replacement_kind = Some(kind);
+        self.record_send(run, adapter.as_ref(), Ok(sent), &request, interface.as_deref())
+            .await
+    }
+
+    /// Waits for the receipt of a send and saves the run. Once broadcast, the
+    /// run is always recorded so the hash isn't lost.
+    async fn record_send(
+        &self,
+        run: TransactionRun,
+        adapter: &dyn FullAdapter,
+        sent: Result<TransactionResult>,
+        request: &TransactionRequest,
+        interface: Option<&str>,
+    ) -> Result<TransactionRun> {
+        let deadline = tokio::time::Instant::now() + RECEIPT_TIMEOUT;
+        self.record_send_until(run, adapter, sent, request, interface, deadline)
+            .await
+    }
+
+    /// `record_send`, giving up on the receipt at `deadline`
+    async fn record_send_until(
+        &self,
+        mut run: TransactionRun,
+        adapter: &dyn FullAdapter,
+        sent: Result<TransactionResult>,
+        request: &TransactionRequest,
+        interface: Option<&str>,
+        deadline: tokio::time::Instant,
+    ) -> Result<TransactionRun> {
+        match sent {
+            Ok(sent) => {
+                run.tx_hash = Some(sent.hash.clone());
+                run.nonce = sent.nonce;
+                record_history(&mut run, format!("Broadcast as {}", sent.hash));
+                match wait_for_receipt(adapter, &sent.hash, deadline).await {
+                    Ok(Some(receipt)) => {
+                        let is_final = self.is_final(adapter, &receipt).await;
+                        apply_receipt(&mut run, adapter, &receipt, interface, is_final);
+                    }
+                    Ok(None) => {
+                        run.error_message = Some(format!(
+                            "No receipt after {}s, transaction may still confirm",
+                            RECEIPT_TIMEOUT.as_secs()
+                        ));
+                    }
+                    Err(e) => {
+                        run.error_message = Some(format!("Failed to fetch receipt: {}", e));
+                    }
+                }
+            }
+            Err(e) => {
+                run.status = TxStatus::Failed;
+                run.error_message = Some(e.to_string());
+            }
+        }
+        self.decode_revert(&mut run).await;
+
+        let finished_at = Utc::now();
+        run.finished_at = Some(finished_at);
+        run.duration_ms = Some((finished_at - run.started_at).num_milliseconds() as u64);
+
+        self.insert_transaction_run(&run, Some(request)).await?;
+        self.settle_run(&run).await?;
+
+        Ok(run)
+    }
+
+    /// Pending and confirming runs that were broadcast, with the chain each was sent on
+    pub async fn list_pending_runs(&self) -> Result<Vec<(String, TransactionRun)>> {
+        let rows = sqlx::query_as::<_, PendingRunRow>(
             r#"
-            SELECT id, transaction_id, status, tx_hash, block_number, gas_used,
-                   error, args, events, executed_at,
-                   result, fee, finished_at, duration_ms, ai_explanation
-            FROM transaction_runs
-            WHERE transaction_id = ?
-            ORDER BY executed_at DESC
+            SELECT w.chain_id, r.id, r.transaction_id, r.status, r.tx_hash, r.block_number, r.gas_used,
+                   r.error, r.args, r.events, r.executed_at,
+                   r.result, r.fee, r.finished_at, r.duration_ms, r.ai_explanation,
+                   r.wallet_id, r.nonce, r.replaces_run_id, r.replacement_kind, r.block_hash, r.history, r.revert
+            FROM transaction_runs r
+            JOIN transactions t ON t.id = r.transaction_id
+            JOIN workspaces w ON w.id = t.workspace_id
+            WHERE r.status IN ('pending', 'confirming') AND r.tx_hash IS NOT NULL
+            ORDER BY r.executed_at
             "#
         )
-        .bind(transaction_id)
         .fetch_all(&self.db)
         .await
         .map_err(|e| CocoError::Database(e.to_string()))?;
 
-        Ok(rows.into_iter().map(TransactionRun::from).collect())
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.chain_id, TransactionRun::from(row.run)))
+            .collect())
     }
 
-    pub async fn update_transaction_run_ai_explanation(
+    /// Records the receipt of a run that was still pending or confirming when it was saved
+    pub async fn complete_run(
         &self,
-        run_id: &str,
-        ai_explanation: &AIExplanation,
-    ) -> Result<()> {
-        let json = serde_json::to_string(ai_explanation)
-            .map_err(|e| CocoError::Serialization(e.to_string()))?;
-
-        let result = sqlx::query("UPDATE transaction_runs SET ai_explanation = ? WHERE id = ?")
-            .bind(&json)
-            .bind(run_id)
-            .execute(&self.db)
-            .await
-            .map_err(|e| CocoError::Database(e.to_string()))?;
+        mut run: TransactionRun,
+        adapter: &dyn FullAdapter,
+        receipt: &TransactionReceipt,
+    ) -> Result<TransactionRun> {
+        let transaction = self.get_transaction(&run.transaction_id).await?;
+        let interface = self.transaction_interface(&transaction).await;
+        let is_final = self.is_final(adapter, receipt).await;
+        apply_receipt(&mut run, adapter, receipt, interface.as_deref(), is_final);
+        self.decode_revert(&mut run).await;
 
-        if result.rows_affected() == 0 {
-            return Err(CocoError::NotFound(format!("Transaction run not found: {}", run_id)));
-        }
+        let finished_at = Utc::now();
+        run.finished_at = Some(finished_at);
+        run.duration_ms = Some((finished_at - run.started_at).num_milliseconds() as u64);
 
-        Ok(())
+        self.update_run_outcome(&run).await?;
+        self.settle_run(&run).await?;
+        Ok(run)
     }
 
-    // Helper methods
-    fn detect_framework(&self, path: &PathBuf) -> Option<String> {
-        // Check for Foundry
-        if path.join("foundry.toml").exists() {
-            return Some("foundry".to_string());
-        }
+    /// Puts a confirming run back to pending after its block was reorganized away;
+    /// the tracker picks up its new receipt, if any, on a later poll
+    pub async fn reopen_run(&self, mut run: TransactionRun, note: String) -> Result<TransactionRun> {
+        run.status = TxStatus::Pending;
+        run.block_number = None;
+        run.block_hash = None;
+        run.error_message = None;
+        run.revert = None;
+        record_history(&mut run, note);
+
+        self.update_run_outcome(&run).await?;
+        Ok(run)
+    }
 
-        // Check for Hardhat
-        if path.join("hardhat.config.js").exists() || path.join("hardhat.config.ts").exists() {
-            return Some("hardhat".to_string());
-        }
+    /// Decodes a failed run's revert data against every ABI in its workspace, so
+    /// custom errors from other contracts it called are named too
+    async fn decode_revert(&self, run: &mut TransactionRun) {
+        let Some(revert) = run.revert.as_mut() else {
+            return;
+        };
 
-        // Check for Anchor (Solana)
-        if path.join("Anchor.toml").exists() {
-            return Some("anchor".to_string());
+        if let Some(data) = revert
+            .data
+            .as_deref()
+            .and_then(|data| hex::decode(data.trim_start_matches("0x")).ok())
+        {
+            if let Ok(transaction) = self.get_transaction(&run.transaction_id).await {
+                let abis = self.workspace_abis(&transaction.workspace_id).await;
+                *revert = abi::revert_info(&data, &abis);
+            }
         }
 
-        // Check for Move.toml (Aptos)
-        if path.join("Move.toml").exists() {
-            return Some("move".to_string());
+        if matches!(run.status, TxStatus::Failed) {
+            run.error_message = Some(revert.message.clone());
         }
-
-        None
     }
-}
 
-impl Default for WorkspaceService {
-    fn default() -> Self {
-        panic!("WorkspaceService requires a database pool")
+    /// Parsed ABIs of the workspace's EVM contracts, skipping any that don't parse
+    async fn workspace_abis(&self, workspace_id: &str) -> Vec<ContractAbi> {
+        self.list_contracts(workspace_id)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|contract| contract.abi.as_deref())
+            .filter_map(|abi| ContractAbi::parse(abi.as_bytes()).ok())
+            .collect()
     }
-}
 
-// Helper structs for SQLx
-#[derive(sqlx::FromRow)]
-struct WorkspaceRow {
-    id: String,
-    chain_id: String,
-    name: String,
-    path: String,
-    framework: Option<String>,
+    async fn update_run_outcome(&self, run: &TransactionRun) -> Result<()> {
+        let events_json = run.events.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default());
+        let history_json = serde_json::to_string(&run.history)?;
+        let revert_json = run.revert.as_ref().map(serde_json::to_string).transpose()?;
+        let result_json = run.result.as_ref().map(serde_json::to_string).transpose()?;
+
+        sqlx::query(
+            r#"
+            UPDATE transaction_runs
+            SET status = ?, block_number = ?, block_hash = ?, gas_used = ?, fee = ?, error = ?, events = ?,
+                finished_at = ?, duration_ms = ?, history = ?, revert = ?, result = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(run_status_to_string(&run.status))
+        .bind(run.block_number.map(|n| n as i64))
+        .bind(&run.block_hash)
+        .bind(run.gas_used.map(|g| g.to_string()))
+        .bind(&run.fee)
+        .bind(&run.error_message)
+        .bind(&events_json)
+        .bind(run.finished_at.map(|t| t.to_rfc3339()))
+        .bind(run.duration_ms.map(|d| d as i64))
+        .bind(&history_json)
+        .bind(&revert_json)
+        .bind(&result_json)
+        .bind(&run.id)
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Whether a receipt is buried under the chain's confirmation depth. Failing
+    /// to tell counts as not final, leaving the run to the tracker.
+    async fn is_final(&self, adapter: &dyn FullAdapter, receipt: &TransactionReceipt) -> bool {
+        let Ok(depth) = self.chain_service.confirmation_depth(adapter.chain_id()).await else {
+            return false;
+        };
+        if depth <= 1 {
+            return true;
+        }
+
+        match adapter.get_block_height().await {
+            Ok(height) => height.saturating_sub(receipt.block_number) + 1 >= depth,
+            Err(_) => false,
+        }
+    }
+
+    /// Once a run is final, closes the other pending runs that shared its nonce
+    /// and mirrors the outcome onto the transaction
+    async fn settle_run(&self, run: &TransactionRun) -> Result<()> {
+        let mined = matches!(run.status, TxStatus::Success | TxStatus::Failed) && run.block_number.is_some();
+        let cancelled = mined && run.replacement_kind == Some(ReplacementKind::Cancel);
+
+        if let (true, Some(wallet_id), Some(nonce)) = (mined, &run.wallet_id, run.nonce) {
+            let superseded = if cancelled { TxStatus::Cancelled } else { TxStatus::Replaced };
+            sqlx::query(
+                r#"
+                UPDATE transaction_runs SET status = ?, finished_at = ?
+                WHERE transaction_id = ? AND wallet_id = ? AND nonce = ? AND status = 'pending' AND id != ?
+                "#,
+            )
+            .bind(run_status_to_string(&superseded))
+            .bind(Utc::now().to_rfc3339())
+            .bind(&run.transaction_id)
+            .bind(wallet_id)
+            .bind(nonce as i64)
+            .bind(&run.id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        }
+
+        let status = match run.status {
+            _ if cancelled => "cancelled",
+            TxStatus::Success => "success",
+            TxStatus::Failed => "failed",
+            TxStatus::Pending | TxStatus::Confirming | TxStatus::Replaced | TxStatus::Cancelled => "pending",
+        };
+
+        sqlx::query("UPDATE transactions SET status = ?, tx_hash = ?, executed_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(&run.tx_hash)
+            .bind(run.started_at.to_rfc3339())
+            .bind(&run.transaction_id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        if let (TxStatus::Success, Some(address)) = (&run.status, deployed_address(run)) {
+            self.register_deployment_run(run, address).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Registers a mined deployment run as the contract's active deployment on
+    /// the workspace's network, once even if the run settles again after a reorg
+    async fn register_deployment_run(&self, run: &TransactionRun, address: &str) -> Result<()> {
+        let target: Option<(Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT t.contract_id, w.chain_id
+            FROM transactions t
+            JOIN workspaces w ON w.id = t.workspace_id
+            WHERE t.id = ?
+            "#,
+        )
+        .bind(&run.transaction_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+        let Some((Some(contract_id), chain_id)) = target else {
+            return Ok(());
+        };
+
+        let existing: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM contract_deployments WHERE contract_id = ? AND tx_hash = ?",
+        )
+        .bind(&contract_id)
+        .bind(&run.tx_hash)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+        if existing.is_some() {
+            return Ok(());
+        }
+
+        self.record_deployment(&ContractDeployment {
+            deployer_wallet_id: run.wallet_id.clone(),
+            tx_hash: run.tx_hash.clone(),
+            block_number: run.block_number,
+            bytecode_hash: run
+                .result
+                .as_ref()
+                .and_then(|r| r.get("bytecodeHash"))
+                .and_then(|h| h.as_str())
+                .map(str::to_string),
+            ..manual_deployment(&contract_id, &chain_id, address)
+        })
+        .await
+    }
+
+    // Deployment operations
+    pub async fn list_contract_deployments(&self, contract_id: &str) -> Result<Vec<ContractDeployment>> {
+        let rows = sqlx::query_as::<_, ContractDeploymentRow>(
+            r#"
+            SELECT id, contract_id, chain_id, address, deployer_wallet_id, tx_hash, block_number,
+                   bytecode_hash, is_active, created_at
+            FROM contract_deployments WHERE contract_id = ? ORDER BY created_at DESC
+            "#,
+        )
+        .bind(contract_id)
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(ContractDeployment::from).collect())
+    }
+
+    /// Makes a deployment the one transactions on its network target
+    pub async fn activate_deployment(&self, deployment_id: &str) -> Result<ContractDeployment> {
+        let row = sqlx::query_as::<_, ContractDeploymentRow>(
+            r#"
+            SELECT id, contract_id, chain_id, address, deployer_wallet_id, tx_hash, block_number,
+                   bytecode_hash, is_active, created_at
+            FROM contract_deployments WHERE id = ?
+            "#,
+        )
+        .bind(deployment_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?
+        .ok_or_else(|| CocoError::NotFound(format!("Deployment not found: {}", deployment_id)))?;
+
+        let deployment = ContractDeployment::from(row);
+        self.set_active_deployment(&deployment.contract_id, &deployment.chain_id, Some(&deployment.id))
+            .await?;
+        Ok(ContractDeployment { is_active: true, ..deployment })
+    }
+
+    /// Address of the contract's active deployment on a network
+    async fn active_address(&self, contract_id: &str, chain_id: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT address FROM contract_deployments WHERE contract_id = ? AND chain_id = ? AND is_active = 1",
+        )
+        .bind(contract_id)
+        .bind(chain_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(row.map(|(address,)| address))
+    }
+
+    /// Adds a deployment as the active one on its network
+    async fn record_deployment(&self, deployment: &ContractDeployment) -> Result<()> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO contract_deployments (
+                id, contract_id, chain_id, address, deployer_wallet_id, tx_hash, block_number,
+                bytecode_hash, is_active, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, ?)
+            "#,
+        )
+        .bind(&deployment.id)
+        .bind(&deployment.contract_id)
+        .bind(&deployment.chain_id)
+        .bind(&deployment.address)
+        .bind(&deployment.deployer_wallet_id)
+        .bind(&deployment.tx_hash)
+        .bind(deployment.block_number.map(|n| n as i64))
+        .bind(&deployment.bytecode_hash)
+        .bind(deployment.created_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        write_active_deployment(&mut tx, &deployment.contract_id, &deployment.chain_id, Some(&deployment.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Makes `deployment_id` (or none) the contract's active deployment on a network
+    async fn set_active_deployment(
+        &self,
+        contract_id: &str,
+        chain_id: &str,
+        deployment_id: Option<&str>,
+    ) -> Result<()> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        write_active_deployment(&mut tx, contract_id, chain_id, deployment_id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    // Transaction run operations
+    pub async fn save_transaction_run(&self, run: &TransactionRun) -> Result<TransactionRun> {
+        self.insert_transaction_run(run, None).await
+    }
+
+    async fn insert_transaction_run(
+        &self,
+        run: &TransactionRun,
+        request: Option<&TransactionRequest>,
+    ) -> Result<TransactionRun> {
+        // Verify transaction exists
+        self.get_transaction(&run.transaction_id).await?;
+
+        // Serialize events to JSON
+        let events_json = run.events.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default());
+
+        // Serialize args/payload to JSON
+        let args_json = run.payload.as_ref().map(|p| serde_json::to_string(p).unwrap_or_default());
+
+        // Serialize result to JSON
+        let result_json = run.result.as_ref().map(|r| serde_json::to_string(r).unwrap_or_default());
+
+        // Serialize AI explanation to JSON
+        let ai_explanation_json = run.ai_explanation.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default());
+
+        // Stored so a speed-up can resend exactly the same call
+        let request_json = request.map(serde_json::to_string).transpose()?;
+        let history_json = serde_json::to_string(&run.history)?;
+        let revert_json = run.revert.as_ref().map(serde_json::to_string).transpose()?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO transaction_runs (
+                id, transaction_id, status, tx_hash, block_number, gas_used,
+                error, args, events, executed_at,
+                result, fee, finished_at, duration_ms, ai_explanation,
+                wallet_id, nonce, request, replaces_run_id, replacement_kind,
+                block_hash, history, revert
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&run.id)
+        .bind(&run.transaction_id)
+        .bind(run_status_to_string(&run.status))
+        .bind(&run.tx_hash)
+        .bind(run.block_number.map(|n| n as i64))
+        .bind(run.gas_used.map(|g| g.to_string()))
+        .bind(&run.error_message)
+        .bind(&args_json)
+        .bind(&events_json)
+        .bind(run.started_at.to_rfc3339())
+        .bind(&result_json)
+        .bind(&run.fee)
+        .bind(run.finished_at.map(|t| t.to_rfc3339()))
+        .bind(run.duration_ms.map(|d| d as i64))
+        .bind(&ai_explanation_json)
+        .bind(&run.wallet_id)
+        .bind(run.nonce.map(|n| n as i64))
+        .bind(&request_json)
+        .bind(&run.replaces_run_id)
+        .bind(run.replacement_kind.map(replacement_kind_to_string))
+        .bind(&run.block_hash)
+        .bind(&history_json)
+        .bind(&revert_json)
+        .execute(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(run.clone())
+    }
+
+    pub async fn get_transaction_run(&self, run_id: &str) -> Result<TransactionRun> {
+        let row = sqlx::query_as::<_, TransactionRunRow>(
+            r#"
+            SELECT id, transaction_id, status, tx_hash, block_number, gas_used,
+                   error, args, events, executed_at,
+                   result, fee, finished_at, duration_ms, ai_explanation,
+                   wallet_id, nonce, replaces_run_id, replacement_kind, block_hash, history, revert
+            FROM transaction_runs
+            WHERE id = ?
+            "#
+        )
+        .bind(run_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        row.map(TransactionRun::from)
+            .ok_or_else(|| CocoError::NotFound(format!("Transaction run not found: {}", run_id)))
+    }
+
+    /// The request a run was broadcast with, if it was sent by the backend
+    async fn get_run_request(&self, run_id: &str) -> Result<Option<TransactionRequest>> {
+        let request: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT request FROM transaction_runs WHERE id = ?")
+                .bind(run_id)
+                .fetch_optional(&self.db)
+                .await
+                .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        match request.and_then(|(r,)| r) {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn list_transaction_runs(&self, transaction_id: &str) -> Result<Vec<TransactionRun>> {
+        let rows = sqlx::query_as::<_, TransactionRunRow>(
+            r#"
+            SELECT id, transaction_id, status, tx_hash, block_number, gas_used,
+                   error, args, events, executed_at,
+                   result, fee, finished_at, duration_ms, ai_explanation,
+                   wallet_id, nonce, replaces_run_id, replacement_kind, block_hash, history, revert
+            FROM transaction_runs
+            WHERE transaction_id = ?
+            ORDER BY executed_at DESC
+            "#
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(TransactionRun::from).collect())
+    }
+
+    pub async fn update_transaction_run_ai_explanation(
+        &self,
+        run_id: &str,
+        ai_explanation: &AIExplanation,
+    ) -> Result<()> {
+        let json = serde_json::to_string(ai_explanation)
+            .map_err(|e| CocoError::Serialization(e.to_string()))?;
+
+        let result = sqlx::query("UPDATE transaction_runs SET ai_explanation = ? WHERE id = ?")
+            .bind(&json)
+            .bind(run_id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(CocoError::NotFound(format!("Transaction run not found: {}", run_id)));
+        }
+
+        Ok(())
+    }
+
+    // Helper methods
+    /// Interface events are decoded against, when the transaction calls a contract
+    async fn transaction_interface(&self, transaction: &Transaction) -> Option<String> {
+        let contract = self.get_contract(transaction.contract_id.as_deref()?).await.ok()?;
+        contract_interface(&contract).ok()
+    }
+
+    async fn get_contract(&self, contract_id: &str) -> Result<Contract> {
+        let row = sqlx::query_as::<_, ContractRow>(
+            "SELECT id, workspace_id, name, path, interface_type, abi, idl, move_definition, bytecode, deployed_address, compiler_version, is_interface, is_dependency, created_at FROM contracts WHERE id = ?"
+        )
+        .bind(contract_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+        row.map(Contract::from)
+            .ok_or_else(|| CocoError::NotFound(format!("Contract not found: {}", contract_id)))
+    }
+
+    fn detect_framework(&self, path: &PathBuf) -> Option<String> {
+        // Check for Foundry
+        if path.join("foundry.toml").exists() {
+            return Some("foundry".to_string());
+        }
+
+        // Check for Hardhat
+        if path.join("hardhat.config.js").exists() || path.join("hardhat.config.ts").exists() {
+            return Some("hardhat".to_string());
+        }
+
+        // Check for Anchor (Solana)
+        if path.join("Anchor.toml").exists() {
+            return Some("anchor".to_string());
+        }
+
+        // Check for Move.toml (Aptos)
+        if path.join("Move.toml").exists() {
+            return Some("move".to_string());
+        }
+
+        None
+    }
+}
+
+impl Default for WorkspaceService {
+    fn default() -> Self {
+        panic!("WorkspaceService requires a database pool")
+    }
+}
+
+/// Marks a transaction as executing until dropped
+struct ExecutionGuard<'a> {
+    executing: &'a Mutex<HashSet<String>>,
+    transaction_id: String,
+}
+
+impl<'a> ExecutionGuard<'a> {
+    fn acquire(executing: &'a Mutex<HashSet<String>>, transaction_id: &str) -> Result<Self> {
+        let mut set = executing.lock().unwrap_or_else(|e| e.into_inner());
+        if !set.insert(transaction_id.to_string()) {
+            return Err(CocoError::Validation(
+                "Transaction is already being executed".to_string(),
+            ));
+        }
+        Ok(Self {
+            executing,
+            transaction_id: transaction_id.to_string(),
+        })
+    }
+}
+
+impl Drop for ExecutionGuard<'_> {
+    fn drop(&mut self) {
+        self.executing
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.transaction_id);
+    }
+}
+
+/// A transfer that has been broadcast but not yet recorded
+struct PendingSend {
+    run: TransactionRun,
+    sent: Result<TransactionResult>,
+    request: TransactionRequest,
+}
+
+/// How a contract reaches the chain: a creation transaction, or a program
+/// binary uploaded to its keypair's address
+enum Deployment {
+    Contract {
+        request: Box<TransactionRequest>,
+        abi_json: Option<String>,
+    },
+    Program {
+        keypair: String,
+    },
+}
+
+/// Picks the interface document the contract's adapter encodes against
+fn contract_interface(contract: &Contract) -> Result<String> {
+    let interface = match contract.interface_type {
+        InterfaceType::Abi => &contract.abi,
+        InterfaceType::Idl => &contract.idl,
+        InterfaceType::Move => &contract.move_definition,
+    };
+
+    interface.clone().ok_or_else(|| {
+        CocoError::Validation(format!(
+            "Contract {} has no {} interface",
+            contract.name, contract.interface_type
+        ))
+    })
+}
+
+/// Payload key holding the send overrides a run was executed with
+const OVERRIDES_KEY: &str = "$overrides";
+
+/// Turns the form payload into contract args, keyed by input name or `arg{i}`
+fn payload_args(payload: &serde_json::Value) -> Vec<ContractArg> {
+    match payload {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .filter(|(name, _)| name.as_str() != OVERRIDES_KEY)
+            .map(|(name, value)| ContractArg {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, value)| ContractArg {
+                name: format!("arg{}", i),
+                value: value.clone(),
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Overrides carried in a payload, if any
+fn payload_overrides(payload: &serde_json::Value) -> Result<Option<TransactionOverrides>> {
+    payload
+        .get(OVERRIDES_KEY)
+        .map(|overrides| {
+            serde_json::from_value(overrides.clone())
+                .map_err(|e| CocoError::Validation(format!("Invalid transaction overrides: {}", e)))
+        })
+        .transpose()
+}
+
+/// Stores the overrides in the run payload so replaying it sends the same
+/// transaction. Positional args are keyed `arg{i}` to make room for them.
+fn with_overrides(payload: serde_json::Value, overrides: &TransactionOverrides) -> Result<serde_json::Value> {
+    if overrides.is_empty() {
+        return Ok(payload);
+    }
+
+    let mut map = match payload {
+        serde_json::Value::Object(map) => map,
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| (format!("arg{}", i), value))
+            .collect(),
+        _ => serde_json::Map::new(),
+    };
+    map.insert(OVERRIDES_KEY.to_string(), serde_json::to_value(overrides)?);
+    Ok(serde_json::Value::Object(map))
+}
+
+/// A run that hasn't been broadcast yet, signed by `wallet_id`
+fn pending_run(transaction_id: &str, payload: serde_json::Value, wallet_id: &str) -> TransactionRun {
+    TransactionRun {
+        id: Uuid::new_v4().to_string(),
+        transaction_id: transaction_id.to_string(),
+        payload: Some(payload),
+        result: None,
+        tx_hash: None,
+        block_number: None,
+        gas_used: None,
+        fee: None,
+        status: TxStatus::Pending,
+        error_message: None,
+        events: None,
+        started_at: Utc::now(),
+        finished_at: None,
+        duration_ms: None,
+        ai_explanation: None,
+        wallet_id: Some(wallet_id.to_string()),
+        nonce: None,
+        replaces_run_id: None,
+        replacement_kind: None,
+        block_hash: None,
+        history: vec![],
+        revert: None,
+    }
+}
+
+/// Fills a run's outcome from its receipt, decoding events against `interface`.
+/// The run stays confirming until `is_final`.
+fn apply_receipt(
+    run: &mut TransactionRun,
+    adapter: &dyn FullAdapter,
+    receipt: &TransactionReceipt,
+    interface: Option<&str>,
+    is_final: bool,
+) {
+    let events = match interface {
+        Some(interface) if !receipt.logs.is_empty() => adapter
+            .decode_events(interface.as_bytes(), &receipt.logs)
+            .unwrap_or_else(|_| receipt.events.clone()),
+        _ => receipt.events.clone(),
+    };
+
+    let status = match (is_final, receipt.status) {
+        (false, _) => TxStatus::Confirming,
+        (true, true) => TxStatus::Success,
+        (true, false) => TxStatus::Failed,
+    };
+    if run.status != status {
+        run.status = status;
+        let note = match run.status {
+            TxStatus::Confirming => format!("Included in block {}", receipt.block_number),
+            TxStatus::Failed => format!("Reverted in block {}", receipt.block_number),
+            _ => format!("Confirmed in block {}", receipt.block_number),
+        };
+        record_history(run, note);
+    }
+
+    run.revert = receipt.revert.clone();
+    if let Some(address) = &receipt.contract_address {
+        let mut result = match run.result.take() {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        result.insert("contractAddress".to_string(), address.clone().into());
+        run.result = Some(serde_json::Value::Object(result));
+    }
+    run.error_message = (!receipt.status).then(|| {
+        receipt
+            .revert
+            .as_ref()
+            .map(|revert| revert.message.clone())
+            .unwrap_or_else(|| "Transaction reverted".to_string())
+    });
+    run.block_number = Some(receipt.block_number);
+    run.block_hash = receipt.block_hash.clone();
+    run.gas_used = Some(receipt.gas_used);
+    run.fee = receipt.fee.clone();
+    run.events = Some(events);
+}
+
+/// Solidity sources under `src/`, listed before the project has been built
+fn solidity_sources(project: &Path) -> Vec<DiscoveredContract> {
+    WalkDir::new(project.join("src"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "sol"))
+        .map(|entry| DiscoveredContract {
+            name: entry
+                .path()
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Unknown")
+                .to_string(),
+            path: entry
+                .path()
+                .strip_prefix(project)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string(),
+            interface_type: InterfaceType::Abi,
+            is_interface: false,
+            is_dependency: false,
+            abi: None,
+            bytecode: None,
+            compiler_version: None,
+            idl: None,
+            move_definition: None,
+            deployed_address: None,
+        })
+        .collect()
+}
+
+/// A deployment whose address was entered by hand rather than sent from here
+fn manual_deployment(contract_id: &str, chain_id: &str, address: &str) -> ContractDeployment {
+    ContractDeployment {
+        id: Uuid::new_v4().to_string(),
+        contract_id: contract_id.to_string(),
+        chain_id: chain_id.to_string(),
+        address: address.to_string(),
+        deployer_wallet_id: None,
+        tx_hash: None,
+        block_number: None,
+        bytecode_hash: None,
+        is_active: true,
+        created_at: Utc::now(),
+    }
+}
+
+/// Switches the contract's active deployment on a network to `deployment_id` (or
+/// none), and mirrors the active address on the workspace's network onto the contract
+async fn write_active_deployment(
+    conn: &mut SqliteConnection,
+    contract_id: &str,
+    chain_id: &str,
+    deployment_id: Option<&str>,
+) -> Result<()> {
+    // Deactivate first: the unique index on active rows is checked row by row
+    sqlx::query("UPDATE contract_deployments SET is_active = 0 WHERE contract_id = ? AND chain_id = ? AND is_active = 1")
+        .bind(contract_id)
+        .bind(chain_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CocoError::Database(e.to_string()))?;
+
+    if let Some(deployment_id) = deployment_id {
+        sqlx::query("UPDATE contract_deployments SET is_active = 1 WHERE id = ? AND contract_id = ? AND chain_id = ?")
+            .bind(deployment_id)
+            .bind(contract_id)
+            .bind(chain_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CocoError::Database(e.to_string()))?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE contracts SET deployed_address = (
+            SELECT d.address
+            FROM contract_deployments d
+            JOIN workspaces w ON w.id = contracts.workspace_id
+            WHERE d.contract_id = contracts.id AND d.chain_id = w.chain_id AND d.is_active = 1
+        )
+        WHERE id = ?
+        "#,
+    )
+    .bind(contract_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| CocoError::Database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Address a deployment run created, as recorded from its receipt
+fn deployed_address(run: &TransactionRun) -> Option<&str> {
+    run.result.as_ref()?.get("contractAddress")?.as_str()
+}
+
+/// Notes the run's current status in its history
+fn record_history(run: &mut TransactionRun, note: String) {
+    run.history.push(RunHistoryEntry {
+        at: Utc::now(),
+        status: run.status.clone(),
+        note,
+    });
+}
+
+/// Polls for a receipt until it lands or `deadline` passes. Checks at least
+/// once, so a receipt that is already there is found even past the deadline.
+async fn wait_for_receipt(
+    adapter: &dyn FullAdapter,
+    hash: &str,
+    deadline: tokio::time::Instant,
+) -> Result<Option<TransactionReceipt>> {
+    loop {
+        match adapter.get_transaction_receipt(hash).await {
+            Ok(receipt) => return Ok(Some(receipt)),
+            Err(CocoError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        if tokio::time::Instant::now() + RECEIPT_POLL_INTERVAL > deadline {
+            return Ok(None);
+        }
+        tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+    }
+}
+
+// Helper structs for SQLx
+#[derive(sqlx::FromRow)]
+struct WorkspaceRow {
+    id: String,
+    chain_id: String,
+    name: String,
+    path: String,
+    framework: Option<String>,
     created_at: String,
     updated_at: String,
     #[sqlx(default)]
@@ -721,6 +2120,40 @@ impl From<WorkspaceRow> for Workspace {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct ContractDeploymentRow {
+    id: String,
+    contract_id: String,
+    chain_id: String,
+    address: String,
+    deployer_wallet_id: Option<String>,
+    tx_hash: Option<String>,
+    block_number: Option<i64>,
+    bytecode_hash: Option<String>,
+    is_active: bool,
+    created_at: String,
+}
+
+impl From<ContractDeploymentRow> for ContractDeployment {
+    fn from(row: ContractDeploymentRow) -> Self {
+        ContractDeployment {
+            id: row.id,
+            contract_id: row.contract_id,
+            chain_id: row.chain_id,
+            address: row.address,
+            deployer_wallet_id: row.deployer_wallet_id,
+            tx_hash: row.tx_hash,
+            block_number: row.block_number.map(|n| n as u64),
+            bytecode_hash: row.bytecode_hash,
+            is_active: row.is_active,
+            created_at: row
+                .created_at
+                .parse::<DateTime<Utc>>()
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct ContractRow {
     id: String,
@@ -733,6 +2166,9 @@ struct ContractRow {
     move_definition: Option<String>,
     bytecode: Option<String>,
     deployed_address: Option<String>,
+    compiler_version: Option<String>,
+    is_interface: bool,
+    is_dependency: bool,
     created_at: String,
 }
 
@@ -752,6 +2188,9 @@ impl From<ContractRow> for Contract {
             move_definition: row.move_definition,
             bytecode: row.bytecode,
             deployed_address: row.deployed_address,
+            compiler_version: row.compiler_version,
+            is_interface: row.is_interface,
+            is_dependency: row.is_dependency,
             created_at: row
                 .created_at
                 .parse::<DateTime<Utc>>()
@@ -812,10 +2251,13 @@ struct TransactionRow {
     function_name: Option<String>,
     args: Option<String>,
     value: Option<String>,
+    overrides: Option<String>,
     status: String,
     tx_hash: Option<String>,
     created_at: String,
     executed_at: Option<String>,
+    #[sqlx(default)]
+    function_missing: bool,
 }
 
 impl From<TransactionRow> for Transaction {
@@ -831,6 +2273,10 @@ impl From<TransactionRow> for Transaction {
                 .and_then(|s| serde_json::from_str(&s).ok())
                 .unwrap_or_default(),
             value: row.value,
+            overrides: row
+                .overrides
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
             status: string_to_tx_status(&row.status),
             tx_hash: row.tx_hash,
             created_at: row
@@ -838,6 +2284,7 @@ impl From<TransactionRow> for Transaction {
                 .parse::<DateTime<Utc>>()
                 .unwrap_or_else(|_| Utc::now()),
             executed_at: row.executed_at.and_then(|s| s.parse().ok()),
+            function_missing: row.function_missing,
         }
     }
 }
@@ -847,10 +2294,37 @@ fn string_to_tx_status(s: &str) -> TransactionStatus {
         "pending" => TransactionStatus::Pending,
         "success" => TransactionStatus::Success,
         "failed" => TransactionStatus::Failed,
+        "cancelled" => TransactionStatus::Cancelled,
         _ => TransactionStatus::Draft,
     }
 }
 
+fn run_status_to_string(status: &TxStatus) -> &'static str {
+    match status {
+        TxStatus::Pending => "pending",
+        TxStatus::Confirming => "confirming",
+        TxStatus::Success => "success",
+        TxStatus::Failed => "failed",
+        TxStatus::Replaced => "replaced",
+        TxStatus::Cancelled => "cancelled",
+    }
+}
+
+fn replacement_kind_to_string(kind: ReplacementKind) -> &'static str {
+    match kind {
+        ReplacementKind::SpeedUp => "speed_up",
+        ReplacementKind::Cancel => "cancel",
+    }
+}
+
+fn string_to_replacement_kind(s: &str) -> Option<ReplacementKind> {
+    match s {
+        "speed_up" => Some(ReplacementKind::SpeedUp),
+        "cancel" => Some(ReplacementKind::Cancel),
+        _ => None,
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct TransactionRunRow {
     id: String,
@@ -874,6 +2348,27 @@ struct TransactionRunRow {
     duration_ms: Option<i64>,
     #[sqlx(default)]
     ai_explanation: Option<String>,
+    #[sqlx(default)]
+    wallet_id: Option<String>,
+    #[sqlx(default)]
+    nonce: Option<i64>,
+    #[sqlx(default)]
+    replaces_run_id: Option<String>,
+    #[sqlx(default)]
+    replacement_kind: Option<String>,
+    #[sqlx(default)]
+    block_hash: Option<String>,
+    #[sqlx(default)]
+    history: Option<String>,
+    #[sqlx(default)]
+    revert: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingRunRow {
+    chain_id: String,
+    #[sqlx(flatten)]
+    run: TransactionRunRow,
 }
 
 impl From<TransactionRunRow> for TransactionRun {
@@ -882,6 +2377,9 @@ impl From<TransactionRunRow> for TransactionRun {
             "pending" => TxStatus::Pending,
             "success" => TxStatus::Success,
             "failed" => TxStatus::Failed,
+            "confirming" => TxStatus::Confirming,
+            "replaced" => TxStatus::Replaced,
+            "cancelled" => TxStatus::Cancelled,
             _ => TxStatus::Pending,
         };
 
@@ -927,6 +2425,809 @@ impl From<TransactionRunRow> for TransactionRun {
             finished_at,
             duration_ms: row.duration_ms.map(|n| n as u64),
             ai_explanation,
+            wallet_id: row.wallet_id,
+            nonce: row.nonce.map(|n| n as u64),
+            replaces_run_id: row.replaces_run_id,
+            replacement_kind: row.replacement_kind.as_deref().and_then(string_to_replacement_kind),
+            block_hash: row.block_hash,
+            history: row
+                .history
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            revert: row.revert.and_then(|s| serde_json::from_str(&s).ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mock::{mock_adapter, MockAdapter};
+    use crate::adapters::{AdapterRegistry, ExternalSigner, SoftwareSigner, TransactionAdapter};
+    use crate::services::{AuditService, TrackerService, VaultService};
+    use crate::types::{ChainConfig, FeePricing, WalletType};
+
+    /// Services on a test database whose Sepolia adapter is a mock, with a
+    /// funded wallet that signs with a stored key
+    struct Harness {
+        service: Arc<WorkspaceService>,
+        tracker: TrackerService,
+        adapter: Arc<MockAdapter>,
+        /// Mock registered for Solana devnet
+        solana: Arc<MockAdapter>,
+        workspace_id: String,
+        wallet: Wallet,
+    }
+
+    async fn harness() -> Harness {
+        let db = crate::db::test_db().await;
+        let adapter = Arc::new(mock_adapter());
+        let solana = Arc::new(MockAdapter::new(ChainConfig {
+            id: "solana-devnet".to_string(),
+            name: "Mock".to_string(),
+            ecosystem: Ecosystem::Solana,
+            rpc_url: String::new(),
+            native_currency: "SOL".to_string(),
+        }));
+        let registry = AdapterRegistry::new();
+        registry.insert("ethereum-sepolia", adapter.clone()).await;
+        registry.insert("solana-devnet", solana.clone()).await;
+        let chains = Arc::new(ChainService::new(db.clone(), Arc::new(tokio::sync::RwLock::new(registry))));
+        let vault = Arc::new(VaultService::new(db.clone()));
+        let audit = Arc::new(AuditService::new(db.clone()));
+        let wallets = Arc::new(WalletService::new(db.clone(), chains.clone(), vault.clone(), audit));
+        let service = Arc::new(WorkspaceService::new(db.clone(), chains.clone(), wallets));
+        let tracker = TrackerService::new(chains, service.clone());
+
+        vault.setup("correct horse").await.unwrap();
+        let key = format!("0x{}", "11".repeat(32));
+        let signer = SoftwareSigner::new(Ecosystem::Evm, &key).unwrap();
+        adapter.seed_wallet(&signer.address(), 10u128.pow(18));
+        let wallet = Wallet {
+            id: "deployer".to_string(),
+            chain_id: "ethereum-sepolia".to_string(),
+            name: "Deployer".to_string(),
+            address: signer.address(),
+            wallet_type: WalletType::Imported,
+            balance: None,
+            hd_wallet_id: None,
+            derivation_path: None,
+            created_at: Utc::now(),
+        };
+        sqlx::query(
+            "INSERT INTO wallets (id, chain_id, name, address, public_key, wallet_type, encrypted_private_key) VALUES (?, ?, ?, ?, '', 'imported', ?)",
+        )
+        .bind(&wallet.id)
+        .bind(&wallet.chain_id)
+        .bind(&wallet.name)
+        .bind(&wallet.address)
+        .bind(vault.encrypt(&key).unwrap())
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let workspace = service.create_workspace("ethereum-sepolia", "Vault", None).await.unwrap();
+        Harness {
+            service,
+            tracker,
+            adapter,
+            solana,
+            workspace_id: workspace.id,
+            wallet,
+        }
+    }
+
+    /// Broadcasts a transfer from the harness wallet and records it as pending
+    /// without waiting for a receipt
+    async fn pending_transfer(h: &Harness) -> TransactionRun {
+        let recipient = Wallet {
+            name: "Recipient".to_string(),
+            address: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
+            ..h.wallet.clone()
+        };
+        let signer = h.service.wallet_service.signer(&h.wallet.id).await.unwrap();
+        let send = h
+            .service
+            .broadcast_transfer(&h.workspace_id, h.adapter.as_ref(), signer.as_ref(), &h.wallet.id, &recipient, "1")
+            .await
+            .unwrap();
+        let deadline = tokio::time::Instant::now();
+        h.service
+            .record_send_until(send.run, h.adapter.as_ref(), send.sent, &send.request, None, deadline)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_overrides_round_trip_through_run_payload() {
+        let overrides = TransactionOverrides {
+            gas_limit: Some(90_000),
+            fee: Some(FeePricing::Eip1559 {
+                max_fee_per_gas: "30000000000".to_string(),
+                max_priority_fee_per_gas: "2000000000".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let payload = with_overrides(serde_json::json!(["0xabc", 5]), &overrides).unwrap();
+        assert_eq!(payload_overrides(&payload).unwrap(), Some(overrides));
+
+        let args = payload_args(&payload);
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0].name, "arg0");
+        assert_eq!(args[1].value, serde_json::json!(5));
+
+        // Nothing pinned leaves the payload untouched
+        let plain = serde_json::json!({ "amount": "1" });
+        assert_eq!(with_overrides(plain.clone(), &TransactionOverrides::default()).unwrap(), plain);
+        assert_eq!(payload_overrides(&plain).unwrap(), None);
+    }
+
+    #[test]
+    fn test_receipt_confirms_only_once_final() {
+        let adapter = mock_adapter();
+        let receipt = TransactionReceipt {
+            hash: "0x01".to_string(),
+            status: true,
+            block_number: 7,
+            block_hash: Some("0xaa".to_string()),
+            gas_used: 21000,
+            fee: None,
+            events: vec![],
+            logs: vec![],
+            revert: None,
+            contract_address: None,
+        };
+        let mut run = pending_run("tx", serde_json::Value::Null, "wallet");
+
+        apply_receipt(&mut run, &adapter, &receipt, None, false);
+        apply_receipt(&mut run, &adapter, &receipt, None, false);
+        assert_eq!(run.status, TxStatus::Confirming);
+        assert_eq!(run.block_hash.as_deref(), Some("0xaa"));
+
+        apply_receipt(&mut run, &adapter, &receipt, None, true);
+        assert_eq!(run.status, TxStatus::Success);
+
+        let notes: Vec<_> = run.history.iter().map(|h| h.note.as_str()).collect();
+        assert_eq!(notes, ["Included in block 7", "Confirmed in block 7"]);
+    }
+
+    #[test]
+    fn test_failed_receipt_keeps_revert_reason() {
+        let adapter = mock_adapter();
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend(ethers::abi::encode(&[ethers::abi::Token::String("Ownable: caller is not the owner".to_string())]));
+        let receipt = TransactionReceipt {
+            hash: "0x01".to_string(),
+            status: false,
+            block_number: 7,
+            block_hash: None,
+            gas_used: 30000,
+            fee: None,
+            events: vec![],
+            logs: vec![],
+            revert: Some(abi::revert_info(&data, &[])),
+            contract_address: None,
+        };
+        let mut run = pending_run("tx", serde_json::Value::Null, "wallet");
+
+        apply_receipt(&mut run, &adapter, &receipt, None, true);
+        assert_eq!(run.status, TxStatus::Failed);
+        assert_eq!(run.error_message.as_deref(), Some("Ownable: caller is not the owner"));
+        assert_eq!(run.revert.as_ref().and_then(|r| r.error_name.as_deref()), Some("Error"));
+    }
+
+    #[test]
+    fn test_deployment_receipt_records_contract_address() {
+        let adapter = mock_adapter();
+        let receipt = TransactionReceipt {
+            hash: "0x01".to_string(),
+            status: true,
+            block_number: 7,
+            block_hash: None,
+            gas_used: 500000,
+            fee: None,
+            events: vec![],
+            logs: vec![],
+            revert: None,
+            contract_address: Some("0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string()),
+        };
+        let mut run = pending_run("tx", serde_json::Value::Null, "wallet");
+        run.result = Some(serde_json::json!({ "bytecodeHash": "0xabcd" }));
+
+        apply_receipt(&mut run, &adapter, &receipt, None, true);
+        assert_eq!(deployed_address(&run), Some("0x5FbDB2315678afecb367f032d93F642f64180aa3"));
+        assert_eq!(run.result.as_ref().unwrap()["bytecodeHash"], "0xabcd");
+    }
+
+    #[tokio::test]
+    async fn test_tracker_completes_mined_run() {
+        let h = harness().await;
+        let run = pending_transfer(&h).await;
+        assert_eq!(run.status, TxStatus::Pending);
+        assert!(h.tracker.poll().await.unwrap().is_empty());
+
+        h.adapter.mine_transaction(run.tx_hash.as_deref().unwrap(), true);
+        let updated = h.tracker.poll().await.unwrap();
+        assert_eq!(updated.len(), 1);
+
+        let run = h.service.get_transaction_run(&run.id).await.unwrap();
+        assert_eq!(run.status, TxStatus::Success);
+        assert_eq!(run.block_number, Some(1000));
+        assert!(h.tracker.poll().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_reuses_nonce_and_replaces_original() {
+        let h = harness().await;
+        let original = pending_transfer(&h).await;
+        h.adapter.set_auto_mine(true);
+
+        let replacement = h.service.speed_up_transaction(&original.id).await.unwrap();
+        assert_eq!(replacement.status, TxStatus::Success);
+        assert_eq!(replacement.nonce, original.nonce);
+        assert_eq!(replacement.replaces_run_id.as_deref(), Some(original.id.as_str()));
+        assert_eq!(replacement.replacement_kind, Some(ReplacementKind::SpeedUp));
+        assert_eq!(replacement.payload, original.payload);
+
+        let original = h.service.get_transaction_run(&original.id).await.unwrap();
+        assert_eq!(original.status, TxStatus::Replaced);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_sends_zero_value_self_transfer() {
+        let h = harness().await;
+        let original = pending_transfer(&h).await;
+        h.adapter.set_auto_mine(true);
+
+        let replacement = h.service.cancel_transaction(&original.id).await.unwrap();
+        assert_eq!(replacement.nonce, original.nonce);
+        assert_eq!(replacement.replacement_kind, Some(ReplacementKind::Cancel));
+
+        let sent = h
+            .adapter
+            .get_transaction(replacement.tx_hash.as_deref().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(sent.to.as_deref(), Some(h.wallet.address.as_str()));
+        assert_eq!(sent.value.as_deref(), Some("0"));
+
+        let original = h.service.get_transaction_run(&original.id).await.unwrap();
+        assert_eq!(original.status, TxStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_tracker_reopens_run_dropped_by_reorg() {
+        let h = harness().await;
+        sqlx::query("UPDATE networks SET confirmation_depth = 3 WHERE id = 'ethereum-sepolia'")
+            .execute(&h.service.db)
+            .await
+            .unwrap();
+        let run = pending_transfer(&h).await;
+        let hash = run.tx_hash.clone().unwrap();
+
+        h.adapter.mine_transaction(&hash, true);
+        h.tracker.poll().await.unwrap();
+        let confirming = h.service.get_transaction_run(&run.id).await.unwrap();
+        assert_eq!(confirming.status, TxStatus::Confirming);
+
+        h.adapter.reorg_transaction(&hash);
+        h.tracker.poll().await.unwrap();
+        let reopened = h.service.get_transaction_run(&run.id).await.unwrap();
+        assert_eq!(reopened.status, TxStatus::Pending);
+        assert_eq!(reopened.block_number, None);
+        assert_eq!(
+            reopened.history.last().map(|entry| entry.note.as_str()),
+            Some("Receipt from block 1000 disappeared in a reorg")
+        );
+
+        h.adapter.mine_transaction(&hash, true);
+        h.adapter.advance_blocks(2);
+        h.tracker.poll().await.unwrap();
+        let settled = h.service.get_transaction_run(&run.id).await.unwrap();
+        assert_eq!(settled.status, TxStatus::Success);
+        assert_eq!(settled.block_number, Some(1000));
+    }
+
+    #[tokio::test]
+    async fn test_activate_deployment_switches_active_address() {
+        let h = harness().await;
+        let first = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let second = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512";
+        let contract = h
+            .service
+            .add_contract(&h.workspace_id, "Vault", Some(first), "abi", Some("[]"), None, None)
+            .await
+            .unwrap();
+        assert_eq!(h.service.active_address(&contract.id, "ethereum-sepolia").await.unwrap().as_deref(), Some(first));
+
+        h.service
+            .record_deployment(&ContractDeployment {
+                tx_hash: Some("0xabc".to_string()),
+                ..manual_deployment(&contract.id, "ethereum-sepolia", second)
+            })
+            .await
+            .unwrap();
+        assert_eq!(h.service.active_address(&contract.id, "ethereum-sepolia").await.unwrap().as_deref(), Some(second));
+
+        let deployments = h.service.list_contract_deployments(&contract.id).await.unwrap();
+        assert_eq!(deployments.len(), 2);
+        let original = deployments.iter().find(|d| d.address == first).unwrap();
+        assert!(!original.is_active);
+
+        let activated = h.service.activate_deployment(&original.id).await.unwrap();
+        assert!(activated.is_active);
+        assert_eq!(h.service.active_address(&contract.id, "ethereum-sepolia").await.unwrap().as_deref(), Some(first));
+        assert_eq!(h.service.get_contract(&contract.id).await.unwrap().deployed_address.as_deref(), Some(first));
+        let active: Vec<_> = h
+            .service
+            .list_contract_deployments(&contract.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|d| d.is_active)
+            .collect();
+        assert_eq!(active.len(), 1);
+
+        // Other networks keep their own active deployment
+        assert_eq!(h.service.active_address(&contract.id, "base-sepolia").await.unwrap(), None);
+
+        // The same deploy transaction can't be registered twice
+        let duplicate = h
+            .service
+            .record_deployment(&ContractDeployment {
+                tx_hash: Some("0xabc".to_string()),
+                ..manual_deployment(&contract.id, "ethereum-sepolia", second)
+            })
+            .await;
+        assert!(matches!(duplicate, Err(CocoError::Database(_))));
+        assert_eq!(h.service.list_contract_deployments(&contract.id).await.unwrap().len(), 2);
+        assert_eq!(h.service.active_address(&contract.id, "ethereum-sepolia").await.unwrap().as_deref(), Some(first));
+    }
+
+    const STORE_ABI: &str = r#"[
+        {"type": "function", "name": "store", "inputs": [{"name": "value", "type": "uint256"}], "outputs": [], "stateMutability": "nonpayable"},
+        {"type": "event", "name": "Stored", "inputs": [{"name": "value", "type": "uint256", "indexed": false}], "anonymous": false}
+    ]"#;
+
+    #[tokio::test]
+    async fn test_execute_sends_to_active_deployment_and_records_receipt() {
+        let h = harness().await;
+        let old = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let active = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512";
+        let contract = h
+            .service
+            .add_contract(&h.workspace_id, "Store", Some(old), "abi", Some(STORE_ABI), None, None)
+            .await
+            .unwrap();
+        h.service
+            .record_deployment(&manual_deployment(&contract.id, "ethereum-sepolia", active))
+            .await
+            .unwrap();
+        let transaction = h
+            .service
+            .create_transaction(&h.workspace_id, "Store 42", Some(&contract.id), Some("store"))
+            .await
+            .unwrap();
+
+        let topic = format!("0x{}", hex::encode(ethers::utils::keccak256("Stored(uint256)")));
+        let forty_two = ethers::abi::encode(&[ethers::abi::Token::Uint(42u64.into())]);
+        h.adapter.emit_on_next_send(vec![crate::types::Log {
+            address: active.to_string(),
+            topics: vec![topic],
+            data: forty_two.clone(),
+        }]);
+        h.adapter.set_auto_mine(true);
+
+        let run = h
+            .service
+            .execute_transaction(&transaction.id, serde_json::json!({"value": "42"}), &h.wallet.id)
+            .await
+            .unwrap();
+        assert_eq!(run.status, TxStatus::Success);
+
+        let hash = run.tx_hash.clone().unwrap();
+        let sent = h.adapter.get_transaction(&hash).await.unwrap();
+        assert_eq!(sent.to.as_deref(), Some(active));
+        let selector = &ethers::utils::keccak256("store(uint256)")[..4];
+        assert_eq!(sent.data, Some([selector, forty_two.as_slice()].concat()));
+
+        let saved = h.service.get_transaction_run(&run.id).await.unwrap();
+        assert_eq!(saved.tx_hash.as_deref(), Some(hash.as_str()));
+        assert_eq!(saved.nonce, Some(0));
+        assert_eq!(saved.gas_used, Some(21000));
+        let events = saved.events.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "Stored");
+        assert_eq!(events[0].args["value"], "42");
+    }
+
+    #[tokio::test]
+    async fn test_same_transaction_cannot_execute_twice_at_once() {
+        let h = harness().await;
+        let contract = h
+            .service
+            .add_contract(
+                &h.workspace_id,
+                "Store",
+                Some("0x5FbDB2315678afecb367f032d93F642f64180aa3"),
+                "abi",
+                Some(STORE_ABI),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let transaction = h
+            .service
+            .create_transaction(&h.workspace_id, "Store 1", Some(&contract.id), Some("store"))
+            .await
+            .unwrap();
+        h.adapter.set_auto_mine(true);
+
+        let payload = serde_json::json!({"value": "1"});
+        let (first, second) = tokio::join!(
+            h.service.execute_transaction(&transaction.id, payload.clone(), &h.wallet.id),
+            h.service.execute_transaction(&transaction.id, payload.clone(), &h.wallet.id),
+        );
+        assert!(first.is_ok());
+        assert!(matches!(second, Err(CocoError::Validation(ref m)) if m.contains("already being executed")));
+        assert_eq!(h.adapter.sent_nonces(&h.wallet.address), vec![0]);
+
+        // The guard is released once the first run is recorded
+        h.service
+            .execute_transaction(&transaction.id, payload, &h.wallet.id)
+            .await
+            .unwrap();
+        assert_eq!(h.adapter.sent_nonces(&h.wallet.address), vec![0, 1]);
+    }
+
+    /// Adds a keyless wallet on Sepolia and gives it `balance` on the mock node
+    async fn add_wallet(h: &Harness, id: &str, address: &str, balance: u128) {
+        sqlx::query(
+            "INSERT INTO wallets (id, chain_id, name, address, public_key, wallet_type) VALUES (?, 'ethereum-sepolia', ?, ?, '', 'watch')",
+        )
+        .bind(id)
+        .bind(id)
+        .bind(address)
+        .execute(&h.service.db)
+        .await
+        .unwrap();
+        h.adapter.seed_wallet(address, balance);
+    }
+
+    /// Recipient and amount of each transfer run in the workspace
+    async fn transfers(h: &Harness) -> Vec<(String, String, TxStatus)> {
+        let mut transfers = Vec::new();
+        for transaction in h.service.list_transactions(&h.workspace_id).await.unwrap() {
+            for run in h.service.list_transaction_runs(&transaction.id).await.unwrap() {
+                let payload = run.payload.unwrap();
+                transfers.push((
+                    payload["to"].as_str().unwrap().to_string(),
+                    payload["amount"].as_str().unwrap().to_string(),
+                    run.status,
+                ));
+            }
+        }
+        transfers.sort_by(|a, b| a.0.cmp(&b.0));
+        transfers
+    }
+
+    /// Removes the wallets `test_db` seeds on Sepolia so only the test's own remain
+    async fn clear_seeded_wallets(h: &Harness) {
+        sqlx::query("DELETE FROM wallets WHERE chain_id = 'ethereum-sepolia' AND id != ?")
+            .bind(&h.wallet.id)
+            .execute(&h.service.db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transfer_native_records_value_and_recipient() {
+        let h = harness().await;
+        let recipient = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        clear_seeded_wallets(&h).await;
+        add_wallet(&h, "bob", recipient, 0).await;
+        h.adapter.set_auto_mine(true);
+
+        let run = h
+            .service
+            .transfer_native(&h.workspace_id, &h.wallet.id, "bob", "500")
+            .await
+            .unwrap();
+        assert_eq!(run.status, TxStatus::Success);
+        assert_eq!(run.wallet_id.as_deref(), Some(h.wallet.id.as_str()));
+
+        let sent = h.adapter.get_transaction(run.tx_hash.as_deref().unwrap()).await.unwrap();
+        assert_eq!(sent.to.as_deref(), Some(recipient));
+        assert_eq!(sent.value.as_deref(), Some("500"));
+
+        let transaction = h.service.get_transaction(&run.transaction_id).await.unwrap();
+        assert_eq!(transaction.value.as_deref(), Some("500"));
+        assert_eq!(transfers(&h).await, vec![(recipient.to_string(), "500".to_string(), TxStatus::Success)]);
+
+        assert!(matches!(
+            h.service.transfer_native(&h.workspace_id, &h.wallet.id, "bob", "1.5").await,
+            Err(CocoError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fund_wallets_tops_up_only_wallets_below_target() {
+        let h = harness().await;
+        clear_seeded_wallets(&h).await;
+        let (empty, low, full) = (
+            "0x0000000000000000000000000000000000000001",
+            "0x0000000000000000000000000000000000000002",
+            "0x0000000000000000000000000000000000000003",
+        );
+        add_wallet(&h, "empty", empty, 0).await;
+        add_wallet(&h, "low", low, 300).await;
+        add_wallet(&h, "full", full, 1000).await;
+        h.adapter.set_auto_mine(true);
+
+        let runs = h.service.fund_wallets(&h.workspace_id, &h.wallet.id, "1000").await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| run.status == TxStatus::Success));
+        // The funder itself is never sent to, and the full wallet is skipped
+        assert_eq!(
+            transfers(&h).await,
+            vec![
+                (empty.to_string(), "1000".to_string(), TxStatus::Success),
+                (low.to_string(), "700".to_string(), TxStatus::Success),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_wallets_records_broadcasts_before_a_failed_send() {
+        let h = harness().await;
+        clear_seeded_wallets(&h).await;
+        add_wallet(&h, "alice", "0x0000000000000000000000000000000000000001", 0).await;
+        add_wallet(&h, "bob", "0x0000000000000000000000000000000000000002", 0).await;
+        h.adapter.set_auto_mine(true);
+        h.adapter.fail_send_after(1, "insufficient funds for gas");
+
+        // The failed send ends the round and comes back as a failed run
+        let runs = h.service.fund_wallets(&h.workspace_id, &h.wallet.id, "1000").await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].status, TxStatus::Success);
+        assert_eq!(runs[1].status, TxStatus::Failed);
+        assert!(runs[1].error_message.as_deref().unwrap().contains("insufficient funds"));
+
+        // Both broadcasts are recorded: the one that went out and the one that failed
+        let statuses: Vec<_> = transfers(&h).await.into_iter().map(|(_, _, status)| status).collect();
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses.contains(&TxStatus::Success) && statuses.contains(&TxStatus::Failed));
+        assert_eq!(h.adapter.sent_nonces(&h.wallet.address), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_keyless_wallet_is_watch_only_and_cannot_send() {
+        let h = harness().await;
+        let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        let watched = h
+            .service
+            .wallet_service
+            .import_wallet("ethereum-sepolia", "Treasury", address, None, WalletType::Imported, None)
+            .await
+            .unwrap();
+        assert_eq!(watched.wallet_type, WalletType::Watch);
+        let (wallet_type, key): (String, Option<Vec<u8>>) =
+            sqlx::query_as("SELECT wallet_type, encrypted_private_key FROM wallets WHERE id = ?")
+                .bind(&watched.id)
+                .fetch_one(&h.service.db)
+                .await
+                .unwrap();
+        assert_eq!((wallet_type.as_str(), key), ("watch", None));
+
+        let contract = h
+            .service
+            .add_contract(&h.workspace_id, "Store", Some(address), "abi", Some(STORE_ABI), None, None)
+            .await
+            .unwrap();
+        let transaction = h
+            .service
+            .create_transaction(&h.workspace_id, "Store 1", Some(&contract.id), Some("store"))
+            .await
+            .unwrap();
+        let transactions_before = h.service.list_transactions(&h.workspace_id).await.unwrap().len();
+
+        let executed = h
+            .service
+            .execute_transaction(&transaction.id, serde_json::json!({"value": "1"}), &watched.id)
+            .await;
+        assert!(matches!(executed, Err(CocoError::Validation(ref m)) if m.contains("watch-only")));
+        let transferred = h
+            .service
+            .transfer_native(&h.workspace_id, &watched.id, &h.wallet.id, "1")
+            .await;
+        assert!(matches!(transferred, Err(CocoError::Validation(ref m)) if m.contains("watch-only")));
+
+        // Nothing was sent or recorded
+        assert!(h.adapter.sent_nonces(address).is_empty());
+        assert!(h.service.list_transaction_runs(&transaction.id).await.unwrap().is_empty());
+        assert_eq!(h.service.list_transactions(&h.workspace_id).await.unwrap().len(), transactions_before);
+    }
+
+    /// Empty project directory for discovery tests
+    fn temp_project() -> PathBuf {
+        let project = std::env::temp_dir().join(format!("coco-workspace-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&project).unwrap();
+        project
+    }
+
+    /// Writes a Foundry artifact for `name` compiled from `source`, with one
+    /// function per signature in `functions`, e.g. `transfer(address,uint256)`
+    fn write_artifact(project: &Path, artifact: &str, source: &str, name: &str, functions: &[&str]) {
+        let abi: Vec<_> = functions
+            .iter()
+            .map(|signature| {
+                let (function, inputs) = signature.trim_end_matches(')').split_once('(').unwrap();
+                let inputs: Vec<_> = inputs
+                    .split(',')
+                    .filter(|t| !t.is_empty())
+                    .map(|t| serde_json::json!({"name": "", "type": t}))
+                    .collect();
+                serde_json::json!({"type": "function", "name": function, "inputs": inputs, "outputs": [], "stateMutability": "nonpayable"})
+            })
+            .collect();
+        let path = project.join(artifact);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            serde_json::json!({
+                "abi": abi,
+                "bytecode": {"object": "0x6080"},
+                "metadata": {"settings": {"compilationTarget": {source: name}}},
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    /// Adds a contract row the way an earlier version saved it, with `path` as recorded then
+    async fn legacy_contract(h: &Harness, workspace_id: &str, name: &str, path: &str) -> String {
+        let contract = h
+            .service
+            .add_contract(workspace_id, name, None, "abi", Some("[]"), None, None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE contracts SET path = ? WHERE id = ?")
+            .bind(path)
+            .bind(&contract.id)
+            .execute(&h.service.db)
+            .await
+            .unwrap();
+        contract.id
+    }
+
+    #[tokio::test]
+    async fn test_rediscovery_updates_absolute_path_rows_in_place() {
+        let h = harness().await;
+        let project = temp_project();
+        let oz_source = "lib/openzeppelin-contracts/contracts/token/ERC20/IERC20.sol";
+        write_artifact(&project, "out/Vault.sol/Vault.json", "src/Vault.sol", "Vault", &["deposit()"]);
+        write_artifact(&project, "out/IERC20.sol/IERC20.json", "src/interfaces/IERC20.sol", "IERC20", &["transfer(address,uint256)"]);
+        write_artifact(&project, "out/oz/IERC20.sol/IERC20.json", oz_source, "IERC20", &["transfer(address,uint256)"]);
+        let workspace = h
+            .service
+            .create_workspace("ethereum-sepolia", "Project", project.to_str())
+            .await
+            .unwrap();
+
+        // Earlier scans stored absolute paths; the pathless IERC20 was added by hand
+        let absolute = |source: &str| project.join(source).to_string_lossy().to_string();
+        let vault = legacy_contract(&h, &workspace.id, "Vault", &absolute("src/Vault.sol")).await;
+        let oz = legacy_contract(&h, &workspace.id, "IERC20", &absolute(oz_source)).await;
+        let manual = legacy_contract(&h, &workspace.id, "IERC20", "").await;
+
+        for _ in 0..2 {
+            h.service.discover_contracts(&workspace.id).await.unwrap();
+            let contracts = h.service.list_contracts(&workspace.id).await.unwrap();
+            assert_eq!(contracts.len(), 3);
+            let path = |id: &str| contracts.iter().find(|c| c.id == id).unwrap().path.clone();
+            assert_eq!(path(&vault), "src/Vault.sol");
+            assert_eq!(path(&oz), oz_source);
+            assert_eq!(path(&manual), "src/interfaces/IERC20.sol");
         }
+
+        std::fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_reports_function_changes_and_flags_missing_functions() {
+        let h = harness().await;
+        let project = temp_project();
+        let artifact = |functions: &[&str]| {
+            write_artifact(&project, "out/Vault.sol/Vault.json", "src/Vault.sol", "Vault", functions)
+        };
+        artifact(&["deposit(uint256)", "withdraw(uint256)", "pause()"]);
+        let workspace = h
+            .service
+            .create_workspace("ethereum-sepolia", "Project", project.to_str())
+            .await
+            .unwrap();
+
+        let first = h.service.sync_contracts(&workspace.id).await.unwrap();
+        assert_eq!(first.contracts.len(), 1);
+        let vault = first.contracts[0].contract_id.clone();
+        let mut added = first.contracts[0].functions.added.clone();
+        added.sort();
+        assert_eq!(added, vec!["deposit", "pause", "withdraw"]);
+        let pause = h
+            .service
+            .create_transaction(&workspace.id, "Pause", Some(&vault), Some("pause"))
+            .await
+            .unwrap();
+
+        artifact(&["deposit(uint256,address)", "withdraw(uint256)", "sweep()"]);
+        let sync = h.service.sync_contracts(&workspace.id).await.unwrap();
+        assert_eq!(sync.contracts.len(), 1);
+        assert_eq!(sync.contracts[0].contract_id, vault);
+        assert_eq!(sync.contracts[0].functions.added, vec!["sweep"]);
+        assert_eq!(sync.contracts[0].functions.removed, vec!["pause"]);
+        assert_eq!(sync.contracts[0].functions.changed, vec!["deposit"]);
+        assert_eq!(sync.stale_transactions, vec![pause.id.clone()]);
+        assert!(h.service.get_transaction(&pause.id).await.unwrap().function_missing);
+
+        artifact(&["deposit(uint256,address)", "withdraw(uint256)", "sweep()", "pause()"]);
+        let sync = h.service.sync_contracts(&workspace.id).await.unwrap();
+        assert_eq!(sync.contracts[0].functions.added, vec!["pause"]);
+        assert!(sync.stale_transactions.is_empty());
+        assert!(!h.service.get_transaction(&pause.id).await.unwrap().function_missing);
+
+        std::fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_anchor_program_deploys_to_its_keypair_address() {
+        let h = harness().await;
+        h.solana.set_auto_mine(true);
+        let key = format!("0x{}", "22".repeat(32));
+        let payer = Arc::new(SoftwareSigner::new(Ecosystem::Solana, &key).unwrap());
+        let wallets = &h.service.wallet_service;
+        let ledger = wallets
+            .import_wallet("solana-devnet", "Payer", &payer.address(), None, WalletType::Ledger, None)
+            .await
+            .unwrap();
+        wallets.attach_signer(&ledger.id, payer.clone()).await.unwrap();
+
+        let project = temp_project();
+        let deploy_dir = project.join("target").join("deploy");
+        std::fs::create_dir_all(&deploy_dir).unwrap();
+        let binary = b"\x7fELF program".to_vec();
+        std::fs::write(deploy_dir.join("counter.so"), &binary).unwrap();
+        let workspace = h
+            .service
+            .create_workspace("solana-devnet", "Counter", Some(project.to_str().unwrap()))
+            .await
+            .unwrap();
+        let program = h
+            .service
+            .add_contract(&workspace.id, "counter", None, "idl", None, Some(r#"{"name": "counter", "instructions": []}"#), None)
+            .await
+            .unwrap();
+
+        // The binary alone doesn't say where to deploy
+        let missing = h.service.deploy_contract(&program.id, &ledger.id, serde_json::Value::Null).await;
+        assert!(matches!(missing, Err(CocoError::Validation(ref m)) if m.contains("anchor build")));
+
+        let program_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let keypair = serde_json::to_string(&program_key.to_keypair_bytes().to_vec()).unwrap();
+        std::fs::write(deploy_dir.join("counter-keypair.json"), keypair).unwrap();
+        let program_id = bs58::encode(program_key.verifying_key().to_bytes()).into_string();
+
+        let deployed = h.service.deploy_contract(&program.id, &ledger.id, serde_json::Value::Null).await.unwrap();
+        assert_eq!(deployed.run.status, TxStatus::Success);
+        assert_eq!(deployed_address(&deployed.run), Some(program_id.as_str()));
+        assert_eq!(deployed.contract.deployed_address.as_deref(), Some(program_id.as_str()));
+
+        let sent = h.solana.get_transaction(deployed.run.tx_hash.as_deref().unwrap()).await.unwrap();
+        assert_eq!(sent.from, payer.address());
+        assert_eq!(sent.data, Some(binary));
+        assert_eq!(h.solana.sent_nonces(&payer.address()).len(), 1);
+
+        std::fs::remove_dir_all(&project).unwrap();
     }
 }
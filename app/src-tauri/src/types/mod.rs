@@ -22,6 +22,10 @@ pub enum WalletType {
     Local,
     Ledger,
     Imported,
+    /// Derived from an HD wallet's mnemonic
+    Hd,
+    /// Address only, tracked for balances and history but unable to sign
+    Watch,
 }
 
 impl Default for WalletType {
@@ -54,6 +58,7 @@ pub enum TransactionStatus {
     Pending,
     Success,
     Failed,
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -141,6 +146,9 @@ pub struct Chain {
     pub network_type: NetworkType,
     pub is_custom: bool,
     pub icon_id: Option<String>,
+    /// Network whose settings (e.g. confirmation depth) apply to the chain
+    #[serde(default)]
+    pub network_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,9 +160,39 @@ pub struct Wallet {
     pub address: String,
     pub wallet_type: WalletType,
     pub balance: Option<String>,
+    /// HD wallet this account was derived from
+    pub hd_wallet_id: Option<String>,
+    pub derivation_path: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A stored BIP-39 mnemonic that chain accounts are derived from by index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdWallet {
+    pub id: String,
+    pub name: String,
     pub created_at: DateTime<Utc>,
 }
 
+/// Outcome of importing a wallet bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBundleImport {
+    pub imported: Vec<Wallet>,
+    pub skipped: Vec<SkippedWallet>,
+}
+
+/// A bundled wallet that was not imported, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedWallet {
+    pub name: String,
+    pub chain_id: String,
+    pub address: String,
+    pub reason: String,
+}
+
 /// Wallet with chain name for display in reuse lists
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -201,7 +239,36 @@ pub struct Contract {
     /// Aptos Move definition JSON (for Aptos contracts)
     pub move_definition: Option<String>,
     pub bytecode: Option<String>,
+    /// Address of the active deployment on the workspace's network
     pub deployed_address: Option<String>,
+    /// Compiler that built the contract, from its build artifact
+    #[serde(default)]
+    pub compiler_version: Option<String>,
+    /// Has no bytecode to deploy: an interface or abstract contract
+    #[serde(default)]
+    pub is_interface: bool,
+    /// Comes from a library rather than the project's own sources
+    #[serde(default)]
+    pub is_dependency: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where a contract is deployed on one network. Of a contract's deployments on
+/// a network, transactions target the active one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractDeployment {
+    pub id: String,
+    pub contract_id: String,
+    pub chain_id: String,
+    pub address: String,
+    /// Wallet that sent the deployment; `None` for addresses entered by hand
+    pub deployer_wallet_id: Option<String>,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<u64>,
+    /// Keccak-256 of the creation bytecode, 0x-prefixed
+    pub bytecode_hash: Option<String>,
+    pub is_active: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -239,6 +306,11 @@ pub struct Transaction {
     pub tx_hash: Option<String>,
     pub created_at: DateTime<Utc>,
     pub executed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub overrides: TransactionOverrides,
+    /// The contract's ABI no longer has `function_name`, as of the last re-sync
+    #[serde(default)]
+    pub function_missing: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -267,6 +339,9 @@ pub struct WalletData {
     pub address: String,
     pub public_key: String,
     pub private_key_encrypted: Option<Vec<u8>>,
+    /// Decrypted hex private key, only held in memory while signing
+    #[serde(skip)]
+    pub private_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -284,17 +359,165 @@ pub struct TokenBalance {
     pub balance: String,
 }
 
+/// Whether the secret vault has a passphrase yet and whether its key is loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultStatus {
+    pub initialized: bool,
+    pub unlocked: bool,
+}
+
+/// Kind of secret recorded in the access log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretKind {
+    WalletKey,
+    EnvVar,
+}
+
+/// One plaintext secret handed to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretAccessEntry {
+    pub id: i64,
+    pub kind: SecretKind,
+    pub wallet_id: Option<String>,
+    /// Address at the time of the reveal, kept after the wallet is deleted
+    pub wallet_address: Option<String>,
+    pub env_key: Option<String>,
+    pub workspace_id: Option<String>,
+    /// Command that revealed the secret
+    pub command: String,
+    pub accessed_at: DateTime<Utc>,
+}
+
+/// Filters for listing the secret access log; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretAccessFilter {
+    pub kind: Option<SecretKind>,
+    pub wallet_id: Option<String>,
+    pub workspace_id: Option<String>,
+    pub env_key: Option<String>,
+    pub command: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
+/// Number of secrets re-encrypted by a key rotation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationReport {
+    pub wallets: u32,
+    pub hd_wallets: u32,
+    pub env_vars: u32,
+}
+
+/// A wallet's balances as seen at one refresh
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBalanceSnapshot {
+    pub id: String,
+    pub wallet_id: String,
+    pub chain_id: String,
+    /// Native balance in the chain's smallest unit
+    pub native: String,
+    pub native_decimals: u8,
+    pub native_symbol: String,
+    pub tokens: Vec<TokenBalance>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransactionRequest {
+    /// Sender for simulations and estimates; sends always use the signer's address
+    #[serde(default)]
+    pub from: Option<String>,
     pub to: Option<String>,
     pub value: Option<String>,
     pub data: Option<Vec<u8>>,
+    /// Accounts touched by the instruction (Solana only)
+    #[serde(default)]
+    pub accounts: Vec<InstructionAccount>,
+    /// Nonce (EVM) or sequence number (Aptos); the node's pending value when unset
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    /// Gas limit (EVM), compute unit limit (Solana) or max gas amount (Aptos);
+    /// estimated when unset
+    #[serde(default)]
+    pub gas_limit: Option<u64>,
+    /// Fee pricing; the node's current price when unset
+    #[serde(default)]
+    pub fee: Option<FeePricing>,
+    /// EIP-2930 access list (EVM only)
+    #[serde(default)]
+    pub access_list: Vec<AccessListItem>,
+}
+
+/// How a transaction pays for execution, in the chain's smallest unit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeePricing {
+    /// Single price per unit of gas: EVM legacy gas price or Aptos gas unit price
+    Legacy { gas_price: String },
+    /// EVM EIP-1559 fee caps, in wei per gas
+    Eip1559 {
+        max_fee_per_gas: String,
+        max_priority_fee_per_gas: String,
+    },
+    /// Solana priority fee, in micro-lamports per compute unit
+    ComputeUnit { micro_lamports_per_unit: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: String,
+    pub storage_keys: Vec<String>,
+}
+
+/// Send settings a saved transaction pins instead of leaving them to the node
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOverrides {
+    pub gas_limit: Option<u64>,
+    pub fee: Option<FeePricing>,
+    pub nonce: Option<u64>,
+    #[serde(default)]
+    pub access_list: Vec<AccessListItem>,
+}
+
+impl TransactionOverrides {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Copies the pinned settings onto a request
+    pub fn apply(&self, request: &mut TransactionRequest) {
+        request.gas_limit = self.gas_limit.or(request.gas_limit);
+        request.fee = self.fee.clone().or(request.fee.take());
+        request.nonce = self.nonce.or(request.nonce);
+        if !self.access_list.is_empty() {
+            request.access_list = self.access_list.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstructionAccount {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionResult {
     pub hash: String,
     pub status: TransactionStatus,
+    /// Nonce or sequence number the transaction was signed with
+    #[serde(default)]
+    pub nonce: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -313,8 +536,37 @@ pub struct TransactionReceipt {
     pub hash: String,
     pub status: bool,
     pub block_number: u64,
+    /// Hash of the including block, used to notice reorgs (EVM only)
+    #[serde(default)]
+    pub block_hash: Option<String>,
     pub gas_used: u64,
+    /// Total fee paid in the chain's smallest unit, when the node reports it
+    #[serde(default)]
+    pub fee: Option<String>,
     pub events: Vec<DecodedEvent>,
+    /// Raw logs emitted by the transaction, decoded against an ABI by the caller
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// Why the transaction failed, when it did
+    #[serde(default)]
+    pub revert: Option<RevertInfo>,
+    /// Address of the contract a creation transaction deployed (EVM only)
+    #[serde(default)]
+    pub contract_address: Option<String>,
+}
+
+/// Why a transaction or simulation failed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertInfo {
+    /// Raw revert data as 0x-prefixed hex (EVM only)
+    pub data: Option<String>,
+    /// `Error`, `Panic` or the custom error's name, when the data was recognised
+    pub error_name: Option<String>,
+    #[serde(default)]
+    pub args: Vec<ContractArg>,
+    /// Readable summary, e.g. `InsufficientAllowance(0x70997970…, 10, 20)`
+    pub message: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -349,20 +601,70 @@ pub struct TransactionRun {
     pub finished_at: Option<DateTime<Utc>>,
     pub duration_ms: Option<u64>,
     pub ai_explanation: Option<AIExplanation>,
+    /// Wallet that signed the run, needed to speed it up or cancel it
+    #[serde(default)]
+    pub wallet_id: Option<String>,
+    /// Nonce (EVM) or sequence number (Aptos) the transaction was sent with
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    /// Run this one was sent to replace
+    #[serde(default)]
+    pub replaces_run_id: Option<String>,
+    #[serde(default)]
+    pub replacement_kind: Option<ReplacementKind>,
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    /// Status changes after broadcast, oldest first
+    #[serde(default)]
+    pub history: Vec<RunHistoryEntry>,
+    #[serde(default)]
+    pub revert: Option<RevertInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunHistoryEntry {
+    pub at: DateTime<Utc>,
+    pub status: TxStatus,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TxStatus {
     Pending,
+    /// Mined, but not yet under the network's confirmation depth
+    Confirming,
     Success,
     Failed,
+    /// Another run with the same nonce confirmed first
+    Replaced,
+    /// A cancellation with the same nonce confirmed first
+    Cancelled,
+}
+
+/// Why a run was sent with the same nonce as an earlier one
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementKind {
+    /// Same transaction with a higher fee
+    SpeedUp,
+    /// Zero-value transfer to self with a higher fee
+    Cancel,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeeEstimate {
     pub gas_limit: u64,
-    pub gas_price: String,
+    pub slow: FeeTier,
+    pub normal: FeeTier,
+    pub fast: FeeTier,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeTier {
+    pub pricing: FeePricing,
+    /// Most the transaction can cost at this tier, in the smallest unit
     pub total_fee: String,
 }
 
@@ -372,6 +674,8 @@ pub struct SimulationResult {
     pub return_data: Vec<u8>,
     pub gas_used: u64,
     pub logs: Vec<Log>,
+    #[serde(default)]
+    pub revert: Option<RevertInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -381,7 +685,7 @@ pub struct Log {
     pub data: Vec<u8>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContractArg {
     pub name: String,
     pub value: serde_json::Value,
@@ -393,20 +697,79 @@ pub struct ContractCallResult {
     pub decoded: serde_json::Value,
 }
 
+/// A contract deployment: the contract (with its new address once mined) and the run that deployed it
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DeploymentResult {
-    pub address: String,
-    pub transaction_hash: String,
-    pub block_number: u64,
+    pub contract: Contract,
+    pub run: TransactionRun,
+}
+
+/// Function names that differ between two versions of a contract's ABI
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Same name, but a different signature, mutability or input names
+    pub changed: Vec<String>,
+}
+
+impl FunctionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// How a re-sync changed one contract's functions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractChanges {
+    pub contract_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub functions: FunctionDiff,
+}
+
+/// Result of re-running discovery on a workspace, sent to the frontend as the
+/// `contracts-synced` event while the workspace is watched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractSync {
+    pub workspace_id: String,
+    /// Contracts whose functions changed, including newly discovered ones
+    pub contracts: Vec<ContractChanges>,
+    /// Saved transactions calling a function their contract no longer has
+    pub stale_transactions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoveredContract {
     pub name: String,
+    /// Source file the contract was compiled from, relative to the project,
+    /// or its build artifact when the source is unknown
     pub path: String,
     pub interface_type: InterfaceType,
+    /// Has no bytecode to deploy: an interface or abstract contract
     pub is_interface: bool,
+    /// Comes from a library (`lib/`, `node_modules`) rather than the project
     pub is_dependency: bool,
+    #[serde(default)]
+    pub abi: Option<String>,
+    /// Creation bytecode as 0x-prefixed hex
+    #[serde(default)]
+    pub bytecode: Option<String>,
+    #[serde(default)]
+    pub compiler_version: Option<String>,
+    /// Anchor IDL JSON
+    #[serde(default)]
+    pub idl: Option<String>,
+    /// Move module interface in the `moveDefinition` shape
+    #[serde(default)]
+    pub move_definition: Option<String>,
+    /// Address the project's config declares the program or module at
+    #[serde(default)]
+    pub deployed_address: Option<String>,
 }
 
 // Chain config for adapters
@@ -436,6 +799,9 @@ pub struct Blockchain {
     pub networks: Vec<Network>,
 }
 
+/// Confirmation depth for networks that don't set one: final once mined
+pub const DEFAULT_CONFIRMATION_DEPTH: u32 = 1;
+
 /// Network entity (belongs to a blockchain)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -457,6 +823,8 @@ pub struct Network {
     pub currency_symbol: String,
     pub currency_decimals: u8,
     pub is_default: bool,
+    /// Blocks a transaction must be buried under before its run is final
+    pub confirmation_depth: u32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -818,6 +1186,7 @@ pub struct CreateNetworkInput {
     pub currency_decimals: Option<u8>,
     #[serde(default)]
     pub is_default: bool,
+    pub confirmation_depth: Option<u32>,
 }
 
 /// Input for updating a network
@@ -831,4 +1200,5 @@ pub struct UpdateNetworkInput {
     pub explorer_api_key: Option<String>,
     pub faucet_url: Option<String>,
     pub is_default: Option<bool>,
+    pub confirmation_depth: Option<u32>,
 }
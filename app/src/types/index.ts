@@ -113,6 +113,7 @@ export interface Chain {
   networkType: NetworkType;  // 'mainnet' | 'testnet' | 'devnet' | 'custom'
   isCustom: boolean;         // true for user-created chains
   iconId?: string;           // Reference to icon component
+  networkId?: string;        // Network whose settings (e.g. confirmation depth) apply
 }
 
 export interface Wallet {